@@ -6,10 +6,12 @@ use proc_macro::TokenStream;
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, quote_spanned, TokenStreamExt};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
     parse_macro_input, parse_quote, Error, Expr, ExprLit, ExprPath, FnArg, Ident, ItemFn,
-    ItemStruct, Lit, Pat, Visibility,
+    ItemStruct, Lit, Meta, MetaNameValue, Pat, Token, Visibility,
 };
 
 macro_rules! err {
@@ -144,10 +146,49 @@ fn get_function_arg_name(f: &ItemFn, arg_index: usize, errors: &mut TokenStream2
 /// }
 /// ```
 ///
+/// # Drivers
+///
+/// Drivers use `#[entry(driver)]`. A driver stays resident after its entry
+/// point returns [`Status::SUCCESS`], while an application is unloaded, so
+/// in this mode the entry point returns [`Status::UNSUPPORTED`] without
+/// running if the image looks like an application. This is a heuristic:
+/// it checks whether the code of the image was loaded as
+/// [`MemoryType::LOADER_CODE`], which is what firmware uses for images
+/// with the EFI application subsystem.
+///
+/// Use `#[entry(driver, unload = path)]` to make the driver unloadable.
+/// `path` is a function with the signature
+/// `fn(&BootServices) -> uefi::Result`, which is registered with
+/// [`BootServices::set_unload_handler`] before the entry point runs, and
+/// must undo what the driver installed. This requires the `alloc` feature
+/// of the `uefi` crate. Without `unload`, the driver can still register a
+/// handler itself.
+///
+/// ```no_run
+/// #![no_main]
+///
+/// use uefi::prelude::*;
+///
+/// fn unload(bt: &BootServices) -> uefi::Result {
+///     // Uninstall the protocols of the driver here.
+///     Ok(())
+/// }
+///
+/// #[entry(driver, unload = unload)]
+/// fn main(image: Handle, st: SystemTable<Boot>) -> Status {
+///     // Install the protocols of the driver here.
+///     Status::SUCCESS
+/// }
+/// ```
+///
 /// [`Handle`]: https://docs.rs/uefi/latest/uefi/data_types/struct.Handle.html
 /// [`SystemTable<Boot>`]: https://docs.rs/uefi/latest/uefi/table/struct.SystemTable.html
 /// [`Status`]: https://docs.rs/uefi/latest/uefi/struct.Status.html
+/// [`Status::SUCCESS`]: https://docs.rs/uefi/latest/uefi/struct.Status.html#associatedconstant.SUCCESS
+/// [`Status::UNSUPPORTED`]: https://docs.rs/uefi/latest/uefi/struct.Status.html#associatedconstant.UNSUPPORTED
 /// [`BootServices::set_image_handle`]: https://docs.rs/uefi/latest/uefi/table/boot/struct.BootServices.html#method.set_image_handle
+/// [`BootServices::set_unload_handler`]: https://docs.rs/uefi/latest/uefi/table/boot/struct.BootServices.html#method.set_unload_handler
+/// [`MemoryType::LOADER_CODE`]: https://docs.rs/uefi/latest/uefi/table/boot/struct.MemoryType.html#associatedconstant.LOADER_CODE
 #[proc_macro_attribute]
 pub fn entry(args: TokenStream, input: TokenStream) -> TokenStream {
     // This code is inspired by the approach in this embedded Rust crate:
@@ -155,11 +196,35 @@ pub fn entry(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let mut errors = TokenStream2::new();
 
-    if !args.is_empty() {
-        errors.append_all(err!(
-            TokenStream2::from(args),
-            "Entry attribute accepts no arguments"
-        ));
+    let args = TokenStream2::from(args);
+    let mut is_driver = false;
+    let mut unload = None;
+    match Punctuated::<Meta, Token![,]>::parse_terminated.parse2(args.clone()) {
+        Ok(metas) => {
+            for meta in metas {
+                match meta {
+                    Meta::Path(path) if path.is_ident("driver") => is_driver = true,
+                    Meta::NameValue(MetaNameValue {
+                        path,
+                        value: Expr::Path(value),
+                        ..
+                    }) if path.is_ident("unload") => unload = Some(value),
+                    meta => errors.append_all(err!(
+                        meta,
+                        "Entry attribute accepts no arguments, or `driver` and `unload = <path>`"
+                    )),
+                }
+            }
+        }
+        Err(_) => errors.append_all(err!(
+            args,
+            "Entry attribute accepts no arguments, or `driver` and `unload = <path>`"
+        )),
+    }
+    if let Some(unload) = &unload {
+        if !is_driver {
+            errors.append_all(err!(unload, "`unload` can only be used with `driver`"));
+        }
     }
 
     let mut f = parse_macro_input!(input as ItemFn);
@@ -208,6 +273,44 @@ pub fn entry(args: TokenStream, input: TokenStream) -> TokenStream {
                 }
             },
         );
+        // An application is unloaded as soon as it returns, so don't let it
+        // run as a driver.
+        if is_driver {
+            f.block.stmts.insert(
+                1,
+                parse_quote! {
+                    match #system_table_ident
+                        .boot_services()
+                        .open_protocol_exclusive::<::uefi::proto::loaded_image::LoadedImage>(
+                            #image_handle_ident,
+                        ) {
+                        ::core::result::Result::Ok(loaded_image) => {
+                            if loaded_image.code_type()
+                                == ::uefi::table::boot::MemoryType::LOADER_CODE
+                            {
+                                return ::uefi::Status::UNSUPPORTED;
+                            }
+                        }
+                        ::core::result::Result::Err(err) => return err.status(),
+                    }
+                },
+            );
+        }
+        if let Some(unload) = unload {
+            f.block.stmts.insert(
+                2,
+                parse_quote! {
+                    if let ::core::result::Result::Err(err) =
+                        #system_table_ident.boot_services().set_unload_handler({
+                            extern crate alloc;
+                            alloc::boxed::Box::new(#unload)
+                        })
+                    {
+                        return err.status();
+                    }
+                },
+            );
+        }
     }
 
     let fn_ident = &f.sig.ident;
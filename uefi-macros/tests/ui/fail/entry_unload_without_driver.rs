@@ -0,0 +1,14 @@
+#![allow(unused_imports)]
+#![no_main]
+
+use uefi::prelude::*;
+use uefi_macros::entry;
+
+fn unload(_bt: &BootServices) -> uefi::Result {
+    Ok(())
+}
+
+#[entry(unload = unload)]
+fn main(_handle: Handle, _st: SystemTable<Boot>) -> Status {
+    Status::SUCCESS
+}
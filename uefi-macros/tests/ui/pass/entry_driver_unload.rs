@@ -0,0 +1,15 @@
+use uefi::table::boot::BootServices;
+use uefi::table::{Boot, SystemTable};
+use uefi::{entry, Handle, Status};
+
+fn unload(_bt: &BootServices) -> uefi::Result {
+    Ok(())
+}
+
+#[entry(driver, unload = unload)]
+fn efi_main(image: Handle, st: SystemTable<Boot>) -> Status {
+    Status::SUCCESS
+}
+
+// trybuild requires a `main` function.
+fn main() {}
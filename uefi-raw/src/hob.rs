@@ -0,0 +1,327 @@
+//! PI Hand-Off Blocks (HOBs).
+//!
+//! The HOB list is created during the PEI phase to pass information about
+//! the system, such as memory resources and firmware volumes, to the DXE
+//! phase. Each HOB starts with a [`HobHeader`]; the list ends with a HOB of
+//! type [`HobType::END_OF_HOB_LIST`].
+
+use crate::table::boot::MemoryType;
+use crate::{Guid, PhysicalAddress};
+use bitflags::bitflags;
+
+newtype_enum! {
+/// Type of a HOB.
+pub enum HobType: u16 => {
+    /// Phase handoff information table. This is always the first HOB.
+    HANDOFF = 0x0001,
+    /// Memory allocation.
+    MEMORY_ALLOCATION = 0x0002,
+    /// Resource descriptor.
+    RESOURCE_DESCRIPTOR = 0x0003,
+    /// GUID extension, with data defined by the GUID.
+    GUID_EXTENSION = 0x0004,
+    /// Firmware volume.
+    FV = 0x0005,
+    /// CPU address space sizes.
+    CPU = 0x0006,
+    /// Memory pool.
+    MEMORY_POOL = 0x0007,
+    /// Firmware volume extracted from a file.
+    FV2 = 0x0009,
+    /// Reserved, unused.
+    LOAD_PEIM_UNUSED = 0x000a,
+    /// Capsule.
+    UEFI_CAPSULE = 0x000b,
+    /// Firmware volume extracted from a file, with authentication status.
+    FV3 = 0x000c,
+    /// HOB that should be ignored.
+    UNUSED = 0xfffe,
+    /// End of the HOB list.
+    END_OF_HOB_LIST = 0xffff,
+}}
+
+/// Common header of all HOBs.
+///
+/// Corresponds to the C type `EFI_HOB_GENERIC_HEADER`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct HobHeader {
+    /// Type of the HOB.
+    pub hob_type: HobType,
+
+    /// Length in bytes of the HOB, including the header. This is a multiple
+    /// of eight.
+    pub hob_length: u16,
+
+    /// Reserved, must be zero.
+    pub reserved: u32,
+}
+
+/// Phase handoff information table (PHIT) HOB.
+///
+/// Corresponds to the C type `EFI_HOB_HANDOFF_INFO_TABLE`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct HandoffInfoTable {
+    /// HOB header, of type [`HobType::HANDOFF`].
+    pub header: HobHeader,
+
+    /// Version of the HOB list format.
+    pub version: u32,
+
+    /// Boot mode of the system.
+    pub boot_mode: BootMode,
+
+    /// Highest address of the memory allocated to the DXE phase.
+    pub memory_top: PhysicalAddress,
+
+    /// Lowest address of the memory allocated to the DXE phase.
+    pub memory_bottom: PhysicalAddress,
+
+    /// Highest address of the free memory in the DXE phase memory.
+    pub free_memory_top: PhysicalAddress,
+
+    /// Lowest address of the free memory in the DXE phase memory.
+    pub free_memory_bottom: PhysicalAddress,
+
+    /// Address of the end-of-list HOB.
+    pub end_of_hob_list: PhysicalAddress,
+}
+
+impl HandoffInfoTable {
+    /// Version of the HOB list format defined by the PI specification.
+    pub const VERSION: u32 = 0x0009;
+}
+
+newtype_enum! {
+/// Boot mode of the system.
+pub enum BootMode: u32 => {
+    /// Boot with full configuration.
+    FULL_CONFIGURATION = 0x00,
+    /// Boot with minimal configuration.
+    MINIMAL_CONFIGURATION = 0x01,
+    /// Boot assuming that the configuration didn't change.
+    ASSUMING_NO_CONFIGURATION_CHANGES = 0x02,
+    /// Boot with full configuration and diagnostics.
+    FULL_CONFIGURATION_PLUS_DIAGNOSTICS = 0x03,
+    /// Boot with default settings.
+    DEFAULT_SETTINGS = 0x04,
+    /// Resume from S4.
+    S4_RESUME = 0x05,
+    /// Resume from S5.
+    S5_RESUME = 0x06,
+    /// Boot with manufacturing mode settings.
+    MFG_MODE_SETTINGS = 0x07,
+    /// Resume from S2.
+    S2_RESUME = 0x10,
+    /// Resume from S3.
+    S3_RESUME = 0x11,
+    /// Boot to update the firmware.
+    FLASH_UPDATE = 0x12,
+    /// Boot in recovery mode.
+    RECOVERY_MODE = 0x20,
+}}
+
+/// Memory allocation HOB.
+///
+/// Corresponds to the C type `EFI_HOB_MEMORY_ALLOCATION`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct MemoryAllocationHob {
+    /// HOB header, of type [`HobType::MEMORY_ALLOCATION`].
+    pub header: HobHeader,
+
+    /// GUID identifying the allocation, or zero.
+    pub name: Guid,
+
+    /// Base address of the allocation.
+    pub memory_base_address: PhysicalAddress,
+
+    /// Length in bytes of the allocation.
+    pub memory_length: u64,
+
+    /// Type of the allocated memory.
+    pub memory_type: MemoryType,
+
+    /// Reserved, must be zero.
+    pub reserved: [u8; 4],
+}
+
+/// Resource descriptor HOB, describing a range of the system's memory or
+/// I/O resources.
+///
+/// Corresponds to the C type `EFI_HOB_RESOURCE_DESCRIPTOR`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct ResourceDescriptorHob {
+    /// HOB header, of type [`HobType::RESOURCE_DESCRIPTOR`].
+    pub header: HobHeader,
+
+    /// GUID of the owner of the resource, or zero.
+    pub owner: Guid,
+
+    /// Type of the resource.
+    pub resource_type: ResourceType,
+
+    /// Attributes of the resource.
+    pub resource_attribute: ResourceAttribute,
+
+    /// Start address of the resource.
+    pub physical_start: PhysicalAddress,
+
+    /// Length in bytes of the resource.
+    pub resource_length: u64,
+}
+
+newtype_enum! {
+/// Type of a resource described by a [`ResourceDescriptorHob`].
+pub enum ResourceType: u32 => {
+    /// System memory (DRAM).
+    SYSTEM_MEMORY = 0x00,
+    /// Memory-mapped I/O.
+    MEMORY_MAPPED_IO = 0x01,
+    /// Processor I/O space.
+    IO = 0x02,
+    /// Memory-mapped firmware device, such as flash.
+    FIRMWARE_DEVICE = 0x03,
+    /// Memory-mapped I/O port.
+    MEMORY_MAPPED_IO_PORT = 0x04,
+    /// Reserved memory.
+    MEMORY_RESERVED = 0x05,
+    /// Reserved I/O space.
+    IO_RESERVED = 0x06,
+    /// Memory that must be accepted before it can be used.
+    MEMORY_UNACCEPTED = 0x07,
+}}
+
+bitflags! {
+    /// Attributes of a resource described by a [`ResourceDescriptorHob`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct ResourceAttribute: u32 {
+        /// The resource is present.
+        const PRESENT = 0x0000_0001;
+        /// The resource is initialized.
+        const INITIALIZED = 0x0000_0002;
+        /// The resource was tested.
+        const TESTED = 0x0000_0004;
+        /// Single-bit ECC is enabled.
+        const SINGLE_BIT_ECC = 0x0000_0008;
+        /// Multiple-bit ECC is enabled.
+        const MULTIPLE_BIT_ECC = 0x0000_0010;
+        /// Reserved ECC mode.
+        const ECC_RESERVED_1 = 0x0000_0020;
+        /// Reserved ECC mode.
+        const ECC_RESERVED_2 = 0x0000_0040;
+        /// The resource is read-protected.
+        const READ_PROTECTED = 0x0000_0080;
+        /// The resource is write-protected.
+        const WRITE_PROTECTED = 0x0000_0100;
+        /// The resource is execution-protected.
+        const EXECUTION_PROTECTED = 0x0000_0200;
+        /// The resource supports uncached access.
+        const UNCACHEABLE = 0x0000_0400;
+        /// The resource supports write-combining.
+        const WRITE_COMBINEABLE = 0x0000_0800;
+        /// The resource supports write-through caching.
+        const WRITE_THROUGH_CACHEABLE = 0x0000_1000;
+        /// The resource supports write-back caching.
+        const WRITE_BACK_CACHEABLE = 0x0000_2000;
+        /// The resource supports 16-bit I/O.
+        const IO_16_BIT = 0x0000_4000;
+        /// The resource supports 32-bit I/O.
+        const IO_32_BIT = 0x0000_8000;
+        /// The resource supports 64-bit I/O.
+        const IO_64_BIT = 0x0001_0000;
+        /// The resource supports uncached, exported access.
+        const UNCACHED_EXPORTED = 0x0002_0000;
+        /// The resource is read-only protected.
+        const READ_ONLY_PROTECTED = 0x0004_0000;
+        /// The resource can be read-only protected.
+        const READ_ONLY_PROTECTABLE = 0x0008_0000;
+        /// The resource can be read-protected.
+        const READ_PROTECTABLE = 0x0010_0000;
+        /// The resource can be write-protected.
+        const WRITE_PROTECTABLE = 0x0020_0000;
+        /// The resource can be execution-protected.
+        const EXECUTION_PROTECTABLE = 0x0040_0000;
+        /// The resource is persistent memory.
+        const PERSISTENT = 0x0080_0000;
+        /// The resource can be used as persistent memory.
+        const PERSISTABLE = 0x0100_0000;
+        /// The resource is more reliable than other memory.
+        const MORE_RELIABLE = 0x0200_0000;
+    }
+}
+
+/// Header of a GUID extension HOB. The HOB data defined by `name` follows
+/// the header.
+///
+/// Corresponds to the C type `EFI_HOB_GUID_TYPE`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct GuidExtensionHob {
+    /// HOB header, of type [`HobType::GUID_EXTENSION`].
+    pub header: HobHeader,
+
+    /// GUID defining the data of the HOB.
+    pub name: Guid,
+}
+
+/// Firmware volume HOB.
+///
+/// Corresponds to the C type `EFI_HOB_FIRMWARE_VOLUME`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct FirmwareVolumeHob {
+    /// HOB header, of type [`HobType::FV`].
+    pub header: HobHeader,
+
+    /// Base address of the firmware volume.
+    pub base_address: PhysicalAddress,
+
+    /// Length in bytes of the firmware volume.
+    pub length: u64,
+}
+
+/// Firmware volume HOB for a volume extracted from a file in another
+/// firmware volume.
+///
+/// Corresponds to the C type `EFI_HOB_FIRMWARE_VOLUME2`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct FirmwareVolume2Hob {
+    /// HOB header, of type [`HobType::FV2`].
+    pub header: HobHeader,
+
+    /// Base address of the firmware volume.
+    pub base_address: PhysicalAddress,
+
+    /// Length in bytes of the firmware volume.
+    pub length: u64,
+
+    /// Name of the firmware volume.
+    pub fv_name: Guid,
+
+    /// Name of the file the firmware volume was extracted from.
+    pub file_name: Guid,
+}
+
+/// CPU HOB, describing the address space sizes of the processor.
+///
+/// Corresponds to the C type `EFI_HOB_CPU`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct CpuHob {
+    /// HOB header, of type [`HobType::CPU`].
+    pub header: HobHeader,
+
+    /// Number of address bits of the memory space.
+    pub size_of_memory_space: u8,
+
+    /// Number of address bits of the I/O space.
+    pub size_of_io_space: u8,
+
+    /// Reserved, must be zero.
+    pub reserved: [u8; 6],
+}
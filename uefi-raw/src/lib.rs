@@ -20,7 +20,9 @@ mod enums;
 
 pub mod capsule;
 pub mod firmware_storage;
+pub mod hob;
 pub mod protocol;
+pub mod signature;
 pub mod table;
 pub mod time;
 
@@ -0,0 +1,213 @@
+//! Firmware Management Protocol (FMP).
+
+use crate::table::esrt::LastAttemptStatus;
+use crate::{guid, Char16, Guid, Status};
+use bitflags::bitflags;
+use core::ffi::c_void;
+
+/// Callback invoked by [`FirmwareManagementProtocol::set_image`] to report
+/// progress. `completion` is a percentage from 1 to 100.
+pub type FirmwareManagementUpdateProgress = unsafe extern "efiapi" fn(completion: usize) -> Status;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct FirmwareManagementProtocol {
+    pub get_image_info: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        image_info_size: *mut usize,
+        image_info: *mut FirmwareImageDescriptor,
+        descriptor_version: *mut u32,
+        descriptor_count: *mut u8,
+        descriptor_size: *mut usize,
+        package_version: *mut u32,
+        package_version_name: *mut *mut Char16,
+    ) -> Status,
+
+    pub get_image: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        image_index: u8,
+        image: *mut c_void,
+        image_size: *mut usize,
+    ) -> Status,
+
+    pub set_image: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        image_index: u8,
+        image: *const c_void,
+        image_size: usize,
+        vendor_code: *const c_void,
+        progress: Option<FirmwareManagementUpdateProgress>,
+        abort_reason: *mut *mut Char16,
+    ) -> Status,
+
+    pub check_image: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        image_index: u8,
+        image: *const c_void,
+        image_size: usize,
+        image_updatable: *mut ImageUpdatable,
+    ) -> Status,
+
+    pub get_package_info: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        package_version: *mut u32,
+        package_version_name: *mut *mut Char16,
+        package_version_name_max_len: *mut u32,
+        attributes_supported: *mut PackageAttributes,
+        attributes_setting: *mut PackageAttributes,
+    ) -> Status,
+
+    pub set_package_info: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        image: *const c_void,
+        image_size: usize,
+        vendor_code: *const c_void,
+        package_version: u32,
+        package_version_name: *const Char16,
+    ) -> Status,
+}
+
+impl FirmwareManagementProtocol {
+    pub const GUID: Guid = guid!("86c77a67-0b97-4633-a187-49104d0685c7");
+
+    /// Package version returned by drivers that don't support package
+    /// versions.
+    pub const PACKAGE_VERSION_NOT_SUPPORTED: u32 = 0xffff_fffe;
+}
+
+/// Description of a firmware image managed by the protocol.
+///
+/// The layout is that of the latest descriptor version,
+/// [`FirmwareImageDescriptor::VERSION`]. Older versions only contain a
+/// prefix of the fields.
+///
+/// Corresponds to the C type `EFI_FIRMWARE_IMAGE_DESCRIPTOR`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct FirmwareImageDescriptor {
+    /// Index of the image, starting at one.
+    pub image_index: u8,
+
+    /// Type of the image. This is the firmware class GUID used in the ESRT.
+    pub image_type_id: Guid,
+
+    /// Unique identifier of the image within the type.
+    pub image_id: u64,
+
+    /// Name of the image, or null.
+    pub image_id_name: *const Char16,
+
+    /// Version of the image.
+    pub version: u32,
+
+    /// Version of the image as a string, or null.
+    pub version_name: *const Char16,
+
+    /// Size of the image in bytes, or zero if unknown.
+    pub size: usize,
+
+    /// Attributes supported by the image.
+    pub attributes_supported: ImageAttributes,
+
+    /// Attributes currently set for the image.
+    pub attributes_setting: ImageAttributes,
+
+    /// Image compatibilities. Bits `0..=15` are defined by the
+    /// specification, bits `16..=63` are vendor-specific.
+    pub compatibilities: u64,
+
+    /// Lowest version that the image can be updated (or rolled back) to.
+    ///
+    /// Added in descriptor version 2.
+    pub lowest_supported_image_version: u32,
+
+    /// Version of the last update attempt.
+    ///
+    /// Added in descriptor version 3.
+    pub last_attempt_version: u32,
+
+    /// Result of the last update attempt.
+    ///
+    /// Added in descriptor version 3.
+    pub last_attempt_status: LastAttemptStatus,
+
+    /// Identifies the device when multiple devices use the same image type,
+    /// or zero if there is a single instance.
+    ///
+    /// Added in descriptor version 3.
+    pub hardware_instance: u64,
+
+    /// Dependencies of the image, or null.
+    ///
+    /// Added in descriptor version 4.
+    pub dependencies: *const u8,
+}
+
+impl FirmwareImageDescriptor {
+    /// Latest version of the descriptor format.
+    pub const VERSION: u32 = 4;
+}
+
+bitflags! {
+    /// Attributes of a firmware image.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(transparent)]
+    pub struct ImageAttributes: u64 {
+        /// The image can be updated with `set_image`.
+        const IMAGE_UPDATABLE = 0x0001;
+
+        /// A reset is required for a new image to take effect.
+        const RESET_REQUIRED = 0x0002;
+
+        /// New images must be authenticated.
+        const AUTHENTICATION_REQUIRED = 0x0004;
+
+        /// The image is in use.
+        const IN_USE = 0x0008;
+
+        /// The image is a UEFI image.
+        const UEFI_IMAGE = 0x0010;
+
+        /// The image has dependencies on other images.
+        const DEPENDENCY = 0x0020;
+    }
+}
+
+bitflags! {
+    /// Result of checking whether an image can be used to update the
+    /// firmware.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(transparent)]
+    pub struct ImageUpdatable: u32 {
+        /// The image can be used for an update.
+        const VALID = 0x0001;
+
+        /// The image is invalid.
+        const INVALID = 0x0002;
+
+        /// The image is of the wrong type.
+        const INVALID_TYPE = 0x0004;
+
+        /// The image is older than the lowest supported version.
+        const INVALID_OLD = 0x0008;
+
+        /// The image can be used for an update with the right vendor code.
+        const VALID_WITH_VENDOR_CODE = 0x0010;
+    }
+}
+
+bitflags! {
+    /// Attributes of a firmware package.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(transparent)]
+    pub struct PackageAttributes: u64 {
+        /// The package version can be updated with `set_package_info`.
+        const VERSION_UPDATABLE = 0x0001;
+
+        /// A reset is required for a new package version to take effect.
+        const RESET_REQUIRED = 0x0002;
+
+        /// New package versions must be authenticated.
+        const AUTHENTICATION_REQUIRED = 0x0004;
+    }
+}
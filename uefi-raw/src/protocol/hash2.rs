@@ -0,0 +1,101 @@
+//! `Hash2` protocol.
+
+use crate::{guid, Guid, Status};
+use core::fmt::{self, Debug, Formatter};
+
+newtype_enum! {
+    /// Hash algorithms that may be supported by the [`Hash2Protocol`].
+    ///
+    /// These are the same GUIDs as used by the older `EFI_HASH_PROTOCOL`.
+    pub enum Hash2AlgorithmType: Guid => {
+        /// MD5 hash algorithm. Not suitable for security purposes.
+        MD5 = guid!("0af7c79c-65b5-4319-b0ae-44ec484e4ad7"),
+
+        /// SHA-1 hash algorithm.
+        SHA1 = guid!("2ae9d80f-3fb2-4095-b7b1-e93157b946b6"),
+
+        /// SHA-224 hash algorithm.
+        SHA224 = guid!("8df01a06-9bd5-4bf7-b021-db4fd9ccf45b"),
+
+        /// SHA-256 hash algorithm.
+        SHA256 = guid!("51aa59de-fdf2-4ea3-bc63-875fb7842ee9"),
+
+        /// SHA-384 hash algorithm.
+        SHA384 = guid!("efa96432-de33-4dd2-aee6-328c33df777a"),
+
+        /// SHA-512 hash algorithm.
+        SHA512 = guid!("caa4381e-750c-4770-b870-7a23b4e42130"),
+    }
+}
+
+/// Output buffer of the [`Hash2Protocol`].
+///
+/// Corresponds to the `EFI_HASH2_OUTPUT` type in the UEFI specification. This
+/// is an untagged union; which member is valid depends on the algorithm used
+/// to produce the hash.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union Hash2Output {
+    pub md5_hash: [u8; 16],
+    pub sha1_hash: [u8; 20],
+    pub sha224_hash: [u8; 28],
+    pub sha256_hash: [u8; 32],
+    pub sha384_hash: [u8; 48],
+    pub sha512_hash: [u8; 64],
+}
+
+impl Debug for Hash2Output {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // The type is an untagged union, so it's unknown how many of the
+        // bytes are actually part of the hash.
+        f.debug_struct("Hash2Output").finish()
+    }
+}
+
+impl Default for Hash2Output {
+    fn default() -> Self {
+        Self {
+            sha512_hash: [0; 64],
+        }
+    }
+}
+
+/// Hash2 protocol.
+///
+/// Instances of this protocol are created with the service binding protocol
+/// identified by [`Hash2Protocol::SERVICE_BINDING_GUID`].
+#[derive(Debug)]
+#[repr(C)]
+pub struct Hash2Protocol {
+    pub get_hash_size: unsafe extern "efiapi" fn(
+        this: *const Self,
+        hash_algorithm: *const Hash2AlgorithmType,
+        hash_size: *mut usize,
+    ) -> Status,
+
+    pub hash: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        hash_algorithm: *const Hash2AlgorithmType,
+        message: *const u8,
+        message_size: usize,
+        hash: *mut Hash2Output,
+    ) -> Status,
+
+    pub hash_init: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        hash_algorithm: *const Hash2AlgorithmType,
+    ) -> Status,
+
+    pub hash_update: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        message: *const u8,
+        message_size: usize,
+    ) -> Status,
+
+    pub hash_final: unsafe extern "efiapi" fn(this: *mut Self, hash: *mut Hash2Output) -> Status,
+}
+
+impl Hash2Protocol {
+    pub const GUID: Guid = guid!("55b1d734-c5e1-49db-9647-b16afb0e305b");
+    pub const SERVICE_BINDING_GUID: Guid = guid!("da836f8d-217f-4ca0-99c2-1ca4e16077ea");
+}
@@ -10,10 +10,14 @@ pub mod device_path;
 pub mod disk;
 pub mod driver;
 pub mod file_system;
+pub mod firmware_management;
+pub mod hash2;
 pub mod loaded_image;
 pub mod media;
 pub mod memory_protection;
 pub mod misc;
 pub mod network;
+pub mod pkcs7;
 pub mod rng;
+pub mod security;
 pub mod shell_params;
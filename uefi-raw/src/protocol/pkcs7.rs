@@ -0,0 +1,41 @@
+//! `Pkcs7Verify` protocol.
+
+use crate::signature::SignatureList;
+use crate::{guid, Guid, Status};
+use core::ffi::c_void;
+
+/// PKCS7 verify protocol.
+///
+/// The signature database parameters are null-terminated arrays of pointers
+/// to [`SignatureList`]s.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Pkcs7VerifyProtocol {
+    pub verify_buffer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        signed_data: *const c_void,
+        signed_data_size: usize,
+        in_data: *const c_void,
+        in_data_size: usize,
+        allowed_db: *const *const SignatureList,
+        revoked_db: *const *const SignatureList,
+        time_stamp_db: *const *const SignatureList,
+        content: *mut c_void,
+        content_size: *mut usize,
+    ) -> Status,
+
+    pub verify_signature: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        signature: *const c_void,
+        signature_size: usize,
+        in_hash: *const c_void,
+        in_hash_size: usize,
+        allowed_db: *const *const SignatureList,
+        revoked_db: *const *const SignatureList,
+        time_stamp_db: *const *const SignatureList,
+    ) -> Status,
+}
+
+impl Pkcs7VerifyProtocol {
+    pub const GUID: Guid = guid!("47889fb2-d671-4fab-a0ca-df0e44df70d6");
+}
@@ -0,0 +1,68 @@
+//! Security architectural protocols.
+//!
+//! These protocols are defined in the UEFI Platform Initialization (PI)
+//! specification. They are used by the `LoadImage` boot service to decide
+//! whether an image may be loaded.
+
+use crate::protocol::device_path::DevicePathProtocol;
+use crate::{guid, Guid, Status};
+use bitflags::bitflags;
+use core::ffi::c_void;
+
+bitflags! {
+    /// Authentication status of a file, as reported by the firmware volume
+    /// or section extraction code that produced it.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct AuthenticationStatus: u32 {
+        /// The platform has overridden the authentication result.
+        const PLATFORM_OVERRIDE = 0x01;
+
+        /// The file is signed.
+        const IMAGE_SIGNED = 0x02;
+
+        /// The signature of the file was not tested.
+        const NOT_TESTED = 0x04;
+
+        /// The signature of the file was tested and failed.
+        const TEST_FAILED = 0x08;
+    }
+}
+
+/// Function type of [`SecurityArchProtocol::file_authentication_state`].
+pub type SecurityFileAuthenticationStateFn = unsafe extern "efiapi" fn(
+    this: *const SecurityArchProtocol,
+    authentication_status: AuthenticationStatus,
+    file: *const DevicePathProtocol,
+) -> Status;
+
+/// Security architectural protocol.
+#[derive(Debug)]
+#[repr(C)]
+pub struct SecurityArchProtocol {
+    pub file_authentication_state: SecurityFileAuthenticationStateFn,
+}
+
+impl SecurityArchProtocol {
+    pub const GUID: Guid = guid!("a46423e3-4617-49f1-b9ff-d1bfa9115839");
+}
+
+/// Function type of [`Security2ArchProtocol::file_authentication`].
+pub type Security2FileAuthenticationFn = unsafe extern "efiapi" fn(
+    this: *const Security2ArchProtocol,
+    file: *const DevicePathProtocol,
+    file_buffer: *mut c_void,
+    file_size: usize,
+    boot_policy: bool,
+) -> Status;
+
+/// Security2 architectural protocol.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Security2ArchProtocol {
+    pub file_authentication: Security2FileAuthenticationFn,
+}
+
+impl Security2ArchProtocol {
+    pub const GUID: Guid = guid!("94ab2f58-1438-4ef1-9152-18941a3a0e68");
+}
@@ -0,0 +1,91 @@
+//! Types for signature databases.
+//!
+//! Signature databases such as `db` and `dbx` are stored in UEFI variables
+//! as a sequence of [`SignatureList`]s.
+
+use crate::{guid, Guid};
+
+newtype_enum! {
+    /// Type of the signatures stored in a [`SignatureList`].
+    pub enum SignatureType: Guid => {
+        /// SHA-256 hash. Each signature is 32 bytes.
+        SHA256 = guid!("c1c41626-504c-4092-aca9-41f936934328"),
+
+        /// RSA-2048 public key (modulus only). Each signature is 256 bytes.
+        RSA2048 = guid!("3c5766e8-269c-4e34-aa14-ed776e85b3b6"),
+
+        /// RSA-2048 signature of a SHA-256 hash. Each signature is 256 bytes.
+        RSA2048_SHA256 = guid!("e2b36190-879b-4a3d-ad8d-f2e7bba32784"),
+
+        /// SHA-1 hash. Each signature is 20 bytes.
+        SHA1 = guid!("826ca512-cf10-4ac9-b187-be01496631bd"),
+
+        /// RSA-2048 signature of a SHA-1 hash. Each signature is 256 bytes.
+        RSA2048_SHA1 = guid!("67f8444f-8743-48f1-a328-1eaab8736080"),
+
+        /// DER-encoded X.509 certificate.
+        X509 = guid!("a5c059a1-94e4-4aa7-87b5-ab155c2bf072"),
+
+        /// SHA-224 hash. Each signature is 28 bytes.
+        SHA224 = guid!("0b6e5233-a65c-44c9-9407-d9ab83bfc8bd"),
+
+        /// SHA-384 hash. Each signature is 48 bytes.
+        SHA384 = guid!("ff3e5307-9fd0-48c9-85f1-8ad56c701e01"),
+
+        /// SHA-512 hash. Each signature is 64 bytes.
+        SHA512 = guid!("093e0fae-a6c4-4f50-9f1b-d41e2b89c19a"),
+
+        /// SHA-256 hash of the to-be-signed contents of an X.509 certificate,
+        /// followed by a revocation time.
+        X509_SHA256 = guid!("3bd2a492-96c0-4079-b420-fcf98ef103ed"),
+
+        /// SHA-384 hash of the to-be-signed contents of an X.509 certificate,
+        /// followed by a revocation time.
+        X509_SHA384 = guid!("7076876e-80c2-4ee6-aad2-28b349a6865b"),
+
+        /// SHA-512 hash of the to-be-signed contents of an X.509 certificate,
+        /// followed by a revocation time.
+        X509_SHA512 = guid!("446dbf63-2502-4cda-bcfa-2465d2b0fe9d"),
+
+        /// PKCS #7 signed data.
+        PKCS7 = guid!("4aafd29d-68df-49ee-8aa9-347d375665a7"),
+    }
+}
+
+/// Header of a list of signatures that all have the same type and size.
+///
+/// The header is followed by `signature_header_size` bytes of type-specific
+/// data, and then by a sequence of [`SignatureData`] entries that are each
+/// `signature_size` bytes in length.
+///
+/// Corresponds to the C type `EFI_SIGNATURE_LIST`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(C)]
+pub struct SignatureList {
+    /// Type of the signatures in the list.
+    pub signature_type: SignatureType,
+
+    /// Total size in bytes of the list, including this header.
+    pub signature_list_size: u32,
+
+    /// Size in bytes of the type-specific header following this header.
+    pub signature_header_size: u32,
+
+    /// Size in bytes of each signature entry, including the owner GUID.
+    pub signature_size: u32,
+}
+
+/// Header of a single entry in a [`SignatureList`].
+///
+/// The header is followed by the signature data.
+///
+/// Corresponds to the C type `EFI_SIGNATURE_DATA`.
+#[derive(Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct SignatureData {
+    /// Identifies the agent that added the signature.
+    pub signature_owner: Guid,
+
+    /// Variable-length signature data.
+    pub signature_data: [u8; 0],
+}
@@ -0,0 +1,82 @@
+//! Debug image info table.
+//!
+//! The debug image info table lists the images that are currently loaded,
+//! so that a debugger can find their base addresses and symbols.
+
+use crate::protocol::loaded_image::LoadedImageProtocol;
+use crate::Handle;
+use bitflags::bitflags;
+use core::fmt::{self, Debug, Formatter};
+
+/// Header of the debug image info table.
+///
+/// Corresponds to the C type `EFI_DEBUG_IMAGE_INFO_TABLE_HEADER`.
+#[derive(Debug)]
+#[repr(C)]
+pub struct DebugImageInfoTableHeader {
+    /// Status of the table. This is written by the firmware while the table
+    /// is being updated, so it must be read with volatile reads.
+    pub update_status: DebugImageInfoUpdateStatus,
+
+    /// Number of entries in the table.
+    pub table_size: u32,
+
+    /// Array of `table_size` entries. Entries may be null.
+    pub efi_debug_image_info_table: *mut DebugImageInfo,
+}
+
+bitflags! {
+    /// Update status of the debug image info table.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct DebugImageInfoUpdateStatus: u32 {
+        /// The table is being updated and its entries may be inconsistent.
+        const UPDATE_IN_PROGRESS = 0x01;
+        /// The table was modified. This is set by the firmware whenever the
+        /// table changes, and may be cleared by a debugger.
+        const TABLE_MODIFIED = 0x02;
+    }
+}
+
+newtype_enum! {
+/// Type of an entry of the debug image info table.
+pub enum DebugImageInfoType: u32 => {
+    /// Entry of type [`DebugImageInfoNormal`].
+    NORMAL = 0x01,
+}}
+
+/// Entry of the debug image info table describing a loaded image.
+///
+/// Corresponds to the C type `EFI_DEBUG_IMAGE_INFO_NORMAL`.
+#[derive(Debug)]
+#[repr(C)]
+pub struct DebugImageInfoNormal {
+    /// Type of the entry, [`DebugImageInfoType::NORMAL`].
+    pub image_info_type: DebugImageInfoType,
+
+    /// Loaded image protocol instance of the image.
+    pub loaded_image_protocol_instance: *const LoadedImageProtocol,
+
+    /// Handle of the image.
+    pub image_handle: Handle,
+}
+
+/// Entry of the debug image info table. The type of the entry is given by
+/// the [`DebugImageInfoType`] that both pointers point to.
+///
+/// Corresponds to the C type `EFI_DEBUG_IMAGE_INFO`.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union DebugImageInfo {
+    pub image_info_type: *const DebugImageInfoType,
+    pub normal_image: *const DebugImageInfoNormal,
+}
+
+impl Debug for DebugImageInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Both fields are pointers, so reading either one is fine.
+        f.debug_tuple("DebugImageInfo")
+            .field(unsafe { &self.image_info_type })
+            .finish()
+    }
+}
@@ -0,0 +1,198 @@
+//! PI DXE services table.
+//!
+//! The DXE services table provides access to the Global Coherency Domain
+//! (GCD), which tracks the memory and I/O space of the system, and to the
+//! DXE dispatcher.
+
+use crate::table::boot::MemoryAttribute;
+use crate::table::Header;
+use crate::{Guid, Handle, PhysicalAddress, Status};
+use core::ffi::c_void;
+
+/// Table of pointers to the DXE services.
+///
+/// Corresponds to the C type `EFI_DXE_SERVICES`.
+#[derive(Debug)]
+#[repr(C)]
+pub struct DxeServices {
+    pub header: Header,
+
+    // Global Coherency Domain services.
+    pub add_memory_space: unsafe extern "efiapi" fn(
+        memory_type: GcdMemoryType,
+        base_address: PhysicalAddress,
+        length: u64,
+        capabilities: MemoryAttribute,
+    ) -> Status,
+    pub allocate_memory_space: unsafe extern "efiapi" fn(
+        allocate_type: GcdAllocateType,
+        memory_type: GcdMemoryType,
+        alignment: usize,
+        length: u64,
+        base_address: *mut PhysicalAddress,
+        image_handle: Handle,
+        device_handle: Handle,
+    ) -> Status,
+    pub free_memory_space:
+        unsafe extern "efiapi" fn(base_address: PhysicalAddress, length: u64) -> Status,
+    pub remove_memory_space:
+        unsafe extern "efiapi" fn(base_address: PhysicalAddress, length: u64) -> Status,
+    pub get_memory_space_descriptor: unsafe extern "efiapi" fn(
+        base_address: PhysicalAddress,
+        descriptor: *mut GcdMemorySpaceDescriptor,
+    ) -> Status,
+    pub set_memory_space_attributes: unsafe extern "efiapi" fn(
+        base_address: PhysicalAddress,
+        length: u64,
+        attributes: MemoryAttribute,
+    ) -> Status,
+    pub get_memory_space_map: unsafe extern "efiapi" fn(
+        number_of_descriptors: *mut usize,
+        memory_space_map: *mut *mut GcdMemorySpaceDescriptor,
+    ) -> Status,
+    pub add_io_space: unsafe extern "efiapi" fn(
+        io_type: GcdIoType,
+        base_address: PhysicalAddress,
+        length: u64,
+    ) -> Status,
+    pub allocate_io_space: unsafe extern "efiapi" fn(
+        allocate_type: GcdAllocateType,
+        io_type: GcdIoType,
+        alignment: usize,
+        length: u64,
+        base_address: *mut PhysicalAddress,
+        image_handle: Handle,
+        device_handle: Handle,
+    ) -> Status,
+    pub free_io_space:
+        unsafe extern "efiapi" fn(base_address: PhysicalAddress, length: u64) -> Status,
+    pub remove_io_space:
+        unsafe extern "efiapi" fn(base_address: PhysicalAddress, length: u64) -> Status,
+    pub get_io_space_descriptor: unsafe extern "efiapi" fn(
+        base_address: PhysicalAddress,
+        descriptor: *mut GcdIoSpaceDescriptor,
+    ) -> Status,
+    pub get_io_space_map: unsafe extern "efiapi" fn(
+        number_of_descriptors: *mut usize,
+        io_space_map: *mut *mut GcdIoSpaceDescriptor,
+    ) -> Status,
+
+    // Dispatcher services.
+    pub dispatch: unsafe extern "efiapi" fn() -> Status,
+    pub schedule:
+        unsafe extern "efiapi" fn(firmware_volume_handle: Handle, file_name: *const Guid) -> Status,
+    pub trust:
+        unsafe extern "efiapi" fn(firmware_volume_handle: Handle, file_name: *const Guid) -> Status,
+    pub process_firmware_volume: unsafe extern "efiapi" fn(
+        firmware_volume_header: *const c_void,
+        size: usize,
+        firmware_volume_handle: *mut Handle,
+    ) -> Status,
+
+    // Added in PI 1.2.
+    pub set_memory_space_capabilities: unsafe extern "efiapi" fn(
+        base_address: PhysicalAddress,
+        length: u64,
+        capabilities: MemoryAttribute,
+    ) -> Status,
+}
+
+impl DxeServices {
+    /// Signature of the table header.
+    pub const SIGNATURE: u64 = 0x5652_4553_5f45_5844;
+}
+
+newtype_enum! {
+/// Type of a memory space region in the GCD.
+pub enum GcdMemoryType: u32 => {
+    /// No memory or device is present.
+    NON_EXISTENT = 0,
+    /// Reserved memory.
+    RESERVED = 1,
+    /// System memory (DRAM).
+    SYSTEM_MEMORY = 2,
+    /// Memory-mapped I/O.
+    MEMORY_MAPPED_IO = 3,
+    /// Persistent memory.
+    PERSISTENT = 4,
+    /// System memory that is more reliable than other system memory.
+    MORE_RELIABLE = 5,
+    /// System memory that must be accepted before it can be used.
+    UNACCEPTED = 6,
+}}
+
+newtype_enum! {
+/// Type of an I/O space region in the GCD.
+pub enum GcdIoType: u32 => {
+    /// No device is present.
+    NON_EXISTENT = 0,
+    /// Reserved I/O space.
+    RESERVED = 1,
+    /// I/O space of a device.
+    IO = 2,
+}}
+
+newtype_enum! {
+/// Search strategy used to allocate memory or I/O space from the GCD.
+pub enum GcdAllocateType: u32 => {
+    /// Allocate the lowest suitable range.
+    ANY_SEARCH_BOTTOM_UP = 0,
+    /// Allocate the lowest suitable range below the given address.
+    MAX_ADDRESS_SEARCH_BOTTOM_UP = 1,
+    /// Allocate the range at the given address.
+    ADDRESS = 2,
+    /// Allocate the highest suitable range.
+    ANY_SEARCH_TOP_DOWN = 3,
+    /// Allocate the highest suitable range below the given address.
+    MAX_ADDRESS_SEARCH_TOP_DOWN = 4,
+}}
+
+/// Descriptor of a memory space region in the GCD.
+///
+/// Corresponds to the C type `EFI_GCD_MEMORY_SPACE_DESCRIPTOR`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct GcdMemorySpaceDescriptor {
+    /// Start address of the region.
+    pub base_address: PhysicalAddress,
+
+    /// Length in bytes of the region.
+    pub length: u64,
+
+    /// Attributes that the region supports.
+    pub capabilities: MemoryAttribute,
+
+    /// Attributes currently set for the region.
+    pub attributes: MemoryAttribute,
+
+    /// Type of the region.
+    pub gcd_memory_type: GcdMemoryType,
+
+    /// Image that allocated the region, or null if it is not allocated.
+    pub image_handle: Handle,
+
+    /// Device that the region was allocated for, or null.
+    pub device_handle: Handle,
+}
+
+/// Descriptor of an I/O space region in the GCD.
+///
+/// Corresponds to the C type `EFI_GCD_IO_SPACE_DESCRIPTOR`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct GcdIoSpaceDescriptor {
+    /// Start address of the region.
+    pub base_address: PhysicalAddress,
+
+    /// Length in bytes of the region.
+    pub length: u64,
+
+    /// Type of the region.
+    pub gcd_io_type: GcdIoType,
+
+    /// Image that allocated the region, or null if it is not allocated.
+    pub image_handle: Handle,
+
+    /// Device that the region was allocated for, or null.
+    pub device_handle: Handle,
+}
@@ -0,0 +1,147 @@
+//! EFI System Resource Table (ESRT).
+//!
+//! The ESRT lists the firmware resources of the system that can be updated
+//! with capsules, along with the result of the last update attempt.
+
+use crate::capsule::CapsuleFlags;
+use crate::Guid;
+use core::fmt;
+
+/// Corresponds to the C type `EFI_SYSTEM_RESOURCE_TABLE`.
+#[derive(Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct SystemResourceTable {
+    /// Number of entries in the table.
+    pub fw_resource_count: u32,
+
+    /// Number of entries that fit in the memory allocated for the table.
+    pub fw_resource_count_max: u32,
+
+    /// Version of the table format. See [`Self::VERSION`].
+    pub fw_resource_version: u64,
+
+    /// Variable-length array of `fw_resource_count` entries.
+    pub entries: [SystemResourceEntry; 0],
+}
+
+impl SystemResourceTable {
+    /// The only defined version of the table format.
+    pub const VERSION: u64 = 1;
+}
+
+/// Corresponds to the C type `EFI_SYSTEM_RESOURCE_ENTRY`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(C)]
+pub struct SystemResourceEntry {
+    /// GUID identifying the firmware resource. This is the GUID of capsules
+    /// that update the resource.
+    pub fw_class: Guid,
+
+    /// Type of the firmware resource.
+    pub fw_type: FirmwareType,
+
+    /// Current version of the firmware resource.
+    pub fw_version: u32,
+
+    /// Lowest version that the resource can be updated (or rolled back) to.
+    pub lowest_supported_fw_version: u32,
+
+    /// Flags that must be set in the capsule header of updates for this
+    /// resource.
+    pub capsule_flags: CapsuleFlags,
+
+    /// Version of the last update attempt.
+    pub last_attempt_version: u32,
+
+    /// Result of the last update attempt.
+    pub last_attempt_status: LastAttemptStatus,
+}
+
+newtype_enum! {
+/// Type of a firmware resource in the ESRT.
+pub enum FirmwareType: u32 => {
+    /// Unknown type.
+    UNKNOWN = 0,
+    /// System firmware.
+    SYSTEM_FIRMWARE = 1,
+    /// Firmware of a device.
+    DEVICE_FIRMWARE = 2,
+    /// UEFI driver.
+    UEFI_DRIVER = 3,
+}}
+
+newtype_enum! {
+/// Result of the last firmware update attempt.
+///
+/// Values between [`VENDOR_RANGE_MIN`] and [`VENDOR_RANGE_MAX`] are
+/// vendor-specific errors.
+///
+/// [`VENDOR_RANGE_MIN`]: Self::VENDOR_RANGE_MIN
+/// [`VENDOR_RANGE_MAX`]: Self::VENDOR_RANGE_MAX
+pub enum LastAttemptStatus: u32 => {
+    /// The update was successful.
+    SUCCESS = 0,
+    /// The update failed for an unspecified reason.
+    ERROR_UNSUCCESSFUL = 1,
+    /// Not enough resources, such as memory, were available.
+    ERROR_INSUFFICIENT_RESOURCES = 2,
+    /// The update version was not accepted, e.g. because it is lower than
+    /// the lowest supported version.
+    ERROR_INCORRECT_VERSION = 3,
+    /// The update image was not in a valid format.
+    ERROR_INVALID_FORMAT = 4,
+    /// The update image failed authentication.
+    ERROR_AUTH_ERROR = 5,
+    /// The system was not connected to AC power.
+    ERROR_PWR_EVT_AC = 6,
+    /// The battery level was insufficient.
+    ERROR_PWR_EVT_BATT = 7,
+    /// The dependencies of the update image were not satisfied.
+    ERROR_UNSATISFIED_DEPENDENCIES = 8,
+    /// First vendor-specific error code.
+    VENDOR_RANGE_MIN = 0x1000,
+    /// Last vendor-specific error code.
+    VENDOR_RANGE_MAX = 0x4000,
+}}
+
+impl LastAttemptStatus {
+    /// Returns true if the update was successful.
+    #[must_use]
+    pub fn is_success(self) -> bool {
+        self == Self::SUCCESS
+    }
+
+    /// If this is a vendor-specific error, returns its offset from
+    /// [`VENDOR_RANGE_MIN`].
+    ///
+    /// [`VENDOR_RANGE_MIN`]: Self::VENDOR_RANGE_MIN
+    #[must_use]
+    pub fn vendor_code(self) -> Option<u32> {
+        (Self::VENDOR_RANGE_MIN.0..=Self::VENDOR_RANGE_MAX.0)
+            .contains(&self.0)
+            .then(|| self.0 - Self::VENDOR_RANGE_MIN.0)
+    }
+}
+
+impl fmt::Display for LastAttemptStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match *self {
+            Self::SUCCESS => "success",
+            Self::ERROR_UNSUCCESSFUL => "unsuccessful",
+            Self::ERROR_INSUFFICIENT_RESOURCES => "insufficient resources",
+            Self::ERROR_INCORRECT_VERSION => "incorrect version",
+            Self::ERROR_INVALID_FORMAT => "invalid image format",
+            Self::ERROR_AUTH_ERROR => "authentication error",
+            Self::ERROR_PWR_EVT_AC => "AC power not connected",
+            Self::ERROR_PWR_EVT_BATT => "insufficient battery",
+            Self::ERROR_UNSATISFIED_DEPENDENCIES => "unsatisfied dependencies",
+            _ => {
+                return match self.vendor_code() {
+                    Some(code) => write!(f, "vendor error {code:#x}"),
+                    None => write!(f, "unknown status {:#x}", self.0),
+                };
+            }
+        };
+        f.write_str(description)
+    }
+}
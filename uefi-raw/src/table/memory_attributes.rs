@@ -0,0 +1,45 @@
+//! EFI Memory Attributes Table.
+//!
+//! The table describes the memory protections of the code and data of
+//! runtime images, which the OS can apply when mapping runtime services.
+
+use bitflags::bitflags;
+
+/// Corresponds to the C type `EFI_MEMORY_ATTRIBUTES_TABLE`.
+#[derive(Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct MemoryAttributesTable {
+    /// Version of the table format.
+    pub version: u32,
+
+    /// Number of memory descriptors in the table.
+    pub number_of_entries: u32,
+
+    /// Size in bytes of each memory descriptor. This may be larger than
+    /// the size of `MemoryDescriptor`.
+    pub descriptor_size: u32,
+
+    /// Flags of the table. Reserved in version 1.
+    pub flags: MemoryAttributesTableFlags,
+    // Followed by `number_of_entries` memory descriptors.
+}
+
+impl MemoryAttributesTable {
+    /// First version of the table format.
+    pub const VERSION_1: u32 = 1;
+
+    /// Version of the table format that added the `flags` field.
+    pub const VERSION_2: u32 = 2;
+}
+
+bitflags! {
+    /// Flags of the [`MemoryAttributesTable`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(transparent)]
+    pub struct MemoryAttributesTableFlags: u32 {
+        /// Runtime code pages are compatible with forward control flow
+        /// guards, such as Intel IBT and Arm BTI, which may be enabled when
+        /// mapping them.
+        const RT_FORWARD_CONTROL_FLOW_GUARD = 0x1;
+    }
+}
@@ -5,6 +5,10 @@ mod revision;
 
 pub mod boot;
 pub mod configuration;
+pub mod debug_image_info;
+pub mod dxe_services;
+pub mod esrt;
+pub mod memory_attributes;
 pub mod runtime;
 pub mod system;
 
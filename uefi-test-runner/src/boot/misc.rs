@@ -1,22 +1,40 @@
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use core::cell::Cell;
 use core::ffi::c_void;
 use core::ptr::{self, NonNull};
 
 use core::mem;
+use core::time::Duration;
+use uefi::futures::{self, sleep, EventFuture};
 use uefi::proto::unsafe_protocol;
+use uefi::table::acpi::{AcpiTables, Fadt, Madt};
 use uefi::table::boot::{
-    BootServices, EventType, MemoryType, OpenProtocolAttributes, OpenProtocolParams, SearchType,
-    TimerTrigger, Tpl,
+    BootServices, Deadline, EventGroup, EventType, MemoryAttribute, MemoryType,
+    OpenProtocolAttributes, OpenProtocolParams, ProtocolInterfaces, ProtocolWatcher, SearchType,
+    Timer, TimerTrigger, Tpl,
 };
+use uefi::table::cfg::ConfigTable;
+use uefi::table::debug_image_info::DebugImageInfoTable;
+use uefi::table::dxe_services::{DxeServices, GcdMemoryType};
+use uefi::table::hob::{Hob, HobList, ResourceType};
+use uefi::table::memory_attributes::MemoryAttributesTable;
+use uefi::table::smbios::{BiosInformation, ProcessorInformation, SmbiosTable};
 use uefi::table::{Boot, SystemTable};
-use uefi::{guid, Event, Guid, Identify};
+use uefi::{guid, Event, Guid, Identify, Status};
 
 pub fn test(st: &SystemTable<Boot>) {
     let bt = st.boot_services();
     info!("Testing timer...");
     test_timer(bt);
+    test_timer_api(bt);
     info!("Testing events...");
     test_event_callback(bt);
     test_callback_with_ctx(bt);
+    test_callback_with_closure(bt);
+    test_group_events(bt);
+    info!("Testing futures...");
+    test_futures(bt);
     info!("Testing watchdog...");
     test_watchdog(bt);
     info!("Testing protocol handler services...");
@@ -24,7 +42,21 @@ pub fn test(st: &SystemTable<Boot>) {
     test_install_protocol_interface(bt);
     test_reinstall_protocol_interface(bt);
     test_uninstall_protocol_interface(bt);
+    test_protocol_watcher(bt);
+    test_install_protocol(bt);
     test_install_configuration_table(st);
+    info!("Testing ACPI tables...");
+    test_acpi_tables(st);
+    info!("Testing SMBIOS tables...");
+    test_smbios_tables(st);
+    info!("Testing memory attributes table...");
+    test_memory_attributes_table(st);
+    info!("Testing HOB list...");
+    test_hob_list(st);
+    info!("Testing DXE services...");
+    test_dxe_services(st);
+    info!("Testing debug image info table...");
+    test_debug_image_info_table(st);
 }
 
 fn test_timer(bt: &BootServices) {
@@ -37,6 +69,29 @@ fn test_timer(bt: &BootServices) {
         .expect("Wait for event failed");
 }
 
+fn test_timer_api(bt: &BootServices) {
+    let timer = Timer::one_shot(bt, Duration::from_millis(1)).expect("Failed to create timer");
+    timer.wait().expect("Failed to wait for timer");
+    // Waiting clears the signaled state.
+    assert!(!timer.check().expect("Failed to check timer"));
+
+    let timer = Timer::periodic(bt, Duration::from_millis(1)).expect("Failed to create timer");
+    timer.wait().expect("Failed to wait for timer");
+    timer.wait().expect("Failed to wait for timer");
+    timer.cancel().expect("Failed to cancel timer");
+
+    let deadline = Deadline::new(bt, Duration::from_secs(60)).expect("Failed to create deadline");
+    assert!(!deadline.has_expired());
+    // A zero timeout expires on the next timer tick, whose period depends
+    // on the firmware.
+    let deadline = Deadline::new(bt, Duration::ZERO).expect("Failed to create deadline");
+    while !deadline.has_expired() {
+        bt.stall(1000);
+    }
+    // The deadline stays expired.
+    assert!(deadline.has_expired());
+}
+
 fn test_event_callback(bt: &BootServices) {
     extern "efiapi" fn callback(_event: Event, _ctx: Option<NonNull<c_void>>) {
         info!("Inside the event callback");
@@ -80,6 +135,84 @@ fn test_callback_with_ctx(bt: &BootServices) {
     assert_eq!(data, 456);
 }
 
+fn test_callback_with_closure(bt: &BootServices) {
+    let count = Rc::new(Cell::new(0));
+    let callback = {
+        let count = count.clone();
+        Box::new(move |_event| count.set(count.get() + 1))
+    };
+    let event = bt
+        .create_event_with_callback(EventType::NOTIFY_WAIT, Tpl::CALLBACK, callback)
+        .expect("Failed to create event with closure");
+
+    let _ = bt.check_event(unsafe { event.unsafe_clone() });
+    assert_eq!(count.get(), 1);
+
+    bt.close_event(event).expect("Failed to close event");
+    assert_eq!(Rc::strong_count(&count), 1);
+
+    // Callbacks can't run at the application TPL.
+    let err = bt
+        .create_event_with_callback(EventType::NOTIFY_WAIT, Tpl::APPLICATION, Box::new(|_| {}))
+        .unwrap_err();
+    assert_eq!(err.status(), Status::INVALID_PARAMETER);
+}
+
+fn test_group_events(bt: &BootServices) {
+    let group = EventGroup(guid!("a5a3c6e1-1bd8-4d0c-9a31-7ac2f0cc6f3b"));
+    let count = Rc::new(Cell::new(0));
+    let callback = || {
+        let count = count.clone();
+        Box::new(move |_event| count.set(count.get() + 1))
+    };
+    let first = bt
+        .create_group_event(group, Tpl::CALLBACK, callback())
+        .expect("Failed to create group event");
+    let second = bt
+        .create_group_event(group, Tpl::CALLBACK, callback())
+        .expect("Failed to create group event");
+
+    // Signaling one event of the group signals all of them.
+    first.signal().expect("Failed to signal event group");
+    assert_eq!(count.get(), 2);
+
+    drop(first);
+    drop(second);
+    assert_eq!(Rc::strong_count(&count), 1);
+
+    // Well-known groups that are signaled while exiting boot services are
+    // rejected.
+    let err = bt
+        .create_group_event(
+            EventGroup::EXIT_BOOT_SERVICES,
+            Tpl::CALLBACK,
+            Box::new(|_| {}),
+        )
+        .unwrap_err();
+    assert_eq!(err.status(), Status::INVALID_PARAMETER);
+}
+
+fn test_futures(bt: &BootServices) {
+    let event = unsafe { bt.create_event(EventType::empty(), Tpl::CALLBACK, None, None) }
+        .expect("Failed to create event");
+
+    let waiting = async {
+        EventFuture::new(bt, &event).await?;
+        bt.check_event(unsafe { event.unsafe_clone() })
+    };
+    let signaling = async {
+        sleep(bt, Duration::from_millis(10)).await?;
+        bt.signal_event(&event)
+    };
+    let (waiting, signaling) =
+        futures::block_on(bt, uefi::join!(waiting, signaling)).expect("Failed to run futures");
+    signaling.expect("Failed to signal event");
+    // The signaled state was cleared by the future.
+    assert!(!waiting.expect("Failed to wait for event"));
+
+    bt.close_event(event).expect("Failed to close event");
+}
+
 fn test_watchdog(bt: &BootServices) {
     // Disable the UEFI watchdog timer
     bt.set_watchdog_timer(0, 0x10000, None)
@@ -184,7 +317,122 @@ fn test_uninstall_protocol_interface(bt: &BootServices) {
     }
 }
 
+fn test_protocol_watcher(bt: &BootServices) {
+    /// Dummy protocol that is only installed by this test.
+    #[unsafe_protocol("5f4b2b3c-21f8-4e0e-93a8-6f1c4b2d8a97")]
+    struct WatchedProtocol;
+
+    let watcher = ProtocolWatcher::<WatchedProtocol>::new(bt).expect("Failed to create watcher");
+    let notified = Rc::new(Cell::new(None));
+    let callback_watcher = {
+        let notified = notified.clone();
+        ProtocolWatcher::<WatchedProtocol>::with_callback(
+            bt,
+            Tpl::CALLBACK,
+            Box::new(move |handle| notified.set(Some(handle))),
+        )
+        .expect("Failed to create watcher with callback")
+    };
+    assert_eq!(watcher.next_handle(), Ok(None));
+
+    let handle = unsafe {
+        bt.install_protocol_interface(None, &WatchedProtocol::GUID, ptr::null_mut())
+            .expect("Failed to install protocol interface")
+    };
+    assert_eq!(watcher.wait(), Ok(handle));
+    assert_eq!(watcher.handles().count(), 0);
+    assert_eq!(notified.get(), Some(handle));
+
+    drop(callback_watcher);
+    unsafe {
+        bt.uninstall_protocol_interface(handle, &WatchedProtocol::GUID, ptr::null_mut())
+            .expect("Failed to uninstall protocol interface");
+    }
+}
+
+fn test_install_protocol(bt: &BootServices) {
+    #[unsafe_protocol("0c2a4b7e-6d1f-4f55-9e3c-2b8d7a91c6e4")]
+    struct FirstProtocol(u32);
+
+    #[unsafe_protocol("7e91f3a0-5b2c-4c8e-a6d4-93f1b05e2d78")]
+    struct SecondProtocol(u32);
+
+    let read_first = |handle| {
+        let protocol = unsafe {
+            bt.open_protocol::<FirstProtocol>(
+                OpenProtocolParams {
+                    handle,
+                    agent: bt.image_handle(),
+                    controller: None,
+                },
+                OpenProtocolAttributes::GetProtocol,
+            )
+        }
+        .expect("Failed to open protocol");
+        protocol.0
+    };
+
+    let mut installed = bt
+        .install_protocol(None, Box::pin(FirstProtocol(1)))
+        .expect("Failed to install protocol");
+    let handle = installed.handle();
+    assert_eq!(read_first(handle), 1);
+    let old = installed
+        .reinstall(Box::pin(FirstProtocol(2)))
+        .expect("Failed to reinstall protocol");
+    assert_eq!(old.0, 1);
+    assert_eq!(installed.interface().0, 2);
+    assert_eq!(read_first(handle), 2);
+    let interface = installed.uninstall().expect("Failed to uninstall protocol");
+    assert_eq!(interface.0, 2);
+    assert!(bt.find_handles::<FirstProtocol>().is_err());
+
+    let mut interfaces = ProtocolInterfaces::new();
+    interfaces.push(Box::pin(FirstProtocol(3)));
+    interfaces.push(Box::pin(SecondProtocol(4)));
+    let installed = bt
+        .install_protocols(None, interfaces)
+        .expect("Failed to install protocols");
+    assert_eq!(read_first(installed.handle()), 3);
+    assert_eq!(
+        bt.find_handles::<SecondProtocol>(),
+        Ok(vec![installed.handle()])
+    );
+    let second = unsafe {
+        bt.open_protocol::<SecondProtocol>(
+            OpenProtocolParams {
+                handle: installed.handle(),
+                agent: bt.image_handle(),
+                controller: None,
+            },
+            OpenProtocolAttributes::GetProtocol,
+        )
+    }
+    .expect("Failed to open protocol");
+    assert_eq!(second.0, 4);
+    drop(second);
+    drop(installed);
+    assert!(bt.find_handles::<FirstProtocol>().is_err());
+    assert!(bt.find_handles::<SecondProtocol>().is_err());
+}
+
 fn test_install_configuration_table(st: &SystemTable<Boot>) {
+    const ID: Guid = guid!("3bdb3089-5662-42df-840e-3922ed6467c9");
+
+    #[repr(transparent)]
+    struct TestTable(u8);
+
+    unsafe impl ConfigTable for TestTable {
+        const GUID: Guid = ID;
+
+        unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+            let table = &*ptr.cast::<Self>();
+            (table.0 == 42).then_some(table)
+        }
+    }
+
+    assert!(st.find_config_table::<TestTable>().is_none());
+
     let config = st
         .boot_services()
         .allocate_pool(MemoryType::ACPI_RECLAIM, 1)
@@ -193,7 +441,6 @@ fn test_install_configuration_table(st: &SystemTable<Boot>) {
     unsafe { config.write(42) };
 
     let count = st.config_table().len();
-    const ID: Guid = guid!("3bdb3089-5662-42df-840e-3922ed6467c9");
 
     unsafe {
         st.boot_services()
@@ -208,4 +455,109 @@ fn test_install_configuration_table(st: &SystemTable<Boot>) {
         .find(|ct| ct.guid == ID)
         .expect("Failed to find test config table");
     assert_eq!(unsafe { *(config_entry.address as *const u8) }, 42);
+
+    let table = st
+        .find_config_table::<TestTable>()
+        .expect("Failed to find typed test config table");
+    assert_eq!(table.0, 42);
+}
+
+fn test_acpi_tables(st: &SystemTable<Boot>) {
+    let tables = AcpiTables::from_system_table(st).expect("Failed to find ACPI tables");
+    assert!(tables.iter().all(|table| table.is_ok()));
+
+    let fadt = tables.get::<Fadt>().expect("Failed to find FADT");
+    info!("FADT revision: {}", fadt.sdt().revision());
+    tables.dsdt().expect("Failed to find DSDT");
+
+    let madt = tables.get::<Madt>().expect("Failed to find MADT");
+    assert!(madt.enabled_processor_count() >= 1);
+}
+
+fn test_smbios_tables(st: &SystemTable<Boot>) {
+    let smbios = SmbiosTable::from_system_table(st).expect("Failed to find SMBIOS tables");
+    info!("SMBIOS version: {}", smbios.version());
+
+    let bios = smbios
+        .find::<BiosInformation>()
+        .expect("Failed to find BIOS information");
+    info!("BIOS vendor: {:?}", bios.vendor());
+
+    assert!(smbios.structures_of_type::<ProcessorInformation>().count() >= 1);
+}
+
+fn test_memory_attributes_table(st: &SystemTable<Boot>) {
+    let Some(table) = st.find_config_table::<MemoryAttributesTable>() else {
+        info!("Memory attributes table is not present");
+        return;
+    };
+    info!("Memory attributes table has {} entries", table.len());
+
+    let bt = st.boot_services();
+    let sizes = bt.memory_map_size();
+    let mut buffer = vec![0_u8; sizes.map_size + 2 * sizes.entry_size];
+    let mut memory_map = bt
+        .memory_map(&mut buffer)
+        .expect("Failed to retrieve UEFI memory map");
+    memory_map.sort();
+
+    // Runtime regions must not be both writable and executable.
+    for region in table.runtime_regions(&memory_map) {
+        assert!(region.att.contains(MemoryAttribute::RUNTIME));
+        if region.ty != MemoryType::RUNTIME_SERVICES_CODE {
+            assert!(region.att.contains(MemoryAttribute::EXECUTE_PROTECT));
+        }
+    }
+}
+
+fn test_hob_list(st: &SystemTable<Boot>) {
+    let hobs = HobList::from_system_table(st).expect("Failed to find HOB list");
+    let handoff = hobs.handoff().expect("HOB list doesn't start with PHIT");
+    info!("Boot mode: {:?}", handoff.boot_mode);
+
+    let system_memory = hobs.iter().any(|hob| {
+        matches!(hob, Hob::ResourceDescriptor(resource)
+            if resource.resource_type == ResourceType::SYSTEM_MEMORY)
+    });
+    assert!(system_memory, "No system memory resource descriptor");
+}
+
+fn test_dxe_services(st: &SystemTable<Boot>) {
+    let dxe = st
+        .find_config_table::<DxeServices>()
+        .expect("Failed to find DXE services table");
+
+    let map = dxe
+        .memory_space_map(st.boot_services())
+        .expect("Failed to get GCD memory space map");
+    let desc = map
+        .iter()
+        .find(|desc| desc.gcd_memory_type == GcdMemoryType::SYSTEM_MEMORY)
+        .expect("No system memory in GCD memory space map");
+
+    let lookup = dxe
+        .get_memory_space_descriptor(desc.base_address)
+        .expect("Failed to get GCD memory space descriptor");
+    assert_eq!(lookup, *desc);
+
+    // Check that the map is sorted and has no gaps.
+    for pair in map.windows(2) {
+        assert_eq!(pair[0].base_address + pair[0].length, pair[1].base_address);
+    }
+}
+
+fn test_debug_image_info_table(st: &SystemTable<Boot>) {
+    let table = st
+        .find_config_table::<DebugImageInfoTable>()
+        .expect("Failed to find debug image info table");
+
+    // The table should list this image.
+    let image = table
+        .find_image(test_debug_image_info_table as *const () as usize)
+        .expect("Failed to find the test runner in the debug image info table");
+    assert_eq!(image.handle(), st.boot_services().image_handle());
+    info!("Test runner loaded at {:#x}", image.base());
+    if let Some(path) = image.pdb_path() {
+        info!("Test runner symbols: {path:?}");
+    }
 }
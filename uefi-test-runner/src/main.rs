@@ -6,13 +6,15 @@ extern crate log;
 #[macro_use]
 extern crate alloc;
 
+use alloc::boxed::Box;
 use alloc::string::ToString;
 use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
 use uefi::prelude::*;
 use uefi::proto::console::serial::Serial;
 use uefi::proto::device_path::build::{self, DevicePathBuilder};
 use uefi::proto::device_path::messaging::Vendor;
-use uefi::table::boot::MemoryType;
+use uefi::table::boot::{EventGroup, MemoryType, Tpl};
 use uefi::Result;
 use uefi::{print, println};
 
@@ -182,6 +184,9 @@ fn send_request_to_host(bt: &BootServices, request: HostRequest) {
     }
 }
 
+/// Set by the closure of a `BEFORE_EXIT_BOOT_SERVICES` group event.
+static BEFORE_EXIT_BOOT_SERVICES_SIGNALED: AtomicBool = AtomicBool::new(false);
+
 fn shutdown(mut st: SystemTable<Boot>) -> ! {
     // Get our text output back.
     st.stdout().reset(false).unwrap();
@@ -196,10 +201,23 @@ fn shutdown(mut st: SystemTable<Boot>) -> ! {
     // type of regression this prevents.
     info!("LOGGING_STILL_WORKING_RIGHT_BEFORE_EBS");
 
+    // The closure only touches a static, so it can run while boot services
+    // are being exited.
+    let _event = unsafe {
+        st.boot_services().create_group_event_unchecked(
+            EventGroup::BEFORE_EXIT_BOOT_SERVICES,
+            Tpl::CALLBACK,
+            Box::new(|_| BEFORE_EXIT_BOOT_SERVICES_SIGNALED.store(true, Ordering::Release)),
+        )
+    }
+    .expect("Failed to create group event")
+    .leak();
+
     info!("Testing complete, exiting boot services...");
 
     // Exit boot services as a proof that it works :)
     let (st, mmap) = unsafe { st.exit_boot_services(MemoryType::LOADER_DATA) };
+    assert!(BEFORE_EXIT_BOOT_SERVICES_SIGNALED.load(Ordering::Acquire));
 
     info!("Memory Map:");
     for desc in mmap.entries() {
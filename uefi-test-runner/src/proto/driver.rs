@@ -1,7 +1,17 @@
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::mem;
 use uefi::prelude::*;
-use uefi::proto::driver::{ComponentName, ComponentName2, LanguageError, LanguageIter};
+use uefi::proto::device_path::DevicePath;
+use uefi::proto::driver::{
+    protocol_name, ComponentName, ComponentName2, ComponentNames, DeviceNode, DeviceTree,
+    DriverBinding, DriverBindingContext, DriverBindingInterface, LanguageError, LanguageIter,
+};
+use uefi::proto::unsafe_protocol;
 use uefi::table::boot::{ScopedProtocol, SearchType};
-use uefi::{CStr16, Result};
+use uefi::{CStr16, Identify, Result};
 
 #[allow(deprecated)]
 use uefi::proto::driver::ComponentName1;
@@ -138,6 +148,159 @@ fn test_component_name<'a, C: ComponentNameInterface<'a>>(
         .expect("failed to find FAT controller");
 }
 
+/// Dummy device protocol managed by `TestDriver`.
+#[unsafe_protocol("d2d6e1a4-8f3b-4c27-9b5e-0a6c3f71e8d2")]
+struct TestDevice(u32);
+
+/// Driver that manages `TestDevice` controllers.
+struct TestDriver {
+    started: Rc<Cell<Option<(Handle, u32)>>>,
+}
+
+impl DriverBinding for TestDriver {
+    fn supported(
+        &self,
+        ctx: &DriverBindingContext,
+        controller: Handle,
+        _remaining_device_path: Option<&DevicePath>,
+    ) -> Result {
+        ctx.open_by_driver::<TestDevice>(controller).map(|_| ())
+    }
+
+    fn start(
+        &self,
+        ctx: &DriverBindingContext,
+        controller: Handle,
+        _remaining_device_path: Option<&DevicePath>,
+    ) -> Result {
+        let device = ctx.open_by_driver::<TestDevice>(controller)?;
+        self.started.set(Some((controller, device.0)));
+        mem::forget(device);
+        Ok(())
+    }
+
+    fn stop(&self, ctx: &DriverBindingContext, controller: Handle, children: &[Handle]) -> Result {
+        assert!(children.is_empty());
+        unsafe { ctx.close_by_driver::<TestDevice>(controller) }?;
+        self.started.set(None);
+        Ok(())
+    }
+}
+
+/// Names of `TestDriver`.
+struct TestNames;
+
+impl ComponentNames for TestNames {
+    const LANGUAGES: &'static [&'static str] = &["en", "fr"];
+
+    fn driver_name(&self, language: &str) -> Option<&CStr16> {
+        match language {
+            "en" => Some(cstr16!("Test Driver")),
+            "fr" => Some(cstr16!("Pilote de test")),
+            _ => None,
+        }
+    }
+
+    fn controller_name(
+        &self,
+        _controller: Handle,
+        child: Option<Handle>,
+        language: &str,
+    ) -> Option<&CStr16> {
+        match (child, language) {
+            (None, "en") => Some(cstr16!("Test Device")),
+            _ => None,
+        }
+    }
+}
+
+fn test_driver_binding(bt: &BootServices) {
+    let image = bt.image_handle();
+    let started = Rc::new(Cell::new(None));
+    let _installed = DriverBindingInterface::install_with_names(
+        bt,
+        image,
+        TestDriver {
+            started: started.clone(),
+        },
+        TestNames,
+    )
+    .expect("Failed to install driver binding and component name");
+
+    {
+        let names = bt
+            .open_protocol_exclusive::<ComponentName2>(image)
+            .expect("Failed to open component name");
+        assert!(names
+            .supported_languages()
+            .unwrap()
+            .eq(["en", "fr"].into_iter()));
+        assert_eq!(names.driver_name("en-US").unwrap(), cstr16!("Test Driver"));
+        assert_eq!(names.driver_name("fr").unwrap(), cstr16!("Pilote de test"));
+        assert_eq!(
+            names.driver_name("de").unwrap_err().status(),
+            Status::UNSUPPORTED
+        );
+        assert_eq!(
+            names.controller_name(image, None, "en").unwrap(),
+            cstr16!("Test Device")
+        );
+    }
+
+    let device = bt
+        .install_protocol(None, Box::pin(TestDevice(42)))
+        .expect("Failed to install device");
+    bt.connect_controller(device.handle(), Some(image), None, false)
+        .expect("Failed to connect controller");
+    assert_eq!(started.get(), Some((device.handle(), 42)));
+
+    let entries = bt
+        .open_protocol_information(device.handle(), &TestDevice::GUID)
+        .expect("Failed to get open protocol information");
+    assert!(entries
+        .iter()
+        .any(|entry| entry.agent() == Some(image) && entry.is_by_driver()));
+
+    bt.disconnect_controller(device.handle(), Some(image), None)
+        .expect("Failed to disconnect controller");
+    assert_eq!(started.get(), None);
+}
+
+fn test_device_tree(bt: &BootServices) {
+    fn visit(node: &DeviceNode, depth: usize, count: &mut usize, managed: &mut usize) {
+        *count += 1;
+        if !node.drivers().is_empty() {
+            *managed += 1;
+        }
+        let names: Vec<_> = node.protocols().iter().filter_map(protocol_name).collect();
+        info!(
+            "{:depth$}{:?} {:?}",
+            "",
+            node.handle(),
+            names,
+            depth = depth * 2
+        );
+        for child in node.children() {
+            visit(child, depth + 1, count, managed);
+        }
+    }
+
+    let tree = DeviceTree::new(bt).expect("Failed to build device tree");
+    assert!(!tree.roots().is_empty());
+    assert!(tree.roots().iter().all(|root| root.device_path().is_some()));
+
+    let mut count = 0;
+    let mut managed = 0;
+    for root in tree.roots() {
+        visit(root, 0, &mut count, &mut managed);
+    }
+    // At least the disks used by the test runner have child controllers
+    // that are managed by a driver.
+    assert!(tree.roots().iter().any(|root| !root.children().is_empty()));
+    assert!(managed > 0);
+    info!("Device tree has {count} controllers, {managed} managed by drivers");
+}
+
 pub fn test(boot_services: &BootServices) {
     info!("Running component name test");
 
@@ -145,4 +308,10 @@ pub fn test(boot_services: &BootServices) {
     test_component_name::<ScopedProtocol<ComponentName1>>(boot_services, "eng");
     test_component_name::<ScopedProtocol<ComponentName2>>(boot_services, "en");
     test_component_name::<ComponentName>(boot_services, "en");
+
+    info!("Running driver binding test");
+    test_driver_binding(boot_services);
+
+    info!("Running device tree test");
+    test_device_tree(boot_services);
 }
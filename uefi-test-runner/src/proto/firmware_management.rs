@@ -0,0 +1,25 @@
+use uefi::proto::firmware_management::FirmwareManagement;
+use uefi::table::boot::BootServices;
+
+pub fn test(bt: &BootServices) {
+    info!("Running firmware management protocol test");
+
+    let Ok(handles) = bt.find_handles::<FirmwareManagement>() else {
+        info!("Firmware management protocol is not supported");
+        return;
+    };
+
+    for handle in handles {
+        let mut fmp = bt
+            .open_protocol_exclusive::<FirmwareManagement>(handle)
+            .expect("failed to open firmware management protocol");
+
+        let info = fmp.get_image_info(bt).expect("failed to get image info");
+        for descriptor in &info.descriptors {
+            info!(
+                "Firmware image {}: type {}, version {:#x}",
+                descriptor.image_index, descriptor.image_type_id, descriptor.version
+            );
+        }
+    }
+}
@@ -14,11 +14,13 @@ pub fn test(image: Handle, st: &mut SystemTable<Boot>) {
     debug::test(bt);
     device_path::test(image, bt);
     driver::test(bt);
+    firmware_management::test(bt);
     loaded_image::test(image, bt);
     media::test(bt);
     network::test(bt);
     pi::test(bt);
     rng::test(bt);
+    security::test(bt);
     shell_params::test(bt);
     string::test(bt);
     misc::test(bt);
@@ -59,12 +61,14 @@ mod console;
 mod debug;
 mod device_path;
 mod driver;
+mod firmware_management;
 mod loaded_image;
 mod media;
 mod misc;
 mod network;
 mod pi;
 mod rng;
+mod security;
 mod shell_params;
 #[cfg(any(
     target_arch = "x86",
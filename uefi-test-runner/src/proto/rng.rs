@@ -1,4 +1,4 @@
-use uefi::proto::rng::{Rng, RngAlgorithmType};
+use uefi::proto::rng::{self, RandomSource, Rng, RngAlgorithmType};
 use uefi::table::boot::BootServices;
 
 pub fn test(bt: &BootServices) {
@@ -21,4 +21,21 @@ pub fn test(bt: &BootServices) {
 
     assert_ne!([0u8; 4], buf);
     info!("Random buffer : {:?}", buf);
+
+    let algorithm = rng
+        .get_rng_preferred(&rng::DEFAULT_ALGORITHM_PREFERENCE, &mut buf)
+        .unwrap();
+    info!("Preferred rng algorithm : {:?}", algorithm);
+
+    // Close the protocol so that the helpers can open it.
+    drop(rng);
+
+    let mut buf = [0u8; 16];
+    let source = rng::fill_bytes(bt, &mut buf).unwrap();
+    assert!(matches!(source, RandomSource::Protocol(_)));
+    assert_ne!([0u8; 16], buf);
+
+    let mut seed = [0u8; 16];
+    rng::fill_seed(bt, &mut seed).unwrap();
+    assert_ne!(buf, seed);
 }
@@ -0,0 +1,97 @@
+use uefi::proto::security::{
+    Hash2, Hash2ServiceBinding, Pkcs7Verify, Security2Arch, SignatureLists,
+};
+use uefi::proto::tcg::HashAlgorithm;
+use uefi::table::boot::BootServices;
+
+pub fn test(bt: &BootServices) {
+    test_hash2(bt);
+    test_pkcs7_verify(bt);
+    test_security2_arch(bt);
+}
+
+fn test_hash2(bt: &BootServices) {
+    info!("Running hash2 protocol test");
+
+    let Ok(sb_handle) = bt.get_handle_for_protocol::<Hash2ServiceBinding>() else {
+        info!("Hash2 service binding protocol is not supported");
+        return;
+    };
+    let mut service_binding = bt
+        .open_protocol_exclusive::<Hash2ServiceBinding>(sb_handle)
+        .expect("failed to open hash2 service binding protocol");
+    let child = service_binding
+        .create_child()
+        .expect("failed to create hash2 child");
+
+    {
+        let mut hash2 = bt
+            .open_protocol_exclusive::<Hash2>(child)
+            .expect("failed to open hash2 protocol");
+
+        // SHA-256 digest of "abc".
+        let expected = [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ];
+
+        assert_eq!(hash2.hash_size(HashAlgorithm::SHA256).unwrap(), 32);
+
+        let digest = hash2.hash(HashAlgorithm::SHA256, b"abc").unwrap();
+        assert_eq!(digest.as_bytes(), expected);
+
+        let mut session = hash2.hash_init(HashAlgorithm::SHA256).unwrap();
+        session.hash_update(b"a").unwrap();
+        session.hash_update(b"bc").unwrap();
+        let digest = session.hash_final().unwrap();
+        assert_eq!(digest.as_bytes(), expected);
+    }
+
+    service_binding
+        .destroy_child(child)
+        .expect("failed to destroy hash2 child");
+}
+
+fn test_pkcs7_verify(bt: &BootServices) {
+    info!("Running pkcs7 verify protocol test");
+
+    let Ok(handle) = bt.get_handle_for_protocol::<Pkcs7Verify>() else {
+        info!("Pkcs7 verify protocol is not supported");
+        return;
+    };
+    let mut pkcs7 = bt
+        .open_protocol_exclusive::<Pkcs7Verify>(handle)
+        .expect("failed to open pkcs7 verify protocol");
+
+    // Garbage data must not verify against an empty database.
+    let allowed_db = SignatureLists::new(&[]).unwrap();
+    let mut content = [0; 16];
+    pkcs7
+        .verify_buffer(
+            &[1, 2, 3],
+            Some(b"abc"),
+            allowed_db,
+            None,
+            None,
+            &mut content,
+        )
+        .expect_err("pkcs7 verify accepted invalid signed data");
+}
+
+fn test_security2_arch(bt: &BootServices) {
+    info!("Running security2 arch protocol test");
+
+    let Ok(handle) = bt.get_handle_for_protocol::<Security2Arch>() else {
+        info!("Security2 arch protocol is not supported");
+        return;
+    };
+    let security2 = bt
+        .open_protocol_exclusive::<Security2Arch>(handle)
+        .expect("failed to open security2 arch protocol");
+
+    // The result depends on whether Secure Boot is enabled, so just check
+    // that the call returns.
+    let result = security2.file_authentication(None, &[0; 64], false);
+    info!("Security2 file authentication of invalid image: {result:?}");
+}
@@ -0,0 +1,500 @@
+//! Building and passing update capsules to the firmware.
+//!
+//! [`RuntimeServices::update_capsule`] takes the capsules both as an array of
+//! headers and as a scatter-gather list of the physical memory blocks
+//! containing them. For capsules that persist across a reset, the firmware
+//! finds the capsules after the reset by walking that list, so both the
+//! capsule data and the list must stay in memory until then.
+//!
+//! [`CapsuleBuilder`] copies capsules into page allocations and creates the
+//! scatter-gather list for them:
+//!
+//! ```no_run
+//! use uefi::capsule::CapsuleBuilder;
+//! use uefi::table::{Boot, SystemTable};
+//! use uefi::{Result, Status};
+//!
+//! fn update_firmware(st: &SystemTable<Boot>, capsule: &[u8]) -> Result {
+//!     let mut builder = CapsuleBuilder::new(st.boot_services());
+//!     builder.add(capsule)?;
+//!     let update = builder.build()?;
+//!
+//!     let rt = st.runtime_services();
+//!     if let Some(reset_type) = update.update(rt)? {
+//!         // The capsule is processed after the reset.
+//!         rt.reset(reset_type, Status::SUCCESS, None);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::table::boot::{AllocateType, BootServices, MemoryType, PAGE_SIZE};
+use crate::table::runtime::{
+    CapsuleBlockDescriptor, CapsuleHeader, CapsuleInfo, PhysicalAddress, ResetType,
+    RuntimeServices, VariableAttributes, VariableVendor,
+};
+use crate::{cstr16, guid, CStr16, Result, Status};
+use alloc::vec::Vec;
+use core::{mem, ptr, slice};
+
+pub use uefi_raw::capsule::CapsuleFlags;
+
+/// Vendor GUID of the [`CAPSULE_UPDATE_DATA`] variable. This is specific to
+/// EDK2-based firmware.
+pub const CAPSULE_VENDOR: VariableVendor =
+    VariableVendor(guid!("711c703f-c285-4b10-a3b0-36ecbd3c8be2"));
+
+/// Name of the variable in which EDK2-based firmware stores the physical
+/// address of the scatter-gather list of capsules to process after a reset.
+/// This variable is not defined by the UEFI Specification.
+pub const CAPSULE_UPDATE_DATA: &CStr16 = cstr16!("CapsuleUpdateData");
+
+/// Number of block descriptors that fit in a page. The last descriptor of
+/// each page of the scatter-gather list either links to the next page or
+/// terminates the list.
+const DESCRIPTORS_PER_PAGE: usize = PAGE_SIZE / mem::size_of::<CapsuleBlockDescriptor>();
+
+/// Page allocations that are freed on drop, unless leaked.
+#[derive(Debug)]
+struct Pages<'a> {
+    bt: &'a BootServices,
+    allocations: Vec<(PhysicalAddress, usize)>,
+}
+
+impl<'a> Pages<'a> {
+    const fn new(bt: &'a BootServices) -> Self {
+        Self {
+            bt,
+            allocations: Vec::new(),
+        }
+    }
+
+    /// Allocate zeroed pages to hold `size` bytes.
+    fn allocate(&mut self, size: usize) -> Result<PhysicalAddress> {
+        let count = ((size + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
+        // The memory must stay reserved if the OS is booted before the
+        // capsules are processed by a reset.
+        let addr = self.bt.allocate_pages(
+            AllocateType::AnyPages,
+            MemoryType::RUNTIME_SERVICES_DATA,
+            count,
+        )?;
+        self.allocations.push((addr, count));
+        unsafe { ptr::write_bytes(addr as *mut u8, 0, count * PAGE_SIZE) };
+        Ok(addr)
+    }
+
+    /// Keep the pages allocated after `self` is dropped.
+    fn leak(&mut self) {
+        self.allocations.clear();
+    }
+}
+
+impl Drop for Pages<'_> {
+    fn drop(&mut self) {
+        for &(addr, count) in &self.allocations {
+            // Ignore errors, there is nothing more to do with the pages.
+            let _ = unsafe { self.bt.free_pages(addr, count) };
+        }
+    }
+}
+
+/// Builder for the capsules passed to a single call of
+/// [`RuntimeServices::update_capsule`].
+///
+/// Each added capsule is copied into a single page allocation, since the
+/// firmware expects each capsule to be contiguous in memory. Calling
+/// [`build`] creates the scatter-gather list describing the capsules, in
+/// blocks of at most [`block_size`] bytes.
+///
+/// All memory allocated by the builder is freed on drop, unless it is
+/// needed by the firmware after a reset.
+///
+/// [`block_size`]: Self::block_size
+/// [`build`]: Self::build
+#[derive(Debug)]
+pub struct CapsuleBuilder<'a> {
+    pages: Pages<'a>,
+    block_size: usize,
+    headers: Vec<PhysicalAddress>,
+    blocks: Vec<CapsuleBlockDescriptor>,
+    persist: bool,
+}
+
+impl<'a> CapsuleBuilder<'a> {
+    /// Create a builder that allocates memory with `bt`.
+    #[must_use]
+    pub const fn new(bt: &'a BootServices) -> Self {
+        Self {
+            pages: Pages::new(bt),
+            block_size: usize::MAX,
+            headers: Vec::new(),
+            blocks: Vec::new(),
+            persist: false,
+        }
+    }
+
+    /// Set the maximum size of the scatter-gather blocks describing the
+    /// capsules added after this call. By default, each capsule is
+    /// described by a single block.
+    ///
+    /// This only affects the scatter-gather list; the capsules themselves
+    /// are always contiguous in memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    pub fn block_size(&mut self, block_size: usize) -> &mut Self {
+        assert_ne!(block_size, 0, "block size must not be zero");
+        self.block_size = block_size;
+        self
+    }
+
+    /// Copy a capsule, including its [`CapsuleHeader`], into the builder.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `capsule` doesn't start with a valid
+    ///   header, its size doesn't match the size in the header, or its
+    ///   flags are invalid.
+    /// * [`Status::OUT_OF_RESOURCES`]: the memory for the copy could not be
+    ///   allocated.
+    pub fn add(&mut self, capsule: &[u8]) -> Result {
+        let header = validate_header(capsule)?;
+
+        let addr = self.pages.allocate(capsule.len())?;
+        unsafe { ptr::copy_nonoverlapping(capsule.as_ptr(), addr as *mut u8, capsule.len()) };
+        self.blocks
+            .extend(split_blocks(addr, capsule.len(), self.block_size));
+        self.headers.push(addr);
+        self.persist |= header.flags.contains(CapsuleFlags::PERSIST_ACROSS_RESET);
+        Ok(())
+    }
+
+    /// Create the scatter-gather list of the added capsules.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: no capsules were added.
+    /// * [`Status::OUT_OF_RESOURCES`]: the memory for the list could not be
+    ///   allocated.
+    pub fn build(mut self) -> Result<CapsuleUpdate<'a>> {
+        if self.headers.is_empty() {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        let list_pages = (0..descriptor_page_count(self.blocks.len()))
+            .map(|_| self.pages.allocate(PAGE_SIZE))
+            .collect::<Result<Vec<_>>>()?;
+        unsafe { write_descriptor_chain(&self.blocks, &list_pages) };
+
+        let size = self.blocks.iter().map(|block| block.length).sum::<u64>()
+            + (list_pages.len() * PAGE_SIZE) as u64;
+        Ok(CapsuleUpdate {
+            pages: self.pages,
+            headers: self.headers,
+            scatter_gather_list: list_pages[0],
+            size,
+            persist: self.persist,
+        })
+    }
+}
+
+/// Capsules ready to be passed to the firmware, created by
+/// [`CapsuleBuilder::build`].
+#[derive(Debug)]
+pub struct CapsuleUpdate<'a> {
+    pages: Pages<'a>,
+    headers: Vec<PhysicalAddress>,
+    scatter_gather_list: PhysicalAddress,
+    size: u64,
+    persist: bool,
+}
+
+impl CapsuleUpdate<'_> {
+    /// Physical address of the first page of the scatter-gather list.
+    #[must_use]
+    pub const fn scatter_gather_list(&self) -> PhysicalAddress {
+        self.scatter_gather_list
+    }
+
+    /// Total size in bytes of the capsules and the scatter-gather list.
+    #[must_use]
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns true if any of the capsules is processed after a reset.
+    #[must_use]
+    pub const fn persists_across_reset(&self) -> bool {
+        self.persist
+    }
+
+    /// Get the headers of the capsules.
+    fn headers(&self) -> Vec<&CapsuleHeader> {
+        self.headers
+            .iter()
+            .map(|addr| unsafe { &*(*addr as *const CapsuleHeader) })
+            .collect()
+    }
+
+    /// Check whether the firmware supports the capsules.
+    ///
+    /// See [`RuntimeServices::query_capsule_capabilities`].
+    pub fn query_capabilities(&self, rt: &RuntimeServices) -> Result<CapsuleInfo> {
+        rt.query_capsule_capabilities(&self.headers())
+    }
+
+    /// Pass the capsules to the firmware with
+    /// [`RuntimeServices::update_capsule`].
+    ///
+    /// If any of the capsules is processed after a reset, the memory of the
+    /// capsules is left allocated and the type of reset required is
+    /// returned. The caller should then reset the system with
+    /// [`RuntimeServices::reset`]. Capsules with
+    /// [`CapsuleFlags::INITIATE_RESET`] set make the firmware reset the
+    /// system itself, in which case this function doesn't return on
+    /// success.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BAD_BUFFER_SIZE`]: one of the capsules is larger than
+    ///   supported by the firmware.
+    ///
+    /// Errors returned by [`RuntimeServices::query_capsule_capabilities`]
+    /// and [`RuntimeServices::update_capsule`] are passed through.
+    pub fn update(mut self, rt: &RuntimeServices) -> Result<Option<ResetType>> {
+        let info = self.query_capabilities(rt)?;
+        let too_large = self
+            .headers()
+            .iter()
+            .any(|header| u64::from(header.capsule_image_size) > info.maximum_capsule_size);
+        if too_large {
+            return Err(Status::BAD_BUFFER_SIZE.into());
+        }
+
+        let list = unsafe {
+            slice::from_raw_parts(
+                self.scatter_gather_list as *const CapsuleBlockDescriptor,
+                DESCRIPTORS_PER_PAGE,
+            )
+        };
+        rt.update_capsule(&self.headers(), list)?;
+
+        if self.persist {
+            self.pages.leak();
+            Ok(Some(info.reset_type))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Store the address of the scatter-gather list in the
+    /// [`CAPSULE_UPDATE_DATA`] variable and leave the memory of the capsules
+    /// allocated.
+    ///
+    /// This is not a delivery method defined by the UEFI Specification. It
+    /// relies on the internal variable that EDK2-based firmware uses to find
+    /// the capsules after a reset, and has no effect on other firmware. Use
+    /// [`update`] where possible.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: none of the capsules persist across
+    ///   a reset.
+    ///
+    /// Errors returned by [`RuntimeServices::set_variable`] are passed
+    /// through.
+    ///
+    /// [`update`]: Self::update
+    pub fn set_update_variable(mut self, rt: &RuntimeServices) -> Result {
+        if !self.persist {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        rt.set_variable(
+            CAPSULE_UPDATE_DATA,
+            &CAPSULE_VENDOR,
+            VariableAttributes::NON_VOLATILE
+                | VariableAttributes::BOOTSERVICE_ACCESS
+                | VariableAttributes::RUNTIME_ACCESS,
+            &self.scatter_gather_list.to_ne_bytes(),
+        )?;
+        self.pages.leak();
+        Ok(())
+    }
+}
+
+/// Check that `capsule` starts with a valid header that matches its size.
+fn validate_header(capsule: &[u8]) -> Result<CapsuleHeader> {
+    if capsule.len() < mem::size_of::<CapsuleHeader>() {
+        return Err(Status::INVALID_PARAMETER.into());
+    }
+    let header = unsafe { capsule.as_ptr().cast::<CapsuleHeader>().read_unaligned() };
+
+    let needs_persist = CapsuleFlags::POPULATE_SYSTEM_TABLE | CapsuleFlags::INITIATE_RESET;
+    let valid = (header.header_size as usize) >= mem::size_of::<CapsuleHeader>()
+        && header.header_size <= header.capsule_image_size
+        && header.capsule_image_size as usize == capsule.len()
+        && (!header.flags.intersects(needs_persist)
+            || header.flags.contains(CapsuleFlags::PERSIST_ACROSS_RESET));
+    if valid {
+        Ok(header)
+    } else {
+        Err(Status::INVALID_PARAMETER.into())
+    }
+}
+
+/// Split the `len` bytes at `address` into blocks of at most `block_size`
+/// bytes.
+fn split_blocks(
+    address: PhysicalAddress,
+    len: usize,
+    block_size: usize,
+) -> impl Iterator<Item = CapsuleBlockDescriptor> {
+    (0..len)
+        .step_by(block_size)
+        .map(move |offset| CapsuleBlockDescriptor {
+            length: (len - offset).min(block_size) as u64,
+            address: address + offset as u64,
+        })
+}
+
+/// Number of pages needed for a scatter-gather list of `block_count`
+/// blocks.
+const fn descriptor_page_count(block_count: usize) -> usize {
+    let per_page = DESCRIPTORS_PER_PAGE - 1;
+    if block_count == 0 {
+        1
+    } else {
+        (block_count + per_page - 1) / per_page
+    }
+}
+
+/// Write the scatter-gather list of `blocks` to `pages`.
+///
+/// # Safety
+///
+/// Each of `pages` must be the address of [`DESCRIPTORS_PER_PAGE`] writable
+/// descriptors, and there must be [`descriptor_page_count`] pages.
+unsafe fn write_descriptor_chain(blocks: &[CapsuleBlockDescriptor], pages: &[PhysicalAddress]) {
+    let mut chunks = blocks.chunks(DESCRIPTORS_PER_PAGE - 1);
+    for (i, &page) in pages.iter().enumerate() {
+        let chunk = chunks.next().unwrap_or_default();
+        let page = page as *mut CapsuleBlockDescriptor;
+        ptr::copy_nonoverlapping(chunk.as_ptr(), page, chunk.len());
+        // Link to the next page, or terminate the list with a null address.
+        page.add(chunk.len()).write(CapsuleBlockDescriptor {
+            length: 0,
+            address: pages.get(i + 1).copied().unwrap_or(0),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::vec;
+
+    fn make_capsule(size: usize, flags: CapsuleFlags) -> Vec<u8> {
+        let header = CapsuleHeader {
+            capsule_guid: guid!("6dcbd5ed-e82d-4c44-bda1-7194199ad92a"),
+            header_size: mem::size_of::<CapsuleHeader>() as u32,
+            flags,
+            capsule_image_size: size as u32,
+        };
+        let mut capsule = vec![0xaa; size];
+        unsafe {
+            capsule
+                .as_mut_ptr()
+                .cast::<CapsuleHeader>()
+                .write_unaligned(header)
+        };
+        capsule
+    }
+
+    #[test]
+    fn test_validate_header() {
+        let capsule = make_capsule(0x40, CapsuleFlags::PERSIST_ACROSS_RESET);
+        assert!(validate_header(&capsule).is_ok());
+
+        // Size mismatch.
+        assert!(validate_header(&capsule[..0x3f]).is_err());
+        assert!(validate_header(&capsule[..0x10]).is_err());
+
+        // Reset without persisting.
+        let capsule = make_capsule(0x40, CapsuleFlags::INITIATE_RESET);
+        assert!(validate_header(&capsule).is_err());
+        let capsule = make_capsule(
+            0x40,
+            CapsuleFlags::INITIATE_RESET | CapsuleFlags::PERSIST_ACROSS_RESET,
+        );
+        assert!(validate_header(&capsule).is_ok());
+    }
+
+    #[test]
+    fn test_split_blocks() {
+        let blocks: Vec<_> = split_blocks(0x1000, 0x50, usize::MAX).collect();
+        assert_eq!(
+            blocks,
+            [CapsuleBlockDescriptor {
+                length: 0x50,
+                address: 0x1000,
+            }]
+        );
+
+        let blocks: Vec<_> = split_blocks(0x1000, 0x50, 0x20).collect();
+        assert_eq!(
+            blocks,
+            [
+                CapsuleBlockDescriptor {
+                    length: 0x20,
+                    address: 0x1000,
+                },
+                CapsuleBlockDescriptor {
+                    length: 0x20,
+                    address: 0x1020,
+                },
+                CapsuleBlockDescriptor {
+                    length: 0x10,
+                    address: 0x1040,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_descriptor_chain() {
+        assert_eq!(DESCRIPTORS_PER_PAGE, 256);
+        assert_eq!(descriptor_page_count(0), 1);
+        assert_eq!(descriptor_page_count(255), 1);
+        assert_eq!(descriptor_page_count(256), 2);
+
+        let blocks: Vec<_> = (1..=300)
+            .map(|i| CapsuleBlockDescriptor {
+                length: i,
+                address: i * 0x1000,
+            })
+            .collect();
+        let mut pages = [
+            Box::new([CapsuleBlockDescriptor::default(); DESCRIPTORS_PER_PAGE]),
+            Box::new([CapsuleBlockDescriptor::default(); DESCRIPTORS_PER_PAGE]),
+        ];
+        let addrs: Vec<_> = pages
+            .iter_mut()
+            .map(|page| page.as_mut_ptr() as PhysicalAddress)
+            .collect();
+        unsafe { write_descriptor_chain(&blocks, &addrs) };
+
+        assert_eq!(pages[0][..255], blocks[..255]);
+        assert_eq!(
+            pages[0][255],
+            CapsuleBlockDescriptor {
+                length: 0,
+                address: addrs[1],
+            }
+        );
+        assert_eq!(pages[1][..45], blocks[255..]);
+        assert_eq!(pages[1][45], CapsuleBlockDescriptor::default());
+    }
+}
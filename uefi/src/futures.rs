@@ -0,0 +1,648 @@
+//! Futures driven by UEFI events, and a minimal executor to run them.
+//!
+//! UEFI is single-threaded, but many operations are asynchronous: timers,
+//! key presses and non-blocking I/O all signal an [`Event`] when they are
+//! done. The futures in this module wait for these events, so that they can
+//! be composed with `async` code instead of hand-written polling loops.
+//!
+//! [`block_on`] runs a future to completion. While the future is pending,
+//! it waits with [`BootServices::wait_for_event`] for any of the events
+//! that the future is waiting for, so the processor is idle in between.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use core::cell::Cell;
+//! use core::time::Duration;
+//! use uefi::proto::console::text::{Input, Key};
+//! use uefi::futures::{block_on, read_key, sleep};
+//! use uefi::join;
+//! use uefi::table::boot::BootServices;
+//!
+//! fn wait_for_key(bt: &BootServices, input: &mut Input) -> uefi::Result<Key> {
+//!     let done = Cell::new(false);
+//!     let spinner = async {
+//!         for frame in ['|', '/', '-', '\\'].iter().cycle() {
+//!             if done.get() {
+//!                 break;
+//!             }
+//!             log::info!("{frame}");
+//!             sleep(bt, Duration::from_millis(100)).await?;
+//!         }
+//!         uefi::Result::Ok(())
+//!     };
+//!     let key = async {
+//!         let key = read_key(bt, input).await;
+//!         done.set(true);
+//!         key
+//!     };
+//!     let (key, spinner) = block_on(bt, join!(key, spinner))?;
+//!     spinner?;
+//!     key
+//! }
+//! ```
+
+use crate::proto::console::text::{Input, Key};
+use crate::proto::media::disk::DiskIo2Token;
+use crate::table::boot::{BootServices, EventType, Timer, Tpl};
+use crate::{Event, Result, ResultExt, Status, StatusExt};
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::{pin, Pin};
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+use core::{iter, mem, ptr};
+
+/// Events that the future run by [`block_on`] is waiting for, and the
+/// wakers to wake when they are signaled.
+type Waiting = RefCell<Vec<(Event, Waker)>>;
+
+/// [`Waiting`] list of the innermost running [`block_on`], or null.
+static WAITING: AtomicPtr<Waiting> = AtomicPtr::new(ptr::null_mut());
+
+/// Event services used by [`block_on`]. This is only implemented by
+/// [`BootServices`], and exists so that the executor can be tested on the
+/// host.
+trait EventServices: 'static {
+    fn create_wake_event(&self) -> Result<Event>;
+    fn wait_for_event(&self, events: &mut [Event]) -> Result<usize>;
+    fn signal_event(&self, event: &Event) -> Result;
+    fn close_event(&self, event: Event) -> Result;
+}
+
+impl EventServices for BootServices {
+    fn create_wake_event(&self) -> Result<Event> {
+        unsafe { self.create_event(EventType::empty(), Tpl::CALLBACK, None, None) }
+    }
+
+    fn wait_for_event(&self, events: &mut [Event]) -> Result<usize> {
+        self.wait_for_event(events).discard_errdata()
+    }
+
+    fn signal_event(&self, event: &Event) -> Result {
+        self.signal_event(event)
+    }
+
+    fn close_event(&self, event: Event) -> Result {
+        self.close_event(event)
+    }
+}
+
+/// Waker of [`block_on`]. Waking signals an event that is always part of
+/// the events that `block_on` waits for, so wakers can be called from
+/// event notification functions.
+struct WakeSignal<S> {
+    woken: AtomicBool,
+    services: *const S,
+    /// Event to signal, or null once `block_on` has returned.
+    event: AtomicPtr<core::ffi::c_void>,
+}
+
+// SAFETY: boot services can only be used on the bootstrap processor, so the
+// waker is never used concurrently.
+unsafe impl<S> Send for WakeSignal<S> {}
+unsafe impl<S> Sync for WakeSignal<S> {}
+
+impl<S: EventServices> Wake for WakeSignal<S> {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        let event = self.event.load(Ordering::Acquire);
+        if let Some(event) = unsafe { Event::from_ptr(event) } {
+            // Ignore the result, `signal_event` can't fail.
+            let _ = unsafe { (*self.services).signal_event(&event) };
+        }
+    }
+}
+
+/// Run `future` to completion.
+///
+/// While the future is pending, this waits for the events registered by
+/// the futures of this module, or for the future to be woken.
+///
+/// This must be called at [`Tpl::APPLICATION`], since it uses
+/// [`BootServices::wait_for_event`].
+///
+/// # Errors
+///
+/// Errors from creating the internal wake event or from
+/// [`BootServices::wait_for_event`] are returned.
+pub fn block_on<F: Future>(bt: &BootServices, future: F) -> Result<F::Output> {
+    run(bt, future)
+}
+
+/// Implementation of [`block_on`].
+fn run<S: EventServices, F: Future>(services: &S, future: F) -> Result<F::Output> {
+    let mut future = pin!(future);
+    let wake_event = services.create_wake_event()?;
+    let signal = Arc::new(WakeSignal {
+        woken: AtomicBool::new(false),
+        services,
+        event: AtomicPtr::new(wake_event.as_ptr()),
+    });
+    let waker = Waker::from(signal.clone());
+    let mut cx = Context::from_waker(&waker);
+    let waiting = Waiting::default();
+
+    let result = loop {
+        waiting.borrow_mut().clear();
+        signal.woken.store(false, Ordering::Release);
+
+        let previous = WAITING.swap(ptr::addr_of!(waiting).cast_mut(), Ordering::AcqRel);
+        let poll = future.as_mut().poll(&mut cx);
+        WAITING.store(previous, Ordering::Release);
+        if let Poll::Ready(output) = poll {
+            break Ok(output);
+        }
+        if signal.woken.load(Ordering::Acquire) {
+            continue;
+        }
+
+        let waiting = waiting.borrow();
+        let mut events: Vec<Event> = iter::once(&wake_event)
+            .chain(waiting.iter().map(|(event, _)| event))
+            .map(|event| unsafe { event.unsafe_clone() })
+            .collect();
+        let index = match services.wait_for_event(&mut events) {
+            Ok(index) => index,
+            Err(err) => break Err(err),
+        };
+        if index > 0 {
+            // `wait_for_event` cleared the signaled state of the event.
+            // Restore it, so that the future waiting for it sees it.
+            if let Err(err) = services.signal_event(&events[index]) {
+                break Err(err);
+            }
+            let (_, event_waker) = &waiting[index - 1];
+            if !event_waker.will_wake(&waker) {
+                event_waker.wake_by_ref();
+            }
+        }
+    };
+
+    signal.event.store(ptr::null_mut(), Ordering::Release);
+    // Ignore the result, the event was created by this function.
+    let _ = services.close_event(wake_event);
+    result
+}
+
+/// Register `event` to be waited for by the running [`block_on`].
+fn register(event: &Event, waker: &Waker) {
+    let waiting = WAITING.load(Ordering::Acquire);
+    if waiting.is_null() {
+        // Not running in `block_on`, so the event can only be polled.
+        waker.wake_by_ref();
+    } else {
+        let event = unsafe { event.unsafe_clone() };
+        unsafe { &*waiting }
+            .borrow_mut()
+            .push((event, waker.clone()));
+    }
+}
+
+/// Future that completes when an event is signaled.
+///
+/// The signaled state of the event is cleared when the future completes,
+/// as with [`BootServices::check_event`]. The event must not be of type
+/// [`EventType::NOTIFY_SIGNAL`].
+#[derive(Debug)]
+pub struct EventFuture<'a> {
+    boot_services: &'a BootServices,
+    event: &'a Event,
+}
+
+impl<'a> EventFuture<'a> {
+    /// Create a future waiting for `event`.
+    #[must_use]
+    pub const fn new(bt: &'a BootServices, event: &'a Event) -> Self {
+        Self {
+            boot_services: bt,
+            event,
+        }
+    }
+}
+
+impl Future for EventFuture<'_> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        match self
+            .boot_services
+            .check_event(unsafe { self.event.unsafe_clone() })
+        {
+            Ok(true) => Poll::Ready(Ok(())),
+            Ok(false) => {
+                register(self.event, cx.waker());
+                Poll::Pending
+            }
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+}
+
+/// Returns a future that completes after `duration`.
+///
+/// The timer is started when the future is first polled. Its resolution is
+/// 100 nanoseconds, but the firmware may signal it later than requested.
+#[must_use]
+pub const fn sleep(bt: &BootServices, duration: Duration) -> Sleep<'_> {
+    Sleep {
+        boot_services: bt,
+        duration,
+        timer: None,
+    }
+}
+
+/// Future returned by [`sleep`].
+#[derive(Debug)]
+pub struct Sleep<'a> {
+    boot_services: &'a BootServices,
+    duration: Duration,
+    timer: Option<Timer<'a>>,
+}
+
+impl Future for Sleep<'_> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        let this = self.get_mut();
+        let timer = match &mut this.timer {
+            Some(timer) => timer,
+            None => match Timer::one_shot(this.boot_services, this.duration) {
+                Ok(timer) => this.timer.insert(timer),
+                Err(err) => return Poll::Ready(Err(err)),
+            },
+        };
+        pin!(EventFuture::new(this.boot_services, timer.event())).poll(cx)
+    }
+}
+
+/// Returns a future that completes with the next key press on `input`.
+#[must_use]
+pub fn read_key<'a>(bt: &'a BootServices, input: &'a mut Input) -> ReadKey<'a> {
+    ReadKey {
+        boot_services: bt,
+        input,
+    }
+}
+
+/// Future returned by [`read_key`].
+#[derive(Debug)]
+pub struct ReadKey<'a> {
+    boot_services: &'a BootServices,
+    input: &'a mut Input,
+}
+
+impl Future for ReadKey<'_> {
+    type Output = Result<Key>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Key>> {
+        let this = self.get_mut();
+        loop {
+            match this.input.read_key() {
+                Ok(Some(key)) => return Poll::Ready(Ok(key)),
+                Ok(None) => {}
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+            let Some(event) = this.input.wait_for_key_event() else {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            };
+            match pin!(EventFuture::new(this.boot_services, &event)).poll(cx) {
+                // A key may be available now.
+                Poll::Ready(Ok(())) => continue,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Token of an asynchronous operation, such as a [`DiskIo2Token`].
+///
+/// The token has an event that the firmware signals when the operation
+/// completes, and a field where the firmware stores the status of the
+/// operation.
+pub trait CompletionToken {
+    /// Event signaled when the operation completes. If this is `None`, the
+    /// operation was performed synchronously.
+    fn event(&self) -> Option<&Event>;
+
+    /// Status of the completed operation.
+    fn status(&self) -> Status;
+}
+
+impl CompletionToken for DiskIo2Token {
+    fn event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    fn status(&self) -> Status {
+        // The status is written by the firmware before signaling the event.
+        unsafe { ptr::read_volatile(&self.transaction_status) }
+    }
+}
+
+/// Returns a future that completes when the operation of `token` is done,
+/// with the status of the operation.
+///
+/// The token must not be of type [`EventType::NOTIFY_SIGNAL`].
+#[must_use]
+pub const fn completion<'a, T: CompletionToken>(
+    bt: &'a BootServices,
+    token: &'a T,
+) -> Completion<'a, T> {
+    Completion {
+        boot_services: bt,
+        token,
+    }
+}
+
+/// Future returned by [`completion`].
+#[derive(Debug)]
+pub struct Completion<'a, T> {
+    boot_services: &'a BootServices,
+    token: &'a T,
+}
+
+impl<T: CompletionToken> Future for Completion<'_, T> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        if let Some(event) = self.token.event() {
+            match pin!(EventFuture::new(self.boot_services, event)).poll(cx) {
+                Poll::Ready(Ok(())) => {}
+                other => return other,
+            }
+        }
+        Poll::Ready(self.token.status().to_result())
+    }
+}
+
+/// Run several futures concurrently, and complete with a tuple of their
+/// outputs.
+///
+/// This evaluates to a future, which can be run with [`block_on`] or
+/// awaited in an `async` block. Each time it is polled, the futures that
+/// aren't done yet are polled in the order they were passed in.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::time::Duration;
+/// use uefi::futures::{block_on, sleep};
+/// use uefi::join;
+/// use uefi::table::boot::BootServices;
+///
+/// fn sleep_three_times(bt: &BootServices) -> uefi::Result {
+///     let (a, b, c) = block_on(
+///         bt,
+///         join!(
+///             sleep(bt, Duration::from_millis(10)),
+///             sleep(bt, Duration::from_millis(20)),
+///             sleep(bt, Duration::from_millis(30)),
+///         ),
+///     )?;
+///     a.and(b).and(c)
+/// }
+/// ```
+///
+/// [`block_on`]: crate::futures::block_on
+#[macro_export]
+macro_rules! join {
+    ($($future:expr),+ $(,)?) => {
+        $crate::join!(@ { () } $($future,)+)
+    };
+    // Pair each future with a tuple pattern prefix of `_`, one for each
+    // future before it, to access it in the tuple of all futures.
+    (@ { ($($count:tt)*) $(($($skip:tt)*) $done:expr,)* } $next:expr, $($rest:expr,)*) => {
+        $crate::join!(
+            @ { ($($count)* _) $(($($skip)*) $done,)* ($($count)*) $next, } $($rest,)*
+        )
+    };
+    (@ { ($($count:tt)*) $(($($skip:tt)*) $future:expr,)+ }) => {{
+        let futures = ($($crate::futures::MaybeDone::Pending($future),)+);
+        async move {
+            let mut futures = ::core::pin::pin!(futures);
+            ::core::future::poll_fn(move |cx| {
+                let mut done = true;
+                $(
+                    // SAFETY: the futures are pinned structurally, they are
+                    // never moved out of the tuple.
+                    let ($($skip,)* future, ..) = unsafe { futures.as_mut().get_unchecked_mut() };
+                    done &= unsafe { ::core::pin::Pin::new_unchecked(future) }.poll(cx);
+                )+
+                if !done {
+                    return ::core::task::Poll::Pending;
+                }
+                ::core::task::Poll::Ready(($({
+                    let ($($skip,)* future, ..) = unsafe { futures.as_mut().get_unchecked_mut() };
+                    unsafe { ::core::pin::Pin::new_unchecked(future) }.take()
+                },)+))
+            })
+            .await
+        }
+    }};
+}
+
+/// Future joined by [`join!`], or its output.
+#[doc(hidden)]
+pub enum MaybeDone<F: Future> {
+    Pending(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> Debug for MaybeDone<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pending(_) => "Pending",
+            Self::Done(_) => "Done",
+            Self::Taken => "Taken",
+        })
+    }
+}
+
+impl<F: Future> MaybeDone<F> {
+    /// Poll the future if it isn't done yet. Returns true if it's done.
+    pub fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> bool {
+        // SAFETY: the future is never moved out of `Pending`. Replacing the
+        // variant drops the future in place.
+        let this = unsafe { self.get_unchecked_mut() };
+        if let Self::Pending(future) = this {
+            match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                Poll::Ready(output) => *this = Self::Done(output),
+                Poll::Pending => return false,
+            }
+        }
+        true
+    }
+
+    /// Take the output of the future.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future isn't done, or if the output was already taken.
+    pub fn take(self: Pin<&mut Self>) -> F::Output {
+        // SAFETY: the variant is `Done`, so there is no pinned future.
+        let this = unsafe { self.get_unchecked_mut() };
+        match mem::replace(this, Self::Taken) {
+            Self::Done(output) => output,
+            _ => panic!("output of joined future is not available"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::future;
+    use core::ptr::NonNull;
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Event services that record which of them the executor uses.
+    #[derive(Default)]
+    struct MockServices {
+        calls: RefCell<Vec<&'static str>>,
+    }
+
+    impl EventServices for MockServices {
+        fn create_wake_event(&self) -> Result<Event> {
+            self.calls.borrow_mut().push("create");
+            Ok(unsafe { Event::from_ptr(NonNull::dangling().as_ptr()) }.unwrap())
+        }
+
+        fn wait_for_event(&self, _events: &mut [Event]) -> Result<usize> {
+            self.calls.borrow_mut().push("wait");
+            Ok(0)
+        }
+
+        fn signal_event(&self, _event: &Event) -> Result {
+            self.calls.borrow_mut().push("signal");
+            Ok(())
+        }
+
+        fn close_event(&self, _event: Event) -> Result {
+            self.calls.borrow_mut().push("close");
+            Ok(())
+        }
+    }
+
+    /// Future that is pending `count` times before completing.
+    struct Yield(u32);
+
+    impl Future for Yield {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+            if self.0 == 0 {
+                Poll::Ready(7)
+            } else {
+                self.0 -= 1;
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Future that wakes itself once before completing.
+    struct WakeOnce(bool);
+
+    impl Future for WakeOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Future that records its name in `log` each time it is polled, and
+    /// is pending `count` times before completing.
+    struct Logged<'a> {
+        name: &'static str,
+        count: u32,
+        log: &'a RefCell<Vec<&'static str>>,
+    }
+
+    impl Future for Logged<'_> {
+        type Output = &'static str;
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<&'static str> {
+            self.log.borrow_mut().push(self.name);
+            if self.count == 0 {
+                Poll::Ready(self.name)
+            } else {
+                self.count -= 1;
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_on_ready() {
+        let services = MockServices::default();
+        assert_eq!(run(&services, future::ready(3)), Ok(3));
+        assert_eq!(*services.calls.borrow(), ["create", "close"]);
+    }
+
+    #[test]
+    fn test_block_on_pending() {
+        let services = MockServices::default();
+        assert_eq!(run(&services, Yield(2)), Ok(7));
+        assert_eq!(
+            *services.calls.borrow(),
+            ["create", "wait", "wait", "close"]
+        );
+
+        // A future that wakes itself is polled again without waiting.
+        let services = MockServices::default();
+        assert_eq!(run(&services, WakeOnce(false)), Ok(()));
+        assert_eq!(*services.calls.borrow(), ["create", "signal", "close"]);
+    }
+
+    #[test]
+    fn test_join() {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(crate::join!(Yield(2), future::ready("ready")));
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready((7, "ready")));
+    }
+
+    #[test]
+    fn test_join_poll_order() {
+        let log = RefCell::new(Vec::new());
+        let logged = |name, count| Logged {
+            name,
+            count,
+            log: &log,
+        };
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(crate::join!(logged("a", 1), logged("b", 0), logged("c", 2),));
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(("a", "b", "c")));
+        // Futures that are done are not polled again.
+        assert_eq!(*log.borrow(), ["a", "b", "c", "a", "c", "c"]);
+    }
+}
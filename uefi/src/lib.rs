@@ -49,6 +49,8 @@
 //!   `Vec` rather than filling a statically-sized array. This requires
 //!   a global allocator; you can use the `global_allocator` feature or
 //!   provide your own.
+//! - `async`: Enable the [`futures`] module, with futures driven by UEFI
+//!   events and a minimal executor to run them. This implies `alloc`.
 //! - `global_allocator`: Set [`allocator::Allocator`] as the global Rust
 //!   allocator. This is a simple allocator that relies on the UEFI pool
 //!   allocator. You can choose to provide your own allocator instead of
@@ -64,6 +66,8 @@
 //!   features] in the nightly compiler.
 //!   As example, in conjunction with the `alloc`-feature, this gate allows
 //!   the `allocator_api` on certain functions.
+//! - `rand_core`: Implement the [`rand_core`] traits `RngCore` and
+//!   `CryptoRng` for the [`Rng`] protocol.
 //! - `qemu`: Enable some code paths to adapt their execution when executed
 //!   in QEMU, such as using the special `qemu-exit` device when the panic
 //!   handler is called.
@@ -77,6 +81,8 @@
 //! [`BootServices`]: table::boot::BootServices
 //! [`GlobalAlloc`]: alloc::alloc::GlobalAlloc
 //! [`SystemTable`]: table::SystemTable
+//! [`Rng`]: proto::rng::Rng
+//! [`rand_core`]: https://docs.rs/rand_core
 //! [`unsafe_protocol`]: proto::unsafe_protocol
 //! [contributing]: https://github.com/rust-osdev/uefi-rs/blob/main/CONTRIBUTING.md
 //! [issue tracker]: https://github.com/rust-osdev/uefi-rs/issues
@@ -127,9 +133,15 @@ pub mod prelude;
 
 pub mod allocator;
 
+#[cfg(feature = "alloc")]
+pub mod capsule;
+
 #[cfg(feature = "alloc")]
 pub mod fs;
 
+#[cfg(feature = "async")]
+pub mod futures;
+
 // As long as this is behind "alloc", we can simplify cfg-feature attributes in this module.
 #[cfg(feature = "alloc")]
 pub(crate) mod mem;
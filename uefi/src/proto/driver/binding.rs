@@ -0,0 +1,439 @@
+use crate::proto::device_path::{DevicePath, FfiDevicePath};
+use crate::proto::{Protocol, ProtocolPointer};
+use crate::table::boot::{
+    BootServices, OpenProtocolAttributes, OpenProtocolParams, ScopedProtocol,
+};
+use crate::table::system_table_boot;
+use crate::{Handle, Identify, Result, Status};
+use core::fmt::{self, Debug, Formatter};
+use core::slice;
+use uefi_raw::protocol::device_path::DevicePathProtocol;
+use uefi_raw::protocol::driver::DriverBindingProtocol;
+#[cfg(feature = "alloc")]
+use {
+    super::{ComponentName2Interface, ComponentNames},
+    crate::table::boot::{InstalledProtocols, ProtocolInterfaces},
+    alloc::boxed::Box,
+};
+
+/// Driver that follows the UEFI driver model.
+///
+/// The firmware calls the functions of this trait to connect the driver to
+/// controllers, and to disconnect it, for example when
+/// [`BootServices::connect_controller`] is called. To make the firmware
+/// aware of the driver, install a [`DriverBindingInterface`] on the image
+/// handle of the driver.
+///
+/// The functions are called with a [`DriverBindingContext`], which opens
+/// and closes protocols with the attributes that the driver model requires.
+///
+/// The functions take `&self`, because the firmware may call them
+/// recursively, for example when `start` connects the child controllers
+/// it creates. Use interior mutability for state that they change.
+pub trait DriverBinding: 'static {
+    /// Version of the driver. If several drivers support a controller, the
+    /// one with the highest version is started first.
+    ///
+    /// Versions 0x0 to 0xf are reserved for platform and OEM drivers, and
+    /// 0xffff_fff0 to 0xffff_ffff for IHV drivers.
+    const VERSION: u32 = 0x10;
+
+    /// Check whether the driver supports `controller`.
+    ///
+    /// This must not change the state of the controller, and must close
+    /// the protocols it opened before returning. Return
+    /// [`Status::UNSUPPORTED`] if the controller isn't supported, and
+    /// [`Status::ALREADY_STARTED`] or [`Status::ACCESS_DENIED`] as returned
+    /// by [`DriverBindingContext::open_by_driver`] if it is managed by this
+    /// or another driver.
+    ///
+    /// `remaining_device_path` is only set for bus drivers, and is the
+    /// part of the device path of the child controller to create, after
+    /// the path of `controller`.
+    fn supported(
+        &self,
+        ctx: &DriverBindingContext,
+        controller: Handle,
+        remaining_device_path: Option<&DevicePath>,
+    ) -> Result;
+
+    /// Start managing `controller`.
+    ///
+    /// Protocols that stay open until [`stop`] must be opened with
+    /// [`DriverBindingContext::open_by_driver`], and their
+    /// [`ScopedProtocol`] must be forgotten with [`core::mem::forget`].
+    /// Bus drivers must open the protocols of `controller` that their
+    /// child controllers use with
+    /// [`DriverBindingContext::open_by_child_controller`].
+    ///
+    /// `remaining_device_path` is only set for bus drivers, and is the
+    /// part of the device path of the child controller to create, after
+    /// the path of `controller`. If it is an end node, no child controller
+    /// must be created.
+    ///
+    /// [`stop`]: Self::stop
+    fn start(
+        &self,
+        ctx: &DriverBindingContext,
+        controller: Handle,
+        remaining_device_path: Option<&DevicePath>,
+    ) -> Result;
+
+    /// Stop managing `controller`, or only the child controllers
+    /// `children` if it isn't empty.
+    ///
+    /// When stopping child controllers, close their protocols with
+    /// [`DriverBindingContext::close_by_child_controller`] before
+    /// destroying them. When stopping `controller`, close its protocols
+    /// with [`DriverBindingContext::close_by_driver`].
+    fn stop(&self, ctx: &DriverBindingContext, controller: Handle, children: &[Handle]) -> Result;
+}
+
+/// Context passed to the functions of [`DriverBinding`].
+#[derive(Debug)]
+pub struct DriverBindingContext<'a> {
+    boot_services: &'a BootServices,
+    image_handle: Handle,
+    driver_binding_handle: Handle,
+}
+
+impl<'a> DriverBindingContext<'a> {
+    /// The boot services.
+    #[must_use]
+    pub const fn boot_services(&self) -> &'a BootServices {
+        self.boot_services
+    }
+
+    /// The image handle of the driver.
+    #[must_use]
+    pub const fn image_handle(&self) -> Handle {
+        self.image_handle
+    }
+
+    /// The handle on which the driver binding is installed. This is the
+    /// agent handle for the protocols that the driver opens.
+    #[must_use]
+    pub const fn driver_binding_handle(&self) -> Handle {
+        self.driver_binding_handle
+    }
+
+    /// Open protocol `P` of `controller` for the driver.
+    ///
+    /// No other driver can open the protocol for a controller while it is
+    /// open, and the driver is stopped before the protocol is uninstalled
+    /// or reinstalled. The protocol is closed when the returned
+    /// [`ScopedProtocol`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::UNSUPPORTED`]: `controller` doesn't support `P`.
+    /// * [`uefi::Status::ALREADY_STARTED`]: the driver already opened `P`.
+    /// * [`uefi::Status::ACCESS_DENIED`]: another driver opened `P`.
+    ///
+    /// See [`BootServices::open_protocol`] for other errors.
+    pub fn open_by_driver<P: ProtocolPointer + ?Sized>(
+        &self,
+        controller: Handle,
+    ) -> Result<ScopedProtocol<'a, P>> {
+        // Safety: the protocol can't be uninstalled while it is open by
+        // driver, until the driver is stopped and closes it.
+        unsafe {
+            self.boot_services.open_protocol::<P>(
+                OpenProtocolParams {
+                    handle: controller,
+                    agent: self.driver_binding_handle,
+                    controller: Some(controller),
+                },
+                OpenProtocolAttributes::ByDriver,
+            )
+        }
+    }
+
+    /// Close protocol `P` of `controller`, that was opened by
+    /// [`open_by_driver`] and whose [`ScopedProtocol`] was forgotten.
+    ///
+    /// # Safety
+    ///
+    /// There must be no [`ScopedProtocol`] left for the opened protocol,
+    /// and no other references to it.
+    ///
+    /// # Errors
+    ///
+    /// See [`BootServices::close_protocol`].
+    ///
+    /// [`open_by_driver`]: Self::open_by_driver
+    pub unsafe fn close_by_driver<P: ProtocolPointer + ?Sized>(
+        &self,
+        controller: Handle,
+    ) -> Result {
+        self.boot_services.close_protocol(
+            controller,
+            &P::GUID,
+            self.driver_binding_handle,
+            Some(controller),
+        )
+    }
+
+    /// Record that the child controller `child` uses protocol `P` of
+    /// `controller`, which the driver opened with [`open_by_driver`].
+    ///
+    /// This lets the firmware stop `child` before `controller` is stopped.
+    /// The protocol stays open until [`close_by_child_controller`] is
+    /// called.
+    ///
+    /// # Errors
+    ///
+    /// See [`BootServices::open_protocol`].
+    ///
+    /// [`open_by_driver`]: Self::open_by_driver
+    /// [`close_by_child_controller`]: Self::close_by_child_controller
+    pub fn open_by_child_controller<P: ProtocolPointer + ?Sized>(
+        &self,
+        controller: Handle,
+        child: Handle,
+    ) -> Result {
+        // The interface isn't returned, so the `ScopedProtocol` can't be
+        // used, and the protocol is closed explicitly.
+        let protocol = unsafe {
+            self.boot_services.open_protocol::<P>(
+                OpenProtocolParams {
+                    handle: controller,
+                    agent: self.driver_binding_handle,
+                    controller: Some(child),
+                },
+                OpenProtocolAttributes::ByChildController,
+            )
+        }?;
+        core::mem::forget(protocol);
+        Ok(())
+    }
+
+    /// Close protocol `P` of `controller`, that was opened by
+    /// [`open_by_child_controller`] for `child`.
+    ///
+    /// # Errors
+    ///
+    /// See [`BootServices::close_protocol`].
+    ///
+    /// [`open_by_child_controller`]: Self::open_by_child_controller
+    pub fn close_by_child_controller<P: ProtocolPointer + ?Sized>(
+        &self,
+        controller: Handle,
+        child: Handle,
+    ) -> Result {
+        // Safety: no reference to the interface was returned when the
+        // protocol was opened.
+        unsafe {
+            self.boot_services.close_protocol(
+                controller,
+                &P::GUID,
+                self.driver_binding_handle,
+                Some(child),
+            )
+        }
+    }
+}
+
+/// Driver binding protocol implemented by the [`DriverBinding`] `D`.
+///
+/// Install it on the image handle of the driver, for example with
+/// [`BootServices::install_protocol`], or with [`install_with_names`] to
+/// also install the names of the driver. The firmware functions are only
+/// available while boot services are active, and are found with
+/// [`system_table_boot`], so the global system table must be set, which
+/// [`entry`] does.
+///
+/// The corresponding C type is `EFI_DRIVER_BINDING_PROTOCOL`.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::mem;
+/// use uefi::proto::device_path::DevicePath;
+/// use uefi::proto::driver::{DriverBinding, DriverBindingContext, DriverBindingInterface};
+/// use uefi::proto::media::block::BlockIO;
+/// use uefi::table::boot::BootServices;
+/// use uefi::{Handle, Result};
+///
+/// struct BlockDriver;
+///
+/// impl DriverBinding for BlockDriver {
+///     fn supported(
+///         &self,
+///         ctx: &DriverBindingContext,
+///         controller: Handle,
+///         _remaining_device_path: Option<&DevicePath>,
+///     ) -> Result {
+///         ctx.open_by_driver::<BlockIO>(controller).map(|_| ())
+///     }
+///
+///     fn start(
+///         &self,
+///         ctx: &DriverBindingContext,
+///         controller: Handle,
+///         _remaining_device_path: Option<&DevicePath>,
+///     ) -> Result {
+///         let block_io = ctx.open_by_driver::<BlockIO>(controller)?;
+///         // Keep the protocol open until `stop`.
+///         mem::forget(block_io);
+///         Ok(())
+///     }
+///
+///     fn stop(&self, ctx: &DriverBindingContext, controller: Handle, _children: &[Handle]) -> Result {
+///         unsafe { ctx.close_by_driver::<BlockIO>(controller) }
+///     }
+/// }
+///
+/// fn install(bt: &BootServices, image: Handle) -> Result {
+///     let binding = Box::pin(DriverBindingInterface::new(image, BlockDriver));
+///     let installed = bt.install_protocol(Some(image), binding)?;
+///     // Keep the driver binding installed while the driver is loaded.
+///     mem::forget(installed);
+///     Ok(())
+/// }
+/// ```
+///
+/// [`BootServices::install_protocol`]: crate::table::boot::BootServices::install_protocol
+/// [`entry`]: crate::entry
+/// [`install_with_names`]: Self::install_with_names
+#[repr(C)]
+pub struct DriverBindingInterface<D: DriverBinding> {
+    raw: DriverBindingProtocol,
+    driver: D,
+}
+
+impl<D: DriverBinding> DriverBindingInterface<D> {
+    /// Create a driver binding for `driver`, to install on `image_handle`,
+    /// the image handle of the driver.
+    pub fn new(image_handle: Handle, driver: D) -> Self {
+        Self {
+            raw: DriverBindingProtocol {
+                supported: supported::<D>,
+                start: start::<D>,
+                stop: stop::<D>,
+                version: D::VERSION,
+                image_handle: image_handle.as_ptr(),
+                driver_binding_handle: image_handle.as_ptr(),
+            },
+            driver,
+        }
+    }
+
+    /// The driver.
+    #[must_use]
+    pub const fn driver(&self) -> &D {
+        &self.driver
+    }
+
+    /// Install a driver binding for `driver`, and a [`ComponentName2`]
+    /// implementation for `names`, on `image_handle`, the image handle of
+    /// the driver. Either both interfaces are installed, or none are.
+    ///
+    /// The interfaces are uninstalled when the returned
+    /// [`InstalledProtocols`] is dropped. Use [`core::mem::forget`] to keep
+    /// them installed while the driver is loaded.
+    ///
+    /// # Errors
+    ///
+    /// See [`BootServices::install_protocols`].
+    ///
+    /// [`ComponentName2`]: super::ComponentName2
+    #[cfg(feature = "alloc")]
+    pub fn install_with_names<N: ComponentNames>(
+        bt: &BootServices,
+        image_handle: Handle,
+        driver: D,
+        names: N,
+    ) -> Result<InstalledProtocols<'_>> {
+        let mut interfaces = ProtocolInterfaces::new();
+        interfaces.push(Box::pin(Self::new(image_handle, driver)));
+        interfaces.push(Box::pin(ComponentName2Interface::new(names)));
+        bt.install_protocols(Some(image_handle), interfaces)
+    }
+}
+
+impl<D: DriverBinding> Debug for DriverBindingInterface<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DriverBindingInterface")
+            .field("raw", &self.raw)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe impl<D: DriverBinding> Identify for DriverBindingInterface<D> {
+    const GUID: crate::Guid = DriverBindingProtocol::GUID;
+}
+
+impl<D: DriverBinding> Protocol for DriverBindingInterface<D> {}
+
+/// Run `f` with the driver of `this`, and convert its result to a status.
+unsafe fn call_driver<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    f: impl FnOnce(&D, &DriverBindingContext, Handle) -> Result,
+) -> Status {
+    let this = &*this.cast::<DriverBindingInterface<D>>();
+    let Some(controller) = Handle::from_ptr(controller) else {
+        return Status::INVALID_PARAMETER;
+    };
+    let st = system_table_boot();
+    let ctx = DriverBindingContext {
+        boot_services: st.boot_services(),
+        // OK to unwrap: the handles were set in `new`.
+        image_handle: Handle::from_ptr(this.raw.image_handle).unwrap(),
+        driver_binding_handle: Handle::from_ptr(this.raw.driver_binding_handle).unwrap(),
+    };
+    match f(&this.driver, &ctx, controller) {
+        Ok(()) => Status::SUCCESS,
+        Err(err) => err.status(),
+    }
+}
+
+unsafe fn remaining_device_path<'a>(ptr: *const DevicePathProtocol) -> Option<&'a DevicePath> {
+    (!ptr.is_null()).then(|| DevicePath::from_ffi_ptr(ptr.cast::<FfiDevicePath>()))
+}
+
+unsafe extern "efiapi" fn supported<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    remaining_device_path_ptr: *const DevicePathProtocol,
+) -> Status {
+    call_driver::<D>(this, controller, |driver, ctx, controller| {
+        driver.supported(
+            ctx,
+            controller,
+            remaining_device_path(remaining_device_path_ptr),
+        )
+    })
+}
+
+unsafe extern "efiapi" fn start<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    remaining_device_path_ptr: *const DevicePathProtocol,
+) -> Status {
+    call_driver::<D>(this, controller, |driver, ctx, controller| {
+        driver.start(
+            ctx,
+            controller,
+            remaining_device_path(remaining_device_path_ptr),
+        )
+    })
+}
+
+unsafe extern "efiapi" fn stop<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    number_of_children: usize,
+    child_handle_buffer: *const uefi_raw::Handle,
+) -> Status {
+    let children: &[Handle] = if number_of_children == 0 {
+        &[]
+    } else {
+        // `Handle` is a transparent wrapper around a non-null pointer.
+        slice::from_raw_parts(child_handle_buffer.cast(), number_of_children)
+    };
+    call_driver::<D>(this, controller, |driver, ctx, controller| {
+        driver.stop(ctx, controller, children)
+    })
+}
@@ -9,7 +9,15 @@ use crate::table::boot::{BootServices, ScopedProtocol};
 use crate::{CStr16, Error, Handle, Result, Status, StatusExt};
 use core::fmt::{self, Debug, Display, Formatter};
 use core::{ptr, slice};
+
 use uefi_raw::protocol::driver::ComponentName2Protocol;
+#[cfg(feature = "alloc")]
+use {
+    crate::proto::Protocol,
+    crate::{Guid, Identify},
+    alloc::vec::Vec,
+    core::ffi::CStr,
+};
 
 /// Protocol that provides human-readable names for a driver and for each of the
 /// controllers that the driver is managing.
@@ -342,6 +350,238 @@ fn language_to_cstr(language: &str) -> Result<LanguageCStr> {
     Ok(lang_cstr)
 }
 
+/// Names of a driver and of the controllers it manages, produced with a
+/// [`ComponentName2Interface`].
+///
+/// The names are only asked for in one of the [`LANGUAGES`]. The language
+/// requested by the caller is matched to them as described in [RFC 4647]
+/// "Lookup", so for example "en" is used if "en-US" is requested.
+///
+/// The returned strings must stay valid as long as the interface is
+/// installed, so they are usually static, or stored in `self` and not
+/// changed.
+///
+/// [`LANGUAGES`]: Self::LANGUAGES
+/// [RFC 4647]: https://www.rfc-editor.org/rfc/rfc4647#section-3.4
+pub trait ComponentNames: 'static {
+    /// Supported languages, as [RFC 4646] language tags such as "en" or
+    /// "fr-CA".
+    ///
+    /// [RFC 4646]: https://www.rfc-editor.org/rfc/rfc4646
+    const LANGUAGES: &'static [&'static str];
+
+    /// Get the name of the driver in `language`, one of the
+    /// [`LANGUAGES`].
+    ///
+    /// [`LANGUAGES`]: Self::LANGUAGES
+    fn driver_name(&self, language: &str) -> Option<&CStr16>;
+
+    /// Get the name of `controller`, or of its child controller `child`, in
+    /// `language`, one of the [`LANGUAGES`].
+    ///
+    /// Return `None` for controllers that the driver doesn't manage. The
+    /// default implementation has no controller names.
+    ///
+    /// [`LANGUAGES`]: Self::LANGUAGES
+    fn controller_name(
+        &self,
+        controller: Handle,
+        child: Option<Handle>,
+        language: &str,
+    ) -> Option<&CStr16> {
+        let _ = (controller, child, language);
+        None
+    }
+}
+
+/// [`ComponentName2`] protocol implemented by the [`ComponentNames`] `N`.
+///
+/// Install it on the same handle as the driver binding of the driver, for
+/// example with [`DriverBindingInterface::install_with_names`].
+///
+/// # Example
+///
+/// ```no_run
+/// use core::mem;
+/// use uefi::proto::driver::{ComponentNames, DriverBinding, DriverBindingInterface};
+/// use uefi::table::boot::BootServices;
+/// use uefi::{cstr16, CStr16, Handle, Result};
+///
+/// struct Names;
+///
+/// impl ComponentNames for Names {
+///     const LANGUAGES: &'static [&'static str] = &["en", "fr"];
+///
+///     fn driver_name(&self, language: &str) -> Option<&CStr16> {
+///         match language {
+///             "en" => Some(cstr16!("Example Driver")),
+///             "fr" => Some(cstr16!("Pilote d'exemple")),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// fn install<D: DriverBinding>(bt: &BootServices, image: Handle, driver: D) -> Result {
+///     let installed = DriverBindingInterface::install_with_names(bt, image, driver, Names)?;
+///     // Keep the protocols installed while the driver is loaded.
+///     mem::forget(installed);
+///     Ok(())
+/// }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[repr(C)]
+pub struct ComponentName2Interface<N: ComponentNames> {
+    raw: ComponentName2Protocol,
+    // Null-terminated list of languages separated by semicolons, which
+    // `raw.supported_languages` points to.
+    supported_languages: Vec<u8>,
+    names: N,
+}
+
+#[cfg(feature = "alloc")]
+impl<N: ComponentNames> ComponentName2Interface<N> {
+    /// Create a [`ComponentName2`] implementation for `names`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a language of [`ComponentNames::LANGUAGES`] isn't ASCII
+    /// or contains a semicolon.
+    pub fn new(names: N) -> Self {
+        assert!(N::LANGUAGES
+            .iter()
+            .all(|language| language.is_ascii() && !language.contains(';')));
+        let mut supported_languages = N::LANGUAGES.join(";").into_bytes();
+        supported_languages.push(0);
+        Self {
+            raw: ComponentName2Protocol {
+                get_driver_name: get_driver_name::<N>,
+                get_controller_name: get_controller_name::<N>,
+                supported_languages: supported_languages.as_ptr(),
+            },
+            supported_languages,
+            names,
+        }
+    }
+
+    /// The names.
+    #[must_use]
+    pub const fn names(&self) -> &N {
+        &self.names
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<N: ComponentNames> Debug for ComponentName2Interface<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentName2Interface")
+            .field("languages", &N::LANGUAGES)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<N: ComponentNames> Identify for ComponentName2Interface<N> {
+    const GUID: Guid = ComponentName2Protocol::GUID;
+}
+
+#[cfg(feature = "alloc")]
+impl<N: ComponentNames> Protocol for ComponentName2Interface<N> {}
+
+/// Find the language of `supported` to use for the `requested` language,
+/// with the RFC 4647 "Lookup" scheme: subtags are removed from the end of
+/// `requested` until it matches a supported language.
+#[cfg(feature = "alloc")]
+fn lookup_language<'a>(supported: &[&'a str], requested: &str) -> Option<&'a str> {
+    let mut requested = requested;
+    loop {
+        if let Some(language) = supported
+            .iter()
+            .find(|language| language.eq_ignore_ascii_case(requested))
+        {
+            return Some(language);
+        }
+
+        requested = &requested[..requested.rfind('-')?];
+        // Also remove a single-character subtag, which only introduces
+        // the subtag after it.
+        if let Some(index) = requested.rfind('-') {
+            if requested.len() - index == 2 {
+                requested = &requested[..index];
+            }
+        }
+    }
+}
+
+/// Read the language passed by the caller and find the supported language
+/// to use for it.
+#[cfg(feature = "alloc")]
+unsafe fn negotiate_language<N: ComponentNames>(
+    language: *const u8,
+) -> core::result::Result<&'static str, Status> {
+    if language.is_null() {
+        return Err(Status::INVALID_PARAMETER);
+    }
+    let language = CStr::from_ptr(language.cast())
+        .to_str()
+        .map_err(|_| Status::UNSUPPORTED)?;
+    lookup_language(N::LANGUAGES, language).ok_or(Status::UNSUPPORTED)
+}
+
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn get_driver_name<N: ComponentNames>(
+    this: *const ComponentName2Protocol,
+    language: *const u8,
+    driver_name: *mut *const u16,
+) -> Status {
+    if driver_name.is_null() {
+        return Status::INVALID_PARAMETER;
+    }
+    let this = &*this.cast::<ComponentName2Interface<N>>();
+    let language = match negotiate_language::<N>(language) {
+        Ok(language) => language,
+        Err(status) => return status,
+    };
+    match this.names.driver_name(language) {
+        Some(name) => {
+            *driver_name = name.as_ptr().cast();
+            Status::SUCCESS
+        }
+        None => Status::UNSUPPORTED,
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn get_controller_name<N: ComponentNames>(
+    this: *const ComponentName2Protocol,
+    controller_handle: uefi_raw::Handle,
+    child_handle: uefi_raw::Handle,
+    language: *const u8,
+    controller_name: *mut *const u16,
+) -> Status {
+    let Some(controller_handle) = Handle::from_ptr(controller_handle) else {
+        return Status::INVALID_PARAMETER;
+    };
+    if controller_name.is_null() {
+        return Status::INVALID_PARAMETER;
+    }
+    let this = &*this.cast::<ComponentName2Interface<N>>();
+    let language = match negotiate_language::<N>(language) {
+        Ok(language) => language,
+        Err(status) => return status,
+    };
+    match this
+        .names
+        .controller_name(controller_handle, Handle::from_ptr(child_handle), language)
+    {
+        Some(name) => {
+            *controller_name = name.as_ptr().cast();
+            Status::SUCCESS
+        }
+        None => Status::UNSUPPORTED,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +649,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lookup_language() {
+        let supported = ["en", "fr-CA", "zh-Hant"];
+        assert_eq!(lookup_language(&supported, "en"), Some("en"));
+        assert_eq!(lookup_language(&supported, "EN"), Some("en"));
+        assert_eq!(lookup_language(&supported, "en-US"), Some("en"));
+        assert_eq!(lookup_language(&supported, "fr-CA"), Some("fr-CA"));
+        assert_eq!(
+            lookup_language(&supported, "zh-Hant-CN-x-private"),
+            Some("zh-Hant")
+        );
+        assert_eq!(lookup_language(&supported, "fr"), None);
+        assert_eq!(lookup_language(&supported, "de-DE"), None);
+        assert_eq!(lookup_language(&supported, ""), None);
+    }
+
     #[test]
     fn test_language_to_cstr() {
         let mut expected = [0; 64];
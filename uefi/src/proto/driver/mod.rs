@@ -1,5 +1,11 @@
 //! UEFI driver model protocols.
 
+mod binding;
 mod component_name;
+#[cfg(feature = "alloc")]
+mod tree;
 
+pub use binding::*;
 pub use component_name::*;
+#[cfg(feature = "alloc")]
+pub use tree::*;
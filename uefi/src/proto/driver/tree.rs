@@ -0,0 +1,279 @@
+use crate::proto::console::gop::GraphicsOutput;
+use crate::proto::console::pointer::Pointer;
+use crate::proto::console::serial::Serial;
+use crate::proto::console::text::{Input, Output};
+use crate::proto::debug::{DebugPort, DebugSupport};
+use crate::proto::device_path::text::{DevicePathFromText, DevicePathToText};
+use crate::proto::device_path::{DevicePath, LoadedImageDevicePath};
+use crate::proto::firmware_management::FirmwareManagement;
+use crate::proto::loaded_image::LoadedImage;
+use crate::proto::media::block::BlockIO;
+use crate::proto::media::disk::{DiskIo, DiskIo2};
+use crate::proto::media::fs::SimpleFileSystem;
+use crate::proto::media::partition::PartitionInfo;
+use crate::proto::misc::{ResetNotification, Timestamp};
+use crate::proto::network::pxe::BaseCode;
+use crate::proto::network::snp::SimpleNetwork;
+use crate::proto::rng::Rng;
+use crate::proto::shell_params::ShellParameters;
+use crate::proto::string::unicode_collation::UnicodeCollation;
+use crate::table::boot::{BootServices, OpenProtocolAttributes, OpenProtocolParams, SearchType};
+use crate::{Guid, Handle, Identify, Result};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use uefi_raw::protocol::driver::{ComponentName2Protocol, DriverBindingProtocol};
+
+/// Protocols that [`protocol_name`] knows the name of.
+const KNOWN_PROTOCOLS: &[(Guid, &str)] = &[
+    (BlockIO::GUID, "BlockIO"),
+    (BaseCode::GUID, "PxeBaseCode"),
+    (
+        ComponentName2Protocol::DEPRECATED_COMPONENT_NAME_GUID,
+        "ComponentName",
+    ),
+    (ComponentName2Protocol::GUID, "ComponentName2"),
+    (DebugPort::GUID, "DebugPort"),
+    (DebugSupport::GUID, "DebugSupport"),
+    (DevicePath::GUID, "DevicePath"),
+    (DevicePathFromText::GUID, "DevicePathFromText"),
+    (DevicePathToText::GUID, "DevicePathToText"),
+    (DiskIo::GUID, "DiskIo"),
+    (DiskIo2::GUID, "DiskIo2"),
+    (DriverBindingProtocol::GUID, "DriverBinding"),
+    (FirmwareManagement::GUID, "FirmwareManagement"),
+    (GraphicsOutput::GUID, "GraphicsOutput"),
+    (Input::GUID, "SimpleTextInput"),
+    (LoadedImage::GUID, "LoadedImage"),
+    (LoadedImageDevicePath::GUID, "LoadedImageDevicePath"),
+    (Output::GUID, "SimpleTextOutput"),
+    (PartitionInfo::GUID, "PartitionInfo"),
+    (Pointer::GUID, "SimplePointer"),
+    (ResetNotification::GUID, "ResetNotification"),
+    (Rng::GUID, "Rng"),
+    (Serial::GUID, "SerialIo"),
+    (ShellParameters::GUID, "ShellParameters"),
+    (SimpleFileSystem::GUID, "SimpleFileSystem"),
+    (SimpleNetwork::GUID, "SimpleNetwork"),
+    (Timestamp::GUID, "Timestamp"),
+    (UnicodeCollation::GUID, "UnicodeCollation2"),
+];
+
+/// Get a short name for a protocol GUID, if it is one of the protocols
+/// supported by this crate.
+#[must_use]
+pub fn protocol_name(guid: &Guid) -> Option<&'static str> {
+    KNOWN_PROTOCOLS
+        .iter()
+        .find(|(known, _)| known == guid)
+        .map(|(_, name)| *name)
+}
+
+/// Tree of the controllers in the system, similar to what the UEFI shell's
+/// `devtree` command shows.
+///
+/// The tree is reconstructed from the open protocol information that the
+/// driver model records: a bus driver opens the protocols of its controller
+/// [`ByChildController`] for each child it creates, and a device driver opens
+/// them [`ByDriver`] on the controllers it manages.
+///
+/// Controllers are the handles that have a [`DevicePath`] installed, as well
+/// as any handle that was created as the child of another controller. Other
+/// handles, such as those of images and drivers, are not part of the tree.
+/// A controller that is the child of several controllers is only listed
+/// under one of them.
+///
+/// [`ByChildController`]: OpenProtocolAttributes::ByChildController
+/// [`ByDriver`]: OpenProtocolAttributes::ByDriver
+#[derive(Debug)]
+pub struct DeviceTree {
+    roots: Vec<DeviceNode>,
+}
+
+impl DeviceTree {
+    /// Take a snapshot of the controllers currently present.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handles in the system cannot be listed.
+    pub fn new(boot_services: &BootServices) -> Result<Self> {
+        let handles = boot_services.locate_handle_buffer(SearchType::AllHandles)?;
+
+        // Handles may have been removed in the meantime, so skip those that
+        // can't be queried.
+        let infos: Vec<HandleInfo> = handles
+            .iter()
+            .filter_map(|handle| HandleInfo::new(boot_services, *handle).ok())
+            .collect();
+
+        let index: BTreeMap<Handle, usize> = infos
+            .iter()
+            .enumerate()
+            .map(|(i, info)| (info.handle, i))
+            .collect();
+
+        let mut has_parent = vec![false; infos.len()];
+        for child in infos.iter().flat_map(|info| &info.children) {
+            if let Some(&child) = index.get(child) {
+                has_parent[child] = true;
+            }
+        }
+        let roots: Vec<usize> = (0..infos.len())
+            .filter(|&i| infos[i].device_path.is_some() && !has_parent[i])
+            .collect();
+
+        let mut infos: Vec<Option<HandleInfo>> = infos.into_iter().map(Some).collect();
+        let roots = roots
+            .into_iter()
+            .filter_map(|i| DeviceNode::new(i, &mut infos, &index))
+            .collect();
+
+        Ok(Self { roots })
+    }
+
+    /// Controllers that are not the child of any other controller.
+    #[must_use]
+    pub fn roots(&self) -> &[DeviceNode] {
+        &self.roots
+    }
+}
+
+/// A controller in a [`DeviceTree`].
+#[derive(Debug)]
+pub struct DeviceNode {
+    handle: Handle,
+    device_path: Option<Box<DevicePath>>,
+    protocols: Vec<Guid>,
+    drivers: Vec<Handle>,
+    children: Vec<DeviceNode>,
+}
+
+impl DeviceNode {
+    /// Create the node of `infos[i]` and its children, taking their
+    /// information out of `infos`.
+    fn new(
+        i: usize,
+        infos: &mut [Option<HandleInfo>],
+        index: &BTreeMap<Handle, usize>,
+    ) -> Option<Self> {
+        // Each handle is only added to the tree once, which also prevents
+        // endless recursion if firmware bugs create a cycle.
+        let info = infos[i].take()?;
+        let children = info
+            .children
+            .iter()
+            .filter_map(|child| index.get(child))
+            .filter_map(|&child| Self::new(child, infos, index))
+            .collect();
+
+        Some(Self {
+            handle: info.handle,
+            device_path: info.device_path,
+            protocols: info.protocols,
+            drivers: info.drivers,
+            children,
+        })
+    }
+
+    /// Handle of the controller.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Device path of the controller, if it has one.
+    #[must_use]
+    pub fn device_path(&self) -> Option<&DevicePath> {
+        self.device_path.as_deref()
+    }
+
+    /// Protocols installed on the controller. Use [`protocol_name`] to get
+    /// readable names for them.
+    #[must_use]
+    pub fn protocols(&self) -> &[Guid] {
+        &self.protocols
+    }
+
+    /// Driver binding handles of the drivers managing the controller.
+    #[must_use]
+    pub fn drivers(&self) -> &[Handle] {
+        &self.drivers
+    }
+
+    /// Child controllers created by the drivers managing the controller.
+    #[must_use]
+    pub fn children(&self) -> &[DeviceNode] {
+        &self.children
+    }
+}
+
+/// Everything about one handle that is needed to build the tree.
+struct HandleInfo {
+    handle: Handle,
+    device_path: Option<Box<DevicePath>>,
+    protocols: Vec<Guid>,
+    drivers: Vec<Handle>,
+    children: Vec<Handle>,
+}
+
+impl HandleInfo {
+    fn new(boot_services: &BootServices, handle: Handle) -> Result<Self> {
+        let protocols: Vec<Guid> = boot_services
+            .protocols_per_handle(handle)?
+            .iter()
+            .map(|guid| **guid)
+            .collect();
+
+        let mut drivers = Vec::new();
+        let mut children = Vec::new();
+        for protocol in &protocols {
+            // The protocol may have been uninstalled in the meantime.
+            let Ok(entries) = boot_services.open_protocol_information(handle, protocol) else {
+                continue;
+            };
+            for entry in entries.iter() {
+                if entry.is_by_child_controller() {
+                    push_unique(&mut children, entry.controller());
+                } else if entry.is_by_driver() {
+                    push_unique(&mut drivers, entry.agent());
+                }
+            }
+        }
+
+        let device_path = if protocols.contains(&DevicePath::GUID) {
+            // Safety: the device path is copied right away and the protocol
+            // is closed again before returning.
+            unsafe {
+                boot_services.open_protocol::<DevicePath>(
+                    OpenProtocolParams {
+                        handle,
+                        agent: boot_services.image_handle(),
+                        controller: None,
+                    },
+                    OpenProtocolAttributes::GetProtocol,
+                )
+            }
+            .ok()
+            .map(|device_path| device_path.to_boxed())
+        } else {
+            None
+        };
+
+        Ok(Self {
+            handle,
+            device_path,
+            protocols,
+            drivers,
+            children,
+        })
+    }
+}
+
+fn push_unique(handles: &mut Vec<Handle>, handle: Option<Handle>) {
+    if let Some(handle) = handle {
+        if !handles.contains(&handle) {
+            handles.push(handle);
+        }
+    }
+}
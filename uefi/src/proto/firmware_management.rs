@@ -0,0 +1,532 @@
+//! Firmware Management Protocol (FMP).
+//!
+//! The protocol is produced by drivers that can read and update the
+//! firmware images of a device, or of the system firmware itself. Each
+//! image is described by an [`ImageDescriptor`]; its `image_type_id` is the
+//! firmware class GUID listed in the [ESRT] and used by update capsules.
+//!
+//! [ESRT]: crate::table::esrt
+
+use crate::proto::unsafe_protocol;
+use crate::{CStr16, Result, Status, StatusExt};
+use core::ffi::c_void;
+use core::ptr;
+use uefi_raw::protocol::firmware_management::FirmwareManagementProtocol;
+
+pub use uefi_raw::protocol::firmware_management::{
+    FirmwareImageDescriptor, ImageAttributes, ImageUpdatable, PackageAttributes,
+};
+pub use uefi_raw::table::esrt::LastAttemptStatus;
+
+#[cfg(feature = "alloc")]
+use {
+    crate::table::boot::BootServices,
+    crate::{CString16, Char16, Guid},
+    alloc::vec::Vec,
+    core::mem::{self, MaybeUninit},
+    core::slice,
+    core::sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Firmware Management Protocol.
+///
+/// Used to query, read and update the firmware images of a device.
+///
+/// Corresponds to the C type `EFI_FIRMWARE_MANAGEMENT_PROTOCOL`.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(FirmwareManagementProtocol::GUID)]
+pub struct FirmwareManagement(FirmwareManagementProtocol);
+
+impl FirmwareManagement {
+    /// Read a copy of the image at `image_index` into `buffer`, and return
+    /// the filled part of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BUFFER_TOO_SMALL`]: `buffer` is too small to hold the
+    ///   image. The required size is returned in the error data.
+    /// * [`Status::INVALID_PARAMETER`]: `image_index` is not valid.
+    /// * [`Status::UNSUPPORTED`]: reading the image is not supported.
+    /// * [`Status::SECURITY_VIOLATION`]: reading the image is not allowed.
+    pub fn get_image<'buf>(
+        &mut self,
+        image_index: u8,
+        buffer: &'buf mut [u8],
+    ) -> Result<&'buf mut [u8], Option<usize>> {
+        let mut size = buffer.len();
+        unsafe {
+            (self.0.get_image)(
+                &mut self.0,
+                image_index,
+                buffer.as_mut_ptr().cast(),
+                &mut size,
+            )
+        }
+        .to_result_with(
+            || &mut buffer[..size],
+            |status| {
+                if status == Status::BUFFER_TOO_SMALL {
+                    Some(size)
+                } else {
+                    None
+                }
+            },
+        )
+    }
+
+    /// Check whether `image` can be used to update the image at
+    /// `image_index`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `image_index` is not valid.
+    /// * [`Status::UNSUPPORTED`]: checking images is not supported.
+    /// * [`Status::SECURITY_VIOLATION`]: `image` failed authentication.
+    pub fn check_image(&mut self, image_index: u8, image: &[u8]) -> Result<ImageUpdatable> {
+        let mut updatable = ImageUpdatable::empty();
+        unsafe {
+            (self.0.check_image)(
+                &mut self.0,
+                image_index,
+                image.as_ptr().cast(),
+                image.len(),
+                &mut updatable,
+            )
+        }
+        .to_result_with_val(|| updatable)
+    }
+
+    /// Update the version of the firmware package.
+    ///
+    /// `auth_data` contains the authentication data if
+    /// [`PackageAttributes::AUTHENTICATION_REQUIRED`] is set, and
+    /// `vendor_code` is passed to the driver as-is.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `version_name` is longer than
+    ///   supported by the driver.
+    /// * [`Status::UNSUPPORTED`]: the package version can't be updated.
+    /// * [`Status::SECURITY_VIOLATION`]: `auth_data` failed authentication.
+    pub fn set_package_info(
+        &mut self,
+        auth_data: Option<&[u8]>,
+        vendor_code: Option<&[u8]>,
+        version: u32,
+        version_name: Option<&CStr16>,
+    ) -> Result {
+        let (auth_data_ptr, auth_data_size) =
+            auth_data.map_or((ptr::null(), 0), |data| (data.as_ptr(), data.len()));
+        unsafe {
+            (self.0.set_package_info)(
+                &mut self.0,
+                auth_data_ptr.cast(),
+                auth_data_size,
+                opt_data_ptr(vendor_code),
+                version,
+                version_name.map_or(ptr::null(), |name| name.as_ptr().cast()),
+            )
+        }
+        .to_result()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FirmwareManagement {
+    /// Get descriptors of the images managed by the driver, along with the
+    /// version of the firmware package containing them.
+    ///
+    /// `bt` is used to free the package version name allocated by the
+    /// driver.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BAD_BUFFER_SIZE`]: the descriptor array returned by the
+    ///   driver is inconsistent.
+    /// * [`Status::OUT_OF_RESOURCES`]: not enough memory for the descriptor
+    ///   array or package version name.
+    pub fn get_image_info(&mut self, bt: &BootServices) -> Result<ImageInfo> {
+        // Use a `u64` buffer to get the alignment of the descriptors.
+        let mut buffer = Vec::<u64>::new();
+        let mut size;
+        let mut descriptor_version = 0;
+        let mut descriptor_count = 0;
+        let mut descriptor_size = 0;
+        let mut package_version = 0;
+        let mut package_version_name = ptr::null_mut();
+        loop {
+            size = buffer.len() * mem::size_of::<u64>();
+            let status = unsafe {
+                (self.0.get_image_info)(
+                    &mut self.0,
+                    &mut size,
+                    buffer.as_mut_ptr().cast(),
+                    &mut descriptor_version,
+                    &mut descriptor_count,
+                    &mut descriptor_size,
+                    &mut package_version,
+                    &mut package_version_name,
+                )
+            };
+            if status != Status::BUFFER_TOO_SMALL {
+                status.to_result()?;
+                break;
+            }
+            let word_size = mem::size_of::<u64>();
+            buffer.resize((size + word_size - 1) / word_size, 0);
+        }
+        let package_version_name = unsafe { take_pool_string(bt, package_version_name) };
+
+        let descriptors_size = usize::from(descriptor_count) * descriptor_size;
+        if descriptor_size == 0 || descriptors_size > buffer.len() * mem::size_of::<u64>() {
+            return Err(Status::BAD_BUFFER_SIZE.into());
+        }
+        let bytes =
+            unsafe { slice::from_raw_parts(buffer.as_ptr().cast::<u8>(), descriptors_size) };
+        let descriptors = bytes
+            .chunks_exact(descriptor_size)
+            .map(|bytes| unsafe { ImageDescriptor::parse(bytes, descriptor_version) })
+            .collect();
+
+        Ok(ImageInfo {
+            descriptor_version,
+            descriptors,
+            package_version,
+            package_version_name,
+        })
+    }
+
+    /// Update the image at `image_index` with `image`.
+    ///
+    /// `vendor_code` is passed to the driver as-is. If `progress` is set, it
+    /// is called with the completion percentage while the update is in
+    /// progress.
+    ///
+    /// `bt` is used to free the abort reason allocated by the driver.
+    ///
+    /// # Errors
+    ///
+    /// If the driver provides a reason for the failure, it is returned in
+    /// the error data.
+    ///
+    /// * [`Status::ABORTED`]: the update was aborted.
+    /// * [`Status::INVALID_PARAMETER`]: `image_index` is not valid.
+    /// * [`Status::UNSUPPORTED`]: updating the image is not supported.
+    /// * [`Status::SECURITY_VIOLATION`]: `image` failed authentication.
+    pub fn set_image(
+        &mut self,
+        bt: &BootServices,
+        image_index: u8,
+        image: &[u8],
+        vendor_code: Option<&[u8]>,
+        mut progress: Option<&mut dyn FnMut(usize)>,
+    ) -> Result<(), Option<CString16>> {
+        let mut abort_reason = ptr::null_mut();
+
+        // The progress function doesn't have a context parameter, so the
+        // callback is passed through a global for the duration of the call.
+        let previous_progress = progress.as_mut().map(|progress| {
+            let progress: *mut &mut dyn FnMut(usize) = progress;
+            PROGRESS.swap(progress.cast(), Ordering::AcqRel)
+        });
+        let status = unsafe {
+            (self.0.set_image)(
+                &mut self.0,
+                image_index,
+                image.as_ptr().cast(),
+                image.len(),
+                opt_data_ptr(vendor_code),
+                previous_progress.map(|_| report_progress as _),
+                &mut abort_reason,
+            )
+        };
+        if let Some(previous_progress) = previous_progress {
+            PROGRESS.store(previous_progress, Ordering::Release);
+        }
+
+        let abort_reason = unsafe { take_pool_string(bt, abort_reason) };
+        status.to_result_with_err(|_| abort_reason)
+    }
+
+    /// Get the version of the firmware package and its attributes.
+    ///
+    /// `bt` is used to free the package version name allocated by the
+    /// driver.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: package information is not supported.
+    pub fn get_package_info(&mut self, bt: &BootServices) -> Result<PackageInfo> {
+        let mut version = 0;
+        let mut version_name = ptr::null_mut();
+        let mut version_name_max_len = 0;
+        let mut attributes_supported = PackageAttributes::empty();
+        let mut attributes_setting = PackageAttributes::empty();
+        let status = unsafe {
+            (self.0.get_package_info)(
+                &mut self.0,
+                &mut version,
+                &mut version_name,
+                &mut version_name_max_len,
+                &mut attributes_supported,
+                &mut attributes_setting,
+            )
+        };
+        let version_name = unsafe { take_pool_string(bt, version_name) };
+        status.to_result_with_val(|| PackageInfo {
+            version,
+            version_name,
+            version_name_max_len,
+            attributes_supported,
+            attributes_setting,
+        })
+    }
+}
+
+/// Get a pointer to optional opaque data, or null if it's not present.
+fn opt_data_ptr(data: Option<&[u8]>) -> *const c_void {
+    data.map_or(ptr::null(), |data| data.as_ptr().cast())
+}
+
+/// Progress callback of the [`FirmwareManagement::set_image`] call in
+/// progress. Points to a `&mut dyn FnMut(usize)`.
+#[cfg(feature = "alloc")]
+static PROGRESS: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn report_progress(completion: usize) -> Status {
+    let progress = PROGRESS
+        .load(Ordering::Acquire)
+        .cast::<&mut dyn FnMut(usize)>();
+    if let Some(progress) = progress.as_mut() {
+        progress(completion);
+    }
+    Status::SUCCESS
+}
+
+/// Copy a string allocated by the driver, then free it.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a null-terminated string allocated from
+/// pool memory.
+#[cfg(feature = "alloc")]
+unsafe fn take_pool_string(bt: &BootServices, ptr: *mut uefi_raw::Char16) -> Option<CString16> {
+    if ptr.is_null() {
+        return None;
+    }
+    let string = CString16::from(CStr16::from_ptr(ptr.cast::<Char16>()));
+    bt.free_pool(ptr.cast())
+        .expect("Failed to free pool string");
+    Some(string)
+}
+
+/// Copy of an optional string owned by the driver.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a null-terminated string.
+#[cfg(feature = "alloc")]
+unsafe fn copy_string(ptr: *const uefi_raw::Char16) -> Option<CString16> {
+    (!ptr.is_null()).then(|| CString16::from(CStr16::from_ptr(ptr.cast::<Char16>())))
+}
+
+/// Information about the images managed by a [`FirmwareManagement`]
+/// driver, returned by [`FirmwareManagement::get_image_info`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageInfo {
+    /// Version of the descriptor format used by the driver.
+    pub descriptor_version: u32,
+
+    /// Descriptors of the images.
+    pub descriptors: Vec<ImageDescriptor>,
+
+    /// Version of the firmware package containing the images, or
+    /// [`FirmwareManagementProtocol::PACKAGE_VERSION_NOT_SUPPORTED`].
+    pub package_version: u32,
+
+    /// Version of the firmware package as a string.
+    pub package_version_name: Option<CString16>,
+}
+
+/// Description of a firmware image.
+///
+/// Fields that were added in later versions of the descriptor format are
+/// `None` if the driver uses an older version.
+///
+/// See [`FirmwareImageDescriptor`] for the raw descriptor.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageDescriptor {
+    /// Index of the image, starting at one. This is the index passed to
+    /// the image functions of [`FirmwareManagement`].
+    pub image_index: u8,
+
+    /// Type of the image. This is the firmware class GUID used in the ESRT.
+    pub image_type_id: Guid,
+
+    /// Unique identifier of the image within the type.
+    pub image_id: u64,
+
+    /// Name of the image.
+    pub image_id_name: Option<CString16>,
+
+    /// Version of the image.
+    pub version: u32,
+
+    /// Version of the image as a string.
+    pub version_name: Option<CString16>,
+
+    /// Size of the image in bytes, or zero if unknown.
+    pub size: usize,
+
+    /// Attributes supported by the image.
+    pub attributes_supported: ImageAttributes,
+
+    /// Attributes currently set for the image.
+    pub attributes_setting: ImageAttributes,
+
+    /// Image compatibilities.
+    pub compatibilities: u64,
+
+    /// Lowest version that the image can be updated (or rolled back) to.
+    pub lowest_supported_image_version: Option<u32>,
+
+    /// Version of the last update attempt.
+    pub last_attempt_version: Option<u32>,
+
+    /// Result of the last update attempt.
+    pub last_attempt_status: Option<LastAttemptStatus>,
+
+    /// Identifies the device when multiple devices use the same image type.
+    pub hardware_instance: Option<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl ImageDescriptor {
+    /// Parse a descriptor of the given format `version`.
+    ///
+    /// # Safety
+    ///
+    /// The string pointers of the descriptor must be null or point to
+    /// null-terminated strings.
+    unsafe fn parse(bytes: &[u8], version: u32) -> Self {
+        // Copy the descriptor to get the alignment of the raw type. Fields
+        // not covered by `bytes` are zero.
+        let mut raw = MaybeUninit::<FirmwareImageDescriptor>::zeroed();
+        let len = bytes.len().min(mem::size_of::<FirmwareImageDescriptor>());
+        ptr::copy_nonoverlapping(bytes.as_ptr(), raw.as_mut_ptr().cast::<u8>(), len);
+        let raw = raw.assume_init();
+
+        let v2 = version >= 2;
+        let v3 = version >= 3;
+        Self {
+            image_index: raw.image_index,
+            image_type_id: raw.image_type_id,
+            image_id: raw.image_id,
+            image_id_name: copy_string(raw.image_id_name),
+            version: raw.version,
+            version_name: copy_string(raw.version_name),
+            size: raw.size,
+            attributes_supported: raw.attributes_supported,
+            attributes_setting: raw.attributes_setting,
+            compatibilities: raw.compatibilities,
+            lowest_supported_image_version: v2.then_some(raw.lowest_supported_image_version),
+            last_attempt_version: v3.then_some(raw.last_attempt_version),
+            last_attempt_status: v3.then_some(raw.last_attempt_status),
+            hardware_instance: v3.then_some(raw.hardware_instance),
+        }
+    }
+
+    /// Returns true if the image can be updated with
+    /// [`FirmwareManagement::set_image`].
+    #[must_use]
+    pub fn is_updatable(&self) -> bool {
+        self.attributes_supported
+            .contains(ImageAttributes::IMAGE_UPDATABLE)
+            && self
+                .attributes_setting
+                .contains(ImageAttributes::IMAGE_UPDATABLE)
+    }
+}
+
+/// Version and attributes of a firmware package, returned by
+/// [`FirmwareManagement::get_package_info`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageInfo {
+    /// Version of the package, or
+    /// [`FirmwareManagementProtocol::PACKAGE_VERSION_NOT_SUPPORTED`].
+    pub version: u32,
+
+    /// Version of the package as a string.
+    pub version_name: Option<CString16>,
+
+    /// Maximum length of the version name in characters, not including
+    /// the null terminator.
+    pub version_name_max_len: u32,
+
+    /// Attributes supported by the package.
+    pub attributes_supported: PackageAttributes,
+
+    /// Attributes currently set for the package.
+    pub attributes_setting: PackageAttributes,
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{cstr16, guid};
+
+    #[test]
+    fn test_parse_descriptor() {
+        let name = cstr16!("System firmware");
+        let raw = FirmwareImageDescriptor {
+            image_index: 1,
+            image_type_id: guid!("3bdb3089-5662-42df-840e-3922ed6467c9"),
+            image_id: 2,
+            image_id_name: name.as_ptr().cast(),
+            version: 0x100,
+            version_name: ptr::null(),
+            size: 0x1000,
+            attributes_supported: ImageAttributes::IMAGE_UPDATABLE
+                | ImageAttributes::RESET_REQUIRED,
+            attributes_setting: ImageAttributes::IMAGE_UPDATABLE,
+            compatibilities: 0,
+            lowest_supported_image_version: 0x80,
+            last_attempt_version: 0x100,
+            last_attempt_status: LastAttemptStatus::SUCCESS,
+            hardware_instance: 3,
+            dependencies: ptr::null(),
+        };
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                (&raw as *const FirmwareImageDescriptor).cast::<u8>(),
+                mem::size_of::<FirmwareImageDescriptor>(),
+            )
+        };
+
+        let descriptor = unsafe { ImageDescriptor::parse(bytes, 3) };
+        assert_eq!(descriptor.image_index, 1);
+        assert_eq!(descriptor.image_type_id, raw.image_type_id);
+        assert_eq!(descriptor.image_id_name.as_deref(), Some(name));
+        assert_eq!(descriptor.version_name, None);
+        assert_eq!(descriptor.size, 0x1000);
+        assert!(descriptor.is_updatable());
+        assert_eq!(descriptor.lowest_supported_image_version, Some(0x80));
+        assert_eq!(
+            descriptor.last_attempt_status,
+            Some(LastAttemptStatus::SUCCESS)
+        );
+        assert_eq!(descriptor.hardware_instance, Some(3));
+
+        // Version 1 descriptors end after the compatibilities.
+        let descriptor = unsafe { ImageDescriptor::parse(&bytes[..88], 1) };
+        assert_eq!(descriptor.version, 0x100);
+        assert_eq!(descriptor.lowest_supported_image_version, None);
+        assert_eq!(descriptor.last_attempt_version, None);
+        assert_eq!(descriptor.hardware_instance, None);
+    }
+}
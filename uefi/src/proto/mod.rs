@@ -70,6 +70,7 @@ pub mod console;
 pub mod debug;
 pub mod device_path;
 pub mod driver;
+pub mod firmware_management;
 pub mod loaded_image;
 pub mod media;
 pub mod misc;
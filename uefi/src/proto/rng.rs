@@ -1,11 +1,24 @@
 //! `Rng` protocol.
 
+use crate::proto::misc::Timestamp;
 use crate::proto::unsafe_protocol;
-use crate::{Result, Status, StatusExt};
+use crate::table::boot::BootServices;
+use crate::{Error, Result, Status, StatusExt};
 use core::{mem, ptr};
 
 pub use uefi_raw::protocol::rng::RngAlgorithmType;
 
+/// Algorithms tried by [`Rng::get_rng_preferred`] when called through
+/// [`fill_bytes`], in order of preference.
+///
+/// The SP800-90 CTR DRBG is preferred since it is the default algorithm of
+/// most implementations and is suitable for cryptographic use. The raw
+/// algorithm provides entropy directly from the hardware source.
+pub const DEFAULT_ALGORITHM_PREFERENCE: [RngAlgorithmType; 2] = [
+    RngAlgorithmType::ALGORITHM_SP800_90_CTR_256,
+    RngAlgorithmType::ALGORITHM_RAW,
+];
+
 /// Rng protocol
 #[derive(Debug)]
 #[repr(transparent)]
@@ -55,4 +68,201 @@ impl Rng {
             (self.0.get_rng)(&mut self.0, algo, buffer_length, buffer.as_mut_ptr()).to_result()
         }
     }
+
+    /// Fill `buffer` with random bytes, trying each algorithm in
+    /// `preference` in order and falling back to the implementation's
+    /// default algorithm if none of them succeed.
+    ///
+    /// On success, the algorithm that was used is returned, or `None` if
+    /// the default algorithm was used.
+    pub fn get_rng_preferred(
+        &mut self,
+        preference: &[RngAlgorithmType],
+        buffer: &mut [u8],
+    ) -> Result<Option<RngAlgorithmType>> {
+        for algorithm in preference {
+            if self.get_rng(Some(*algorithm), buffer).is_ok() {
+                return Ok(Some(*algorithm));
+            }
+        }
+        self.get_rng(None, buffer).map(|()| None)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for Rng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.get_rng(None, dest)
+            .expect("failed to get random bytes");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> core::result::Result<(), rand_core::Error> {
+        self.get_rng(None, dest).map_err(|err| {
+            // Custom error codes must be at least `CUSTOM_START`. Keep the
+            // low bits of the status code so that it can be identified.
+            let code = rand_core::Error::CUSTOM_START | (err.status().0 as u32 & 0x3fff_ffff);
+            // OK to unwrap: `CUSTOM_START` is non-zero.
+            core::num::NonZeroU32::new(code).unwrap().into()
+        })
+    }
+}
+
+/// The default algorithm of the [`Rng`] protocol is required to be suitable
+/// for cryptographic use.
+#[cfg(feature = "rand_core")]
+impl rand_core::CryptoRng for Rng {}
+
+/// Source of the random bytes returned by [`fill_bytes`] and
+/// [`fill_seed`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RandomSource {
+    /// The [`Rng`] protocol was used with the given algorithm, or with the
+    /// default algorithm if `None`.
+    Protocol(Option<RngAlgorithmType>),
+
+    /// The `RDRAND` instruction was used because the [`Rng`] protocol was
+    /// not available or failed.
+    Rdrand,
+}
+
+/// Fill `buffer` with random bytes from the best available source.
+///
+/// The [`Rng`] protocol is tried first, using the algorithms in
+/// [`DEFAULT_ALGORITHM_PREFERENCE`]. If the protocol is not available or
+/// fails, the `RDRAND` instruction is used on x86 CPUs that support it.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: no source of random bytes is available.
+pub fn fill_bytes(bt: &BootServices, buffer: &mut [u8]) -> Result<RandomSource> {
+    let from_protocol = bt
+        .get_handle_for_protocol::<Rng>()
+        .and_then(|handle| bt.open_protocol_exclusive::<Rng>(handle))
+        .and_then(|mut rng| rng.get_rng_preferred(&DEFAULT_ALGORITHM_PREFERENCE, buffer));
+    if let Ok(algorithm) = from_protocol {
+        return Ok(RandomSource::Protocol(algorithm));
+    }
+
+    if rdrand::fill_bytes(buffer) {
+        Ok(RandomSource::Rdrand)
+    } else {
+        Err(Error::from(Status::NOT_FOUND))
+    }
+}
+
+/// Fill `buffer` with seed material, e.g. for stack canaries or for
+/// randomizing load addresses.
+///
+/// The buffer is filled with [`fill_bytes`], and then the count of the
+/// [`Timestamp`] protocol, if available, is mixed in. The timestamp only
+/// adds a small amount of entropy, but ensures that seeds differ between
+/// boots even if the random source is of poor quality.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: no source of random bytes is available.
+pub fn fill_seed(bt: &BootServices, buffer: &mut [u8]) -> Result<RandomSource> {
+    let source = fill_bytes(bt, buffer)?;
+
+    let timestamp = bt
+        .get_handle_for_protocol::<Timestamp>()
+        .and_then(|handle| bt.open_protocol_exclusive::<Timestamp>(handle));
+    if let Ok(timestamp) = timestamp {
+        mix_timestamp(buffer, || timestamp.get_timestamp());
+    }
+
+    Ok(source)
+}
+
+/// XOR each 8-byte chunk of `buffer` with a scrambled timestamp.
+///
+/// Each timestamp is passed through the SplitMix64 finalizer so that the
+/// few bits that change between reads are spread over the whole chunk.
+fn mix_timestamp(buffer: &mut [u8], mut get_timestamp: impl FnMut() -> u64) {
+    for (i, chunk) in buffer.chunks_mut(8).enumerate() {
+        let mut z =
+            get_timestamp().wrapping_add((i as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        for (byte, mix) in chunk.iter_mut().zip(z.to_le_bytes()) {
+            *byte ^= mix;
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod rdrand {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::{__cpuid, _rdrand32_step as rdrand_step};
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::{__cpuid, _rdrand64_step as rdrand_step};
+
+    /// Number of times to retry `RDRAND` before giving up, as recommended by
+    /// Intel.
+    const RETRIES: usize = 10;
+
+    /// Bit in `ECX` of CPUID leaf 1 indicating `RDRAND` support.
+    const RDRAND_BIT: u32 = 1 << 30;
+
+    /// Fill `buffer` using `RDRAND`. Returns `false` if the instruction is
+    /// not supported or fails repeatedly.
+    pub fn fill_bytes(buffer: &mut [u8]) -> bool {
+        // `__cpuid` is a safe function in newer versions of Rust.
+        #[allow(unused_unsafe)]
+        let supported = unsafe { __cpuid(1) }.ecx & RDRAND_BIT != 0;
+        // SAFETY: support for the instruction was checked above.
+        supported && unsafe { fill_bytes_impl(buffer) }
+    }
+
+    #[target_feature(enable = "rdrand")]
+    unsafe fn fill_bytes_impl(buffer: &mut [u8]) -> bool {
+        let mut value = 0;
+        for chunk in buffer.chunks_mut(core::mem::size_of_val(&value)) {
+            if !(0..RETRIES).any(|_| rdrand_step(&mut value) == 1) {
+                return false;
+            }
+            chunk.copy_from_slice(&value.to_le_bytes()[..chunk.len()]);
+        }
+        true
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+mod rdrand {
+    /// `RDRAND` is only available on x86.
+    pub const fn fill_bytes(_buffer: &mut [u8]) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_timestamp() {
+        let mut buffer = [0; 20];
+        mix_timestamp(&mut buffer, || 0);
+        // Chunks differ even if the timestamp doesn't change.
+        assert_ne!(buffer[..8], buffer[8..16]);
+        assert_ne!(buffer[..4], buffer[16..]);
+
+        let mut other = [0; 20];
+        mix_timestamp(&mut other, || 1);
+        assert_ne!(buffer, other);
+
+        // Mixing is an XOR, so mixing twice restores the input.
+        mix_timestamp(&mut other, || 1);
+        assert_eq!(other, [0; 20]);
+    }
 }
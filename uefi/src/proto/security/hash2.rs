@@ -0,0 +1,235 @@
+use crate::proto::tcg::HashAlgorithm;
+use crate::proto::unsafe_protocol;
+use crate::{Error, Handle, Result, Status, StatusExt};
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Deref;
+use core::ptr;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::hash2::{Hash2AlgorithmType, Hash2Output, Hash2Protocol};
+
+/// Size in bytes of the largest digest the [`Hash2`] protocol can produce.
+pub const MAX_DIGEST_SIZE: usize = 64;
+
+/// Get the [`Hash2AlgorithmType`] corresponding to a single
+/// [`HashAlgorithm`] flag, along with the size of its digest.
+///
+/// Returns `None` if `algorithm` does not have exactly one of the
+/// [`SHA1`], [`SHA256`], [`SHA384`], or [`SHA512`] bits set.
+///
+/// [`SHA1`]: HashAlgorithm::SHA1
+/// [`SHA256`]: HashAlgorithm::SHA256
+/// [`SHA384`]: HashAlgorithm::SHA384
+/// [`SHA512`]: HashAlgorithm::SHA512
+fn algorithm_type(algorithm: HashAlgorithm) -> Option<(Hash2AlgorithmType, usize)> {
+    if algorithm == HashAlgorithm::SHA1 {
+        Some((Hash2AlgorithmType::SHA1, 20))
+    } else if algorithm == HashAlgorithm::SHA256 {
+        Some((Hash2AlgorithmType::SHA256, 32))
+    } else if algorithm == HashAlgorithm::SHA384 {
+        Some((Hash2AlgorithmType::SHA384, 48))
+    } else if algorithm == HashAlgorithm::SHA512 {
+        Some((Hash2AlgorithmType::SHA512, 64))
+    } else {
+        None
+    }
+}
+
+/// Same as [`algorithm_type`], but returns an `UNSUPPORTED` error for
+/// algorithms that cannot be used with the [`Hash2`] protocol.
+fn algorithm_type_or_err(algorithm: HashAlgorithm) -> Result<(Hash2AlgorithmType, usize)> {
+    algorithm_type(algorithm).ok_or_else(|| Error::from(Status::UNSUPPORTED))
+}
+
+/// Message digest produced by the [`Hash2`] protocol.
+///
+/// The digest dereferences to a byte slice whose length matches the
+/// algorithm that was used to produce it.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Digest {
+    bytes: [u8; MAX_DIGEST_SIZE],
+    len: usize,
+}
+
+impl Digest {
+    /// Get the bytes of the digest.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Pointer to the digest bytes, reinterpreted as the raw output union.
+    fn as_output_ptr(&mut self) -> *mut Hash2Output {
+        self.bytes.as_mut_ptr().cast()
+    }
+}
+
+impl Deref for Digest {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for Digest {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Debug for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for byte in self.as_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Hash2 protocol.
+///
+/// This protocol provides access to hash algorithms implemented by the
+/// firmware. Supported algorithms are selected with the [`HashAlgorithm`]
+/// flags also used by the [`tcg`] protocols; exactly one of [`SHA1`],
+/// [`SHA256`], [`SHA384`], or [`SHA512`] must be passed at a time.
+///
+/// Instances of this protocol are usually created through the
+/// [`Hash2ServiceBinding`] protocol.
+///
+/// Corresponds to the C type `EFI_HASH2_PROTOCOL`.
+///
+/// [`tcg`]: crate::proto::tcg
+/// [`SHA1`]: HashAlgorithm::SHA1
+/// [`SHA256`]: HashAlgorithm::SHA256
+/// [`SHA384`]: HashAlgorithm::SHA384
+/// [`SHA512`]: HashAlgorithm::SHA512
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Hash2Protocol::GUID)]
+pub struct Hash2(Hash2Protocol);
+
+impl Hash2 {
+    /// Get the size in bytes of the digest produced by `algorithm`, as
+    /// reported by the firmware.
+    pub fn hash_size(&self, algorithm: HashAlgorithm) -> Result<usize> {
+        let (algorithm, _) = algorithm_type_or_err(algorithm)?;
+        let mut hash_size = 0;
+        unsafe {
+            (self.0.get_hash_size)(&self.0, &algorithm, &mut hash_size)
+                .to_result_with_val(|| hash_size)
+        }
+    }
+
+    /// Hash `message` in one shot.
+    pub fn hash(&mut self, algorithm: HashAlgorithm, message: &[u8]) -> Result<Digest> {
+        let (algorithm, len) = algorithm_type_or_err(algorithm)?;
+        let mut digest = Digest {
+            bytes: [0; MAX_DIGEST_SIZE],
+            len,
+        };
+        unsafe {
+            (self.0.hash)(
+                &mut self.0,
+                &algorithm,
+                message.as_ptr(),
+                message.len(),
+                digest.as_output_ptr(),
+            )
+            .to_result_with_val(|| digest)
+        }
+    }
+
+    /// Start a new streaming hash operation.
+    ///
+    /// Data is added to the hash with [`HashSession::hash_update`], and the
+    /// digest is retrieved with [`HashSession::hash_final`]. Starting a new
+    /// operation discards any operation that has not been finalized.
+    pub fn hash_init(&mut self, algorithm: HashAlgorithm) -> Result<HashSession<'_>> {
+        let (algorithm, len) = algorithm_type_or_err(algorithm)?;
+        unsafe { (self.0.hash_init)(&mut self.0, &algorithm) }
+            .to_result_with_val(|| HashSession { hash2: self, len })
+    }
+}
+
+/// A streaming hash operation started with [`Hash2::hash_init`].
+#[derive(Debug)]
+pub struct HashSession<'a> {
+    hash2: &'a mut Hash2,
+    len: usize,
+}
+
+impl HashSession<'_> {
+    /// Add `message` to the data being hashed.
+    pub fn hash_update(&mut self, message: &[u8]) -> Result {
+        let proto = &mut self.hash2.0;
+        unsafe { (proto.hash_update)(proto, message.as_ptr(), message.len()) }.to_result()
+    }
+
+    /// Finish the operation and get the digest of all data passed to
+    /// [`hash_update`].
+    ///
+    /// [`hash_update`]: Self::hash_update
+    pub fn hash_final(self) -> Result<Digest> {
+        let proto = &mut self.hash2.0;
+        let mut digest = Digest {
+            bytes: [0; MAX_DIGEST_SIZE],
+            len: self.len,
+        };
+        unsafe { (proto.hash_final)(proto, digest.as_output_ptr()) }.to_result_with_val(|| digest)
+    }
+}
+
+/// Service binding protocol for creating [`Hash2`] instances.
+///
+/// Corresponds to the C type `EFI_HASH2_SERVICE_BINDING_PROTOCOL`.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Hash2Protocol::SERVICE_BINDING_GUID)]
+pub struct Hash2ServiceBinding(ServiceBindingProtocol);
+
+impl Hash2ServiceBinding {
+    /// Create a new handle with a [`Hash2`] protocol installed on it.
+    ///
+    /// The child should be released with [`destroy_child`] once it is no
+    /// longer needed.
+    ///
+    /// [`destroy_child`]: Self::destroy_child
+    pub fn create_child(&mut self) -> Result<Handle> {
+        let mut child_handle = ptr::null_mut();
+        unsafe {
+            (self.0.create_child)(&mut self.0, &mut child_handle).to_result_with_val(|| {
+                Handle::from_ptr(child_handle).expect("create_child returned a null handle")
+            })
+        }
+    }
+
+    /// Destroy a child previously created with [`create_child`].
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, child_handle: Handle) -> Result {
+        unsafe { (self.0.destroy_child)(&mut self.0, child_handle.as_ptr()) }.to_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_algorithm_type() {
+        assert_eq!(
+            algorithm_type(HashAlgorithm::SHA256),
+            Some((Hash2AlgorithmType::SHA256, 32))
+        );
+        assert_eq!(
+            algorithm_type(HashAlgorithm::SHA512),
+            Some((Hash2AlgorithmType::SHA512, 64))
+        );
+        assert_eq!(algorithm_type(HashAlgorithm::SM3_256), None);
+        assert_eq!(algorithm_type(HashAlgorithm::empty()), None);
+        assert_eq!(
+            algorithm_type(HashAlgorithm::SHA1 | HashAlgorithm::SHA256),
+            None
+        );
+    }
+}
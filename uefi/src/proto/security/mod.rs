@@ -1,4 +1,18 @@
 //! Protocols related to secure technologies.
 
+mod hash2;
 mod memory_protection;
+mod pkcs7;
+mod security_arch;
+mod signature;
+
+pub use hash2::{Digest, Hash2, Hash2ServiceBinding, HashSession, MAX_DIGEST_SIZE};
 pub use memory_protection::MemoryProtection;
+pub use pkcs7::Pkcs7Verify;
+pub use security_arch::{
+    AuthenticationStatus, Security2Arch, Security2FileAuthenticationFn, SecurityArch,
+    SecurityFileAuthenticationStateFn,
+};
+pub use signature::{
+    Signature, SignatureList, SignatureListError, SignatureListIter, SignatureLists, SignatureType,
+};
@@ -0,0 +1,137 @@
+use crate::proto::unsafe_protocol;
+use uefi_raw::protocol::pkcs7::Pkcs7VerifyProtocol;
+
+#[cfg(feature = "alloc")]
+use {
+    super::SignatureLists,
+    crate::{Result, Status, StatusExt},
+    alloc::vec::Vec,
+    core::ptr,
+    uefi_raw::signature::SignatureList as RawSignatureList,
+};
+
+/// PKCS7 verify protocol.
+///
+/// This protocol verifies PKCS #7 signatures against databases of allowed
+/// and revoked signatures, such as the `db` and `dbx` variables. Each
+/// database is passed in as [`SignatureLists`].
+///
+/// Corresponds to the C type `EFI_PKCS7_VERIFY_PROTOCOL`.
+///
+/// [`SignatureLists`]: super::SignatureLists
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Pkcs7VerifyProtocol::GUID)]
+pub struct Pkcs7Verify(Pkcs7VerifyProtocol);
+
+#[cfg(feature = "alloc")]
+impl Pkcs7Verify {
+    /// Verify a PKCS #7 `SignedData` structure.
+    ///
+    /// If `in_data` is `Some`, `signed_data` is treated as a detached
+    /// signature of `in_data`. Otherwise the signed content is extracted
+    /// from `signed_data` and copied to `content`, and the filled part of
+    /// `content` is returned.
+    ///
+    /// The signature must chain to an entry in `allowed_db` and must not
+    /// match an entry in `revoked_db`. If `timestamp_db` is provided,
+    /// revoked certificates are still accepted if the signature has a
+    /// timestamp from a trusted authority that predates the revocation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::SECURITY_VIOLATION`]: the signature could not be verified,
+    ///   or was revoked.
+    /// * [`Status::BUFFER_TOO_SMALL`]: `content` is too small to hold the
+    ///   embedded content. The required size is returned in the error data.
+    /// * [`Status::UNSUPPORTED`]: `signed_data` is not a supported PKCS #7
+    ///   structure.
+    pub fn verify_buffer<'buf>(
+        &mut self,
+        signed_data: &[u8],
+        in_data: Option<&[u8]>,
+        allowed_db: SignatureLists,
+        revoked_db: Option<SignatureLists>,
+        timestamp_db: Option<SignatureLists>,
+        content: &'buf mut [u8],
+    ) -> Result<&'buf mut [u8], Option<usize>> {
+        let allowed_db = allowed_db.to_ffi_list();
+        let revoked_db = revoked_db.map(SignatureLists::to_ffi_list);
+        let timestamp_db = timestamp_db.map(SignatureLists::to_ffi_list);
+        let (in_data_ptr, in_data_size) =
+            in_data.map_or((ptr::null(), 0), |data| (data.as_ptr(), data.len()));
+        let mut content_size = content.len();
+
+        unsafe {
+            (self.0.verify_buffer)(
+                &mut self.0,
+                signed_data.as_ptr().cast(),
+                signed_data.len(),
+                in_data_ptr.cast(),
+                in_data_size,
+                allowed_db.as_ptr(),
+                opt_list_ptr(&revoked_db),
+                opt_list_ptr(&timestamp_db),
+                content.as_mut_ptr().cast(),
+                &mut content_size,
+            )
+        }
+        .to_result_with(
+            || &mut content[..content_size],
+            |status| {
+                if status == Status::BUFFER_TOO_SMALL {
+                    Some(content_size)
+                } else {
+                    None
+                }
+            },
+        )
+    }
+
+    /// Verify a detached PKCS #7 signature of a hash.
+    ///
+    /// `in_hash` is the hash of the signed content; its algorithm must match
+    /// the digest algorithm in `signature`. The database arguments are used
+    /// in the same way as in [`verify_buffer`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::SECURITY_VIOLATION`]: the signature could not be verified,
+    ///   or was revoked.
+    /// * [`Status::UNSUPPORTED`]: `signature` is not a supported PKCS #7
+    ///   structure.
+    ///
+    /// [`verify_buffer`]: Self::verify_buffer
+    pub fn verify_signature(
+        &mut self,
+        signature: &[u8],
+        in_hash: &[u8],
+        allowed_db: SignatureLists,
+        revoked_db: Option<SignatureLists>,
+        timestamp_db: Option<SignatureLists>,
+    ) -> Result {
+        let allowed_db = allowed_db.to_ffi_list();
+        let revoked_db = revoked_db.map(SignatureLists::to_ffi_list);
+        let timestamp_db = timestamp_db.map(SignatureLists::to_ffi_list);
+
+        unsafe {
+            (self.0.verify_signature)(
+                &mut self.0,
+                signature.as_ptr().cast(),
+                signature.len(),
+                in_hash.as_ptr().cast(),
+                in_hash.len(),
+                allowed_db.as_ptr(),
+                opt_list_ptr(&revoked_db),
+                opt_list_ptr(&timestamp_db),
+            )
+        }
+        .to_result()
+    }
+}
+
+/// Get a pointer to an optional null-terminated list, or null if `None`.
+#[cfg(feature = "alloc")]
+fn opt_list_ptr(list: &Option<Vec<*const RawSignatureList>>) -> *const *const RawSignatureList {
+    list.as_ref().map_or(ptr::null(), |list| list.as_ptr())
+}
@@ -0,0 +1,140 @@
+use crate::proto::device_path::DevicePath;
+use crate::proto::unsafe_protocol;
+use crate::{Result, StatusExt};
+use core::{mem, ptr};
+use uefi_raw::protocol::security::{Security2ArchProtocol, SecurityArchProtocol};
+
+pub use uefi_raw::protocol::security::{
+    AuthenticationStatus, Security2FileAuthenticationFn, SecurityFileAuthenticationStateFn,
+};
+
+/// Security architectural protocol.
+///
+/// This protocol is used by the DXE core to check the authentication
+/// status of files found in firmware volumes. It is defined in the UEFI
+/// Platform Initialization (PI) specification.
+///
+/// Corresponds to the C type `EFI_SECURITY_ARCH_PROTOCOL`.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(SecurityArchProtocol::GUID)]
+pub struct SecurityArch(SecurityArchProtocol);
+
+impl SecurityArch {
+    /// Ask the platform whether a file with the given authentication status
+    /// may be used.
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::SECURITY_VIOLATION`]: the file must not be used, and
+    ///   the platform will not defer the decision.
+    /// * [`uefi::Status::ACCESS_DENIED`]: the file must not be used now, but
+    ///   may be deferred and used later.
+    pub fn file_authentication_state(
+        &self,
+        authentication_status: AuthenticationStatus,
+        file: &DevicePath,
+    ) -> Result {
+        unsafe {
+            (self.0.file_authentication_state)(
+                &self.0,
+                authentication_status,
+                file.as_ffi_ptr().cast(),
+            )
+        }
+        .to_result()
+    }
+
+    /// Replace the firmware's implementation of
+    /// [`file_authentication_state`] with `hook`. The previous implementation
+    /// is returned so that the hook can call it, and so that it can be
+    /// restored later.
+    ///
+    /// # Safety
+    ///
+    /// `hook` will be called by the firmware for files loaded by any image,
+    /// so it must remain valid until the previous implementation has been
+    /// restored. In particular, the image containing `hook` must restore the
+    /// previous implementation before it is unloaded.
+    ///
+    /// [`file_authentication_state`]: Self::file_authentication_state
+    pub unsafe fn replace_file_authentication_state(
+        &mut self,
+        hook: SecurityFileAuthenticationStateFn,
+    ) -> SecurityFileAuthenticationStateFn {
+        mem::replace(&mut self.0.file_authentication_state, hook)
+    }
+}
+
+/// Security2 architectural protocol.
+///
+/// This protocol is used by the `LoadImage` boot service to check whether
+/// an image passes the platform's security policy, e.g. Secure Boot
+/// signature verification. It is defined in the UEFI Platform
+/// Initialization (PI) specification.
+///
+/// Loaders that verify images themselves, like shim, replace the
+/// firmware's implementation with [`replace_file_authentication`] so that
+/// images they trust can be chainloaded with [`BootServices::load_image`].
+///
+/// Corresponds to the C type `EFI_SECURITY2_ARCH_PROTOCOL`.
+///
+/// [`replace_file_authentication`]: Self::replace_file_authentication
+/// [`BootServices::load_image`]: crate::table::boot::BootServices::load_image
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Security2ArchProtocol::GUID)]
+pub struct Security2Arch(Security2ArchProtocol);
+
+impl Security2Arch {
+    /// Ask the platform whether an image passes its security policy.
+    ///
+    /// `file` is the device path the image was loaded from, if known, and
+    /// `buffer` contains the image data. If `boot_policy` is true, the
+    /// request originates from the boot manager.
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::SECURITY_VIOLATION`]: the image must not be used,
+    ///   and the platform will not defer the decision.
+    /// * [`uefi::Status::ACCESS_DENIED`]: the image must not be used now, but
+    ///   may be deferred and used later.
+    pub fn file_authentication(
+        &self,
+        file: Option<&DevicePath>,
+        buffer: &[u8],
+        boot_policy: bool,
+    ) -> Result {
+        let file = file.map(|p| p.as_ffi_ptr()).unwrap_or(ptr::null());
+        unsafe {
+            (self.0.file_authentication)(
+                &self.0,
+                file.cast(),
+                // The buffer is only read, despite the non-const pointer.
+                buffer.as_ptr().cast_mut().cast(),
+                buffer.len(),
+                boot_policy,
+            )
+        }
+        .to_result()
+    }
+
+    /// Replace the firmware's implementation of [`file_authentication`]
+    /// with `hook`. The previous implementation is returned so that the hook
+    /// can call it, and so that it can be restored later.
+    ///
+    /// # Safety
+    ///
+    /// `hook` will be called by the firmware for images loaded by any image,
+    /// so it must remain valid until the previous implementation has been
+    /// restored. In particular, the image containing `hook` must restore the
+    /// previous implementation before it is unloaded.
+    ///
+    /// [`file_authentication`]: Self::file_authentication
+    pub unsafe fn replace_file_authentication(
+        &mut self,
+        hook: Security2FileAuthenticationFn,
+    ) -> Security2FileAuthenticationFn {
+        mem::replace(&mut self.0.file_authentication, hook)
+    }
+}
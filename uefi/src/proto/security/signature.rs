@@ -0,0 +1,305 @@
+use crate::Guid;
+use core::fmt::{self, Display, Formatter};
+use core::mem;
+use uefi_raw::signature::SignatureList as RawSignatureList;
+
+pub use uefi_raw::signature::SignatureType;
+
+/// Size of the `EFI_SIGNATURE_LIST` header.
+const LIST_HEADER_SIZE: usize = mem::size_of::<RawSignatureList>();
+
+/// Size of the owner GUID at the start of each `EFI_SIGNATURE_DATA`.
+const OWNER_SIZE: usize = mem::size_of::<Guid>();
+
+/// Error returned by [`SignatureLists::new`] if the data is not a valid
+/// sequence of signature lists.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureListError {
+    /// The data ends in the middle of a signature list header.
+    Truncated {
+        /// Offset of the incomplete header.
+        offset: usize,
+    },
+
+    /// The size fields of a signature list header are inconsistent with
+    /// each other or with the amount of available data.
+    InvalidSize {
+        /// Offset of the invalid header.
+        offset: usize,
+    },
+}
+
+impl Display for SignatureListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { offset } => {
+                write!(f, "truncated signature list at offset {offset}")
+            }
+            Self::InvalidSize { offset } => {
+                write!(f, "invalid signature list size at offset {offset}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl core::error::Error for SignatureListError {}
+
+/// A validated sequence of `EFI_SIGNATURE_LIST` structures.
+///
+/// This is the format used by signature database variables such as `db`
+/// and `dbx` (see [`VariableVendor::IMAGE_SECURITY_DATABASE`]).
+///
+/// [`VariableVendor::IMAGE_SECURITY_DATABASE`]: crate::table::runtime::VariableVendor::IMAGE_SECURITY_DATABASE
+#[derive(Clone, Copy, Debug)]
+pub struct SignatureLists<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> SignatureLists<'a> {
+    /// Validate that `data` contains a sequence of signature lists.
+    pub fn new(data: &'a [u8]) -> Result<Self, SignatureListError> {
+        let mut offset = 0;
+        while offset < data.len() {
+            let list = SignatureList::parse(&data[offset..], offset)?;
+            offset += list.data.len();
+        }
+        Ok(Self { data })
+    }
+
+    /// Get the underlying bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns `true` if there are no signature lists.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Get an iterator over the signature lists.
+    #[must_use]
+    pub const fn iter(&self) -> SignatureListIter<'a> {
+        SignatureListIter { data: self.data }
+    }
+
+    /// Get a null-terminated array of pointers to each list, as expected
+    /// by the [`Pkcs7Verify`] protocol.
+    ///
+    /// [`Pkcs7Verify`]: super::Pkcs7Verify
+    #[cfg(feature = "alloc")]
+    pub(crate) fn to_ffi_list(self) -> alloc::vec::Vec<*const RawSignatureList> {
+        self.iter()
+            .map(|list| list.data.as_ptr().cast())
+            .chain(core::iter::once(core::ptr::null()))
+            .collect()
+    }
+}
+
+impl<'a> IntoIterator for SignatureLists<'a> {
+    type Item = SignatureList<'a>;
+    type IntoIter = SignatureListIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the lists in [`SignatureLists`].
+#[derive(Clone, Debug)]
+pub struct SignatureListIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for SignatureListIter<'a> {
+    type Item = SignatureList<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        // The data was validated when `SignatureLists` was created.
+        let list = SignatureList::parse(self.data, 0).ok()?;
+        self.data = &self.data[list.data.len()..];
+        Some(list)
+    }
+}
+
+/// A single `EFI_SIGNATURE_LIST`, containing signatures that all have the
+/// same type and size.
+#[derive(Clone, Copy, Debug)]
+pub struct SignatureList<'a> {
+    header: RawSignatureList,
+    data: &'a [u8],
+}
+
+impl<'a> SignatureList<'a> {
+    /// Parse the list at the start of `data`. The `offset` is only used for
+    /// error reporting.
+    fn parse(data: &'a [u8], offset: usize) -> Result<Self, SignatureListError> {
+        if data.len() < LIST_HEADER_SIZE {
+            return Err(SignatureListError::Truncated { offset });
+        }
+        // SAFETY: the length was checked above, and the header contains
+        // only plain integers and GUIDs.
+        let header = unsafe { data.as_ptr().cast::<RawSignatureList>().read_unaligned() };
+
+        let list_size = header.signature_list_size as usize;
+        let header_size = header.signature_header_size as usize;
+        let signature_size = header.signature_size as usize;
+
+        let body_size = list_size
+            .checked_sub(LIST_HEADER_SIZE)
+            .and_then(|size| size.checked_sub(header_size))
+            .ok_or(SignatureListError::InvalidSize { offset })?;
+        let valid = list_size <= data.len()
+            && signature_size >= OWNER_SIZE
+            && body_size % signature_size == 0;
+        if !valid {
+            return Err(SignatureListError::InvalidSize { offset });
+        }
+
+        Ok(Self {
+            header,
+            data: &data[..list_size],
+        })
+    }
+
+    /// Type of the signatures in this list.
+    #[must_use]
+    pub const fn signature_type(&self) -> SignatureType {
+        self.header.signature_type
+    }
+
+    /// Size in bytes of each signature entry, including the owner GUID.
+    #[must_use]
+    pub const fn signature_size(&self) -> usize {
+        self.header.signature_size as usize
+    }
+
+    /// Type-specific data between the list header and the first signature.
+    #[must_use]
+    pub fn signature_header(&self) -> &'a [u8] {
+        let header_size = self.header.signature_header_size as usize;
+        &self.data[LIST_HEADER_SIZE..LIST_HEADER_SIZE + header_size]
+    }
+
+    /// Get an iterator over the signatures in the list.
+    pub fn signatures(&self) -> impl Iterator<Item = Signature<'a>> {
+        let start = LIST_HEADER_SIZE + self.header.signature_header_size as usize;
+        self.data[start..]
+            .chunks_exact(self.signature_size())
+            .map(|entry| {
+                let (owner, data) = entry.split_at(OWNER_SIZE);
+                Signature {
+                    owner: Guid::from_bytes(owner.try_into().unwrap()),
+                    data,
+                }
+            })
+    }
+
+    /// Get the raw bytes of the list, including the header.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// A single entry of a [`SignatureList`].
+///
+/// Corresponds to the C type `EFI_SIGNATURE_DATA`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Signature<'a> {
+    /// Identifies the agent that added the signature.
+    pub owner: Guid,
+
+    /// Signature data. The format depends on the [`SignatureType`] of the
+    /// list containing the signature.
+    pub data: &'a [u8],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use alloc::vec::Vec;
+
+    fn make_list(ty: SignatureType, header: &[u8], owner: Guid, sigs: &[&[u8]]) -> Vec<u8> {
+        let sig_size = OWNER_SIZE + sigs[0].len();
+        let list_size = LIST_HEADER_SIZE + header.len() + sig_size * sigs.len();
+
+        let mut v = Vec::new();
+        v.extend(ty.0.to_bytes());
+        v.extend(u32::try_from(list_size).unwrap().to_le_bytes());
+        v.extend(u32::try_from(header.len()).unwrap().to_le_bytes());
+        v.extend(u32::try_from(sig_size).unwrap().to_le_bytes());
+        v.extend(header);
+        for sig in sigs {
+            v.extend(owner.to_bytes());
+            v.extend(*sig);
+        }
+        v
+    }
+
+    #[test]
+    fn test_signature_lists() {
+        let owner = guid!("77fa9abd-0359-4d32-bd60-28f4e78f784b");
+        let mut data = make_list(SignatureType::SHA256, &[], owner, &[&[1; 32], &[2; 32]]);
+        data.extend(make_list(SignatureType::X509, &[9, 9], owner, &[&[3; 5]]));
+
+        let lists = SignatureLists::new(&data).unwrap();
+        let lists: Vec<_> = lists.iter().collect();
+        assert_eq!(lists.len(), 2);
+
+        assert_eq!(lists[0].signature_type(), SignatureType::SHA256);
+        assert_eq!(lists[0].signature_header(), &[]);
+        let sigs: Vec<_> = lists[0].signatures().collect();
+        assert_eq!(
+            sigs,
+            [
+                Signature {
+                    owner,
+                    data: &[1; 32]
+                },
+                Signature {
+                    owner,
+                    data: &[2; 32]
+                }
+            ]
+        );
+
+        assert_eq!(lists[1].signature_type(), SignatureType::X509);
+        assert_eq!(lists[1].signature_header(), &[9, 9]);
+        assert_eq!(lists[1].signatures().count(), 1);
+        assert_eq!(lists[1].as_bytes().len(), LIST_HEADER_SIZE + 2 + 16 + 5);
+
+        assert!(SignatureLists::new(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_signature_lists_invalid() {
+        let owner = guid!("77fa9abd-0359-4d32-bd60-28f4e78f784b");
+        let list = make_list(SignatureType::SHA256, &[], owner, &[&[1; 32]]);
+
+        assert_eq!(
+            SignatureLists::new(&list[..10]).unwrap_err(),
+            SignatureListError::Truncated { offset: 0 }
+        );
+        assert_eq!(
+            SignatureLists::new(&list[..list.len() - 1]).unwrap_err(),
+            SignatureListError::InvalidSize { offset: 0 }
+        );
+
+        // Signature size that doesn't evenly divide the list.
+        let mut bad = list.clone();
+        bad[24..28].copy_from_slice(&47u32.to_le_bytes());
+        let mut data = list.clone();
+        data.extend(&bad);
+        assert_eq!(
+            SignatureLists::new(&data).unwrap_err(),
+            SignatureListError::InvalidSize { offset: list.len() }
+        );
+    }
+}
@@ -13,6 +13,8 @@ use crate::{Result, Status, StatusExt};
 use core::ffi::c_void;
 use core::mem::MaybeUninit;
 
+pub mod sbat;
+
 // The `PE_COFF_LOADER_IMAGE_CONTEXT` type. None of our methods need to inspect
 // the fields of this struct, we just need to make sure it is the right size.
 #[repr(C)]
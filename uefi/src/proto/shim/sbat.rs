@@ -0,0 +1,497 @@
+//! SBAT (Secure Boot Advanced Targeting) metadata.
+//!
+//! SBAT is the revocation mechanism used by [shim]. Each image carries a
+//! `.sbat` PE section containing CSV records, one per component built into
+//! the image, each with a *generation* number. The platform stores a
+//! revocation list in the `SbatLevel` UEFI variable with the minimum
+//! generation that is still allowed for each component. An image is
+//! rejected if any of its components has a generation lower than the
+//! minimum in the revocation list.
+//!
+//! # Example
+//!
+//! ```
+//! use uefi::proto::shim::sbat::{Sbat, SbatLevel};
+//!
+//! let image_sbat = Sbat::new(
+//!     b"sbat,1,SBAT Version,sbat,1,https://github.com/rhboot/shim/blob/main/SBAT.md\n\
+//!       grub,3,Free Software Foundation,grub,2.06,https://www.gnu.org/software/grub/\n",
+//! )
+//! .unwrap();
+//!
+//! let level = SbatLevel::new(b"sbat,1,2022052400\ngrub,2\n").unwrap();
+//! assert!(level.verify(&image_sbat).is_ok());
+//!
+//! let level = SbatLevel::new(b"sbat,1,2023012900\nshim,2\ngrub,4\n").unwrap();
+//! assert!(level.verify(&image_sbat).is_err());
+//! ```
+//!
+//! [shim]: https://github.com/rhboot/shim/blob/main/SBAT.md
+
+use crate::table::runtime::{RuntimeServices, VariableVendor};
+use crate::{cstr16, guid, CStr16, Status};
+use core::fmt::{self, Display, Formatter};
+use core::str;
+
+/// Name of the PE section containing an image's SBAT data.
+pub const SBAT_SECTION_NAME: &[u8] = b".sbat";
+
+/// Name of the UEFI variable containing the SBAT revocation list.
+pub const SBAT_LEVEL_VARIABLE_NAME: &CStr16 = cstr16!("SbatLevel");
+
+/// Vendor GUID of shim's UEFI variables, including
+/// [`SBAT_LEVEL_VARIABLE_NAME`].
+pub const SHIM_LOCK_VENDOR: VariableVendor =
+    VariableVendor(guid!("605dab50-e046-4300-abb6-3dd810dd8b23"));
+
+/// Number of fields in a record of an image's `.sbat` section.
+const IMAGE_RECORD_FIELDS: usize = 6;
+
+/// Number of fields in a record of the revocation list.
+const LEVEL_RECORD_FIELDS: usize = 2;
+
+/// Errors that can occur when parsing SBAT data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SbatError {
+    /// The data is not valid UTF-8.
+    InvalidUtf8,
+
+    /// A record has too few fields, an empty component name, or a
+    /// component generation that is not a decimal number.
+    InvalidRecord {
+        /// Zero-based index of the line containing the invalid record.
+        line: usize,
+    },
+
+    /// The revocation list does not start with an `sbat` record.
+    MissingSbatRecord,
+
+    /// The image is not a valid PE image.
+    InvalidPe,
+
+    /// The image does not have a `.sbat` section.
+    MissingSection,
+}
+
+impl Display for SbatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 => write!(f, "SBAT data is not valid UTF-8"),
+            Self::InvalidRecord { line } => write!(f, "invalid SBAT record on line {line}"),
+            Self::MissingSbatRecord => write!(f, "SBAT revocation list has no sbat record"),
+            Self::InvalidPe => write!(f, "invalid PE image"),
+            Self::MissingSection => write!(f, "PE image has no .sbat section"),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl core::error::Error for SbatError {}
+
+/// A component entry in an image's `.sbat` section.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SbatEntry<'a> {
+    /// Name of the component, e.g. `grub`.
+    pub component_name: &'a str,
+
+    /// Security generation of the component.
+    pub component_generation: u32,
+
+    /// Human-readable name of the vendor that built the component.
+    pub vendor_name: &'a str,
+
+    /// Name of the vendor's package containing the component.
+    pub vendor_package_name: &'a str,
+
+    /// Vendor's version of the package.
+    pub vendor_version: &'a str,
+
+    /// URL with more information about the vendor's package.
+    pub vendor_url: &'a str,
+}
+
+/// A minimum component generation in the SBAT revocation list.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SbatRevocation<'a> {
+    /// Name of the component, e.g. `grub`.
+    pub component_name: &'a str,
+
+    /// Minimum security generation of the component that is allowed.
+    pub component_generation: u32,
+}
+
+/// Contents of an image's `.sbat` section.
+#[derive(Clone, Copy, Debug)]
+pub struct Sbat<'a> {
+    data: &'a str,
+}
+
+impl<'a> Sbat<'a> {
+    /// Parse SBAT data, e.g. the contents of an image's `.sbat` section.
+    ///
+    /// Parsing stops at the first null byte, since the section is commonly
+    /// padded with zeros.
+    pub fn new(data: &'a [u8]) -> Result<Self, SbatError> {
+        let data = to_str(data)?;
+        for (line, fields) in records(data) {
+            parse_image_record(fields).ok_or(SbatError::InvalidRecord { line })?;
+        }
+        Ok(Self { data })
+    }
+
+    /// Find the `.sbat` section in a PE image and parse it.
+    ///
+    /// `image` must contain the image as it is stored on disk, not as it is
+    /// laid out in memory after loading.
+    pub fn from_pe(image: &'a [u8]) -> Result<Self, SbatError> {
+        let section = find_pe_section(image, SBAT_SECTION_NAME)?;
+        Self::new(section)
+    }
+
+    /// Get an iterator over the component entries.
+    pub fn entries(&self) -> impl Iterator<Item = SbatEntry<'a>> {
+        // The data was validated when `Sbat` was created.
+        records(self.data).filter_map(|(_, fields)| parse_image_record(fields))
+    }
+}
+
+/// The SBAT revocation list, as stored in the `SbatLevel` variable.
+#[derive(Clone, Copy, Debug)]
+pub struct SbatLevel<'a> {
+    data: &'a str,
+}
+
+impl<'a> SbatLevel<'a> {
+    /// Parse a revocation list.
+    ///
+    /// The first record must be the `sbat` record, which contains the SBAT
+    /// format generation and optionally a datestamp identifying the
+    /// revocation list. Parsing stops at the first null byte.
+    pub fn new(data: &'a [u8]) -> Result<Self, SbatError> {
+        let data = to_str(data)?;
+        for (line, fields) in records(data) {
+            parse_level_record(fields).ok_or(SbatError::InvalidRecord { line })?;
+        }
+        let level = Self { data };
+        match level.revocations().next() {
+            Some(first) if first.component_name == "sbat" => Ok(level),
+            _ => Err(SbatError::MissingSbatRecord),
+        }
+    }
+
+    /// Read and parse the [`SBAT_LEVEL_VARIABLE_NAME`] variable into `buf`.
+    ///
+    /// # Errors
+    ///
+    /// * Errors from [`RuntimeServices::get_variable`], e.g.
+    ///   [`Status::NOT_FOUND`] if shim has not set up a revocation list.
+    /// * [`Status::COMPROMISED_DATA`]: the variable could not be parsed.
+    pub fn read_variable(rt: &RuntimeServices, buf: &'a mut [u8]) -> crate::Result<Self> {
+        let (data, _) = rt.get_variable(SBAT_LEVEL_VARIABLE_NAME, &SHIM_LOCK_VENDOR, buf)?;
+        Self::new(data).map_err(|_| Status::COMPROMISED_DATA.into())
+    }
+
+    /// Datestamp identifying the revocation list, if present.
+    #[must_use]
+    pub fn datestamp(&self) -> Option<&'a str> {
+        // The first record was validated to be the `sbat` record.
+        records(self.data)
+            .next()
+            .and_then(|(_, mut fields)| fields.nth(LEVEL_RECORD_FIELDS))
+    }
+
+    /// Get an iterator over the revocations, including the leading `sbat`
+    /// record.
+    pub fn revocations(&self) -> impl Iterator<Item = SbatRevocation<'a>> {
+        // The data was validated when `SbatLevel` was created.
+        records(self.data).filter_map(|(_, fields)| parse_level_record(fields))
+    }
+
+    /// Find the first entry of `image` that is revoked by this list.
+    #[must_use]
+    pub fn find_revoked<'b>(&self, image: &Sbat<'b>) -> Option<SbatEntry<'b>> {
+        self.revocations().find_map(|revocation| {
+            image.entries().find(|entry| {
+                entry.component_name == revocation.component_name
+                    && entry.component_generation < revocation.component_generation
+            })
+        })
+    }
+
+    /// Check that none of the entries of `image` are revoked by this list.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::SECURITY_VIOLATION`]: a component of the image is revoked.
+    pub fn verify(&self, image: &Sbat) -> crate::Result {
+        match self.find_revoked(image) {
+            Some(_) => Err(Status::SECURITY_VIOLATION.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Convert the data up to the first null byte to a string.
+fn to_str(data: &[u8]) -> Result<&str, SbatError> {
+    let len = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    str::from_utf8(&data[..len]).map_err(|_| SbatError::InvalidUtf8)
+}
+
+/// Get an iterator over the non-empty lines of CSV data, along with the
+/// line index and an iterator over the fields of that line.
+fn records(data: &str) -> impl Iterator<Item = (usize, str::Split<'_, char>)> {
+    data.split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| (index, line.split(',')))
+}
+
+/// Parse the component name and generation fields shared by both record
+/// types.
+fn parse_component<'a>(fields: &mut str::Split<'a, char>) -> Option<(&'a str, u32)> {
+    let name = fields.next().filter(|name| !name.is_empty())?;
+    let generation = fields.next()?;
+    if !generation.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((name, generation.parse().ok()?))
+}
+
+fn parse_image_record(mut fields: str::Split<'_, char>) -> Option<SbatEntry<'_>> {
+    let (component_name, component_generation) = parse_component(&mut fields)?;
+    let mut vendor = [""; IMAGE_RECORD_FIELDS - 2];
+    for field in &mut vendor {
+        *field = fields.next()?;
+    }
+    let [vendor_name, vendor_package_name, vendor_version, vendor_url] = vendor;
+    Some(SbatEntry {
+        component_name,
+        component_generation,
+        vendor_name,
+        vendor_package_name,
+        vendor_version,
+        vendor_url,
+    })
+}
+
+fn parse_level_record(mut fields: str::Split<'_, char>) -> Option<SbatRevocation<'_>> {
+    let (component_name, component_generation) = parse_component(&mut fields)?;
+    Some(SbatRevocation {
+        component_name,
+        component_generation,
+    })
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Find the file contents of the section named `name` in a PE image.
+fn find_pe_section<'a>(image: &'a [u8], name: &[u8]) -> Result<&'a [u8], SbatError> {
+    // Offset of the `e_lfanew` field in the DOS header.
+    const PE_OFFSET_FIELD: usize = 0x3c;
+    // Size of the PE signature plus the COFF file header.
+    const COFF_END: usize = 4 + 20;
+    // Size of a section header.
+    const SECTION_HEADER_SIZE: usize = 40;
+
+    let find = || -> Option<Result<&'a [u8], SbatError>> {
+        if image.get(..2)? != b"MZ" {
+            return None;
+        }
+        let pe = usize::try_from(read_u32(image, PE_OFFSET_FIELD)?).ok()?;
+        if image.get(pe..pe.checked_add(4)?)? != b"PE\0\0" {
+            return None;
+        }
+        let num_sections = usize::from(read_u16(image, pe + 6)?);
+        let optional_header_size = usize::from(read_u16(image, pe + 20)?);
+        let sections = pe + COFF_END + optional_header_size;
+
+        for i in 0..num_sections {
+            let header = sections + i * SECTION_HEADER_SIZE;
+            let section_name = image.get(header..header + 8)?;
+            let len = section_name.iter().position(|b| *b == 0).unwrap_or(8);
+            if &section_name[..len] != name {
+                continue;
+            }
+
+            let virtual_size = read_u32(image, header + 8)?;
+            let raw_size = read_u32(image, header + 16)?;
+            let raw_offset = usize::try_from(read_u32(image, header + 20)?).ok()?;
+            // The raw data is padded to the file alignment, so prefer the
+            // virtual size if it is smaller.
+            let size = match virtual_size {
+                0 => raw_size,
+                _ => virtual_size.min(raw_size),
+            };
+            let size = usize::try_from(size).ok()?;
+            return Some(Ok(image.get(raw_offset..raw_offset.checked_add(size)?)?));
+        }
+        Some(Err(SbatError::MissingSection))
+    };
+
+    find().unwrap_or(Err(SbatError::InvalidPe))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    const IMAGE_SBAT: &[u8] = b"\
+sbat,1,SBAT Version,sbat,1,https://github.com/rhboot/shim/blob/main/SBAT.md\n\
+shim,2,UEFI shim,shim,1,https://github.com/rhboot/shim\n\
+grub,3,Free Software Foundation,grub,2.06,https://www.gnu.org/software/grub/\n\
+grub.debian,4,Debian,grub2,2.06-3,https://tracker.debian.org/pkg/grub2\n\0\0\0";
+
+    #[test]
+    fn test_image_sbat() {
+        let sbat = Sbat::new(IMAGE_SBAT).unwrap();
+        let entries: Vec<_> = sbat.entries().collect();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[2],
+            SbatEntry {
+                component_name: "grub",
+                component_generation: 3,
+                vendor_name: "Free Software Foundation",
+                vendor_package_name: "grub",
+                vendor_version: "2.06",
+                vendor_url: "https://www.gnu.org/software/grub/",
+            }
+        );
+
+        assert_eq!(
+            Sbat::new(b"sbat,1,a,b,c,d\r\ngrub,1,a,b,c\n").unwrap_err(),
+            SbatError::InvalidRecord { line: 1 }
+        );
+        assert_eq!(
+            Sbat::new(b"grub,x,a,b,c,d\n").unwrap_err(),
+            SbatError::InvalidRecord { line: 0 }
+        );
+        assert_eq!(Sbat::new(b"\xff").unwrap_err(), SbatError::InvalidUtf8);
+    }
+
+    #[test]
+    fn test_sbat_level() {
+        let level = SbatLevel::new(b"sbat,1,2023012900\r\nshim,2\r\ngrub,3\r\n").unwrap();
+        assert_eq!(level.datestamp(), Some("2023012900"));
+        let revocations: Vec<_> = level.revocations().collect();
+        assert_eq!(
+            revocations,
+            [
+                SbatRevocation {
+                    component_name: "sbat",
+                    component_generation: 1
+                },
+                SbatRevocation {
+                    component_name: "shim",
+                    component_generation: 2
+                },
+                SbatRevocation {
+                    component_name: "grub",
+                    component_generation: 3
+                },
+            ]
+        );
+
+        assert_eq!(SbatLevel::new(b"sbat,1\n").unwrap().datestamp(), None);
+        assert_eq!(
+            SbatLevel::new(b"grub,1\n").unwrap_err(),
+            SbatError::MissingSbatRecord
+        );
+        assert_eq!(
+            SbatLevel::new(b"").unwrap_err(),
+            SbatError::MissingSbatRecord
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let sbat = Sbat::new(IMAGE_SBAT).unwrap();
+
+        let level = SbatLevel::new(b"sbat,1,2022052400\ngrub,2\n").unwrap();
+        assert_eq!(level.find_revoked(&sbat), None);
+        assert!(level.verify(&sbat).is_ok());
+
+        // Equal generations are allowed.
+        let level = SbatLevel::new(b"sbat,1,2023012900\nshim,2\ngrub,3\n").unwrap();
+        assert!(level.verify(&sbat).is_ok());
+
+        let level = SbatLevel::new(b"sbat,1,2024010900\nshim,4\ngrub,3\n").unwrap();
+        assert_eq!(
+            level.find_revoked(&sbat).map(|entry| entry.component_name),
+            Some("shim")
+        );
+        assert_eq!(
+            level.verify(&sbat).unwrap_err().status(),
+            Status::SECURITY_VIOLATION
+        );
+
+        // Components are matched by their full name.
+        let level = SbatLevel::new(b"sbat,1\ngrub.debian,5\n").unwrap();
+        assert_eq!(
+            level.find_revoked(&sbat).map(|entry| entry.component_name),
+            Some("grub.debian")
+        );
+    }
+
+    /// Size of the PE signature plus the COFF file header.
+    const COFF_SIZE: usize = 24;
+
+    /// Build a minimal PE image with the given sections.
+    fn make_pe(sections: &[(&[u8], &[u8])]) -> Vec<u8> {
+        let pe = 0x40;
+        let optional_header_size = 0xf0;
+        let section_table = pe + COFF_SIZE + optional_header_size;
+        let mut data_offset = section_table + sections.len() * 40;
+
+        let mut image = vec![0; data_offset];
+        image[..2].copy_from_slice(b"MZ");
+        image[0x3c..0x40].copy_from_slice(&(pe as u32).to_le_bytes());
+        image[pe..pe + 4].copy_from_slice(b"PE\0\0");
+        image[pe + 6..pe + 8].copy_from_slice(&(sections.len() as u16).to_le_bytes());
+        image[pe + 20..pe + 22].copy_from_slice(&(optional_header_size as u16).to_le_bytes());
+
+        for (i, (name, data)) in sections.iter().enumerate() {
+            // Pad the raw data to simulate the file alignment.
+            let raw_size = data.len() + 7;
+            let header = section_table + i * 40;
+            image[header..header + name.len()].copy_from_slice(name);
+            image[header + 8..header + 12].copy_from_slice(&(data.len() as u32).to_le_bytes());
+            image[header + 16..header + 20].copy_from_slice(&(raw_size as u32).to_le_bytes());
+            image[header + 20..header + 24].copy_from_slice(&(data_offset as u32).to_le_bytes());
+            image.extend(*data);
+            image.extend([0xaa; 7]);
+            data_offset += raw_size;
+        }
+        image
+    }
+
+    #[test]
+    fn test_from_pe() {
+        let image = make_pe(&[(b".text", &[0xcc; 16]), (b".sbat", IMAGE_SBAT)]);
+        let sbat = Sbat::from_pe(&image).unwrap();
+        assert_eq!(sbat.entries().count(), 4);
+
+        let image = make_pe(&[(b".text", &[0xcc; 16])]);
+        assert_eq!(
+            Sbat::from_pe(&image).unwrap_err(),
+            SbatError::MissingSection
+        );
+
+        assert_eq!(
+            Sbat::from_pe(&image[..0x50]).unwrap_err(),
+            SbatError::InvalidPe
+        );
+        assert_eq!(Sbat::from_pe(b"").unwrap_err(), SbatError::InvalidPe);
+    }
+}
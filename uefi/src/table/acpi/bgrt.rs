@@ -0,0 +1,78 @@
+use super::{AcpiError, AcpiTable, Sdt};
+
+/// Length of the table.
+const LENGTH: usize = 56;
+
+/// Boot Graphics Resource Table (`BGRT`).
+///
+/// Describes the logo image that the firmware displayed during boot, so
+/// that the OS can keep showing it.
+#[derive(Clone, Copy, Debug)]
+pub struct Bgrt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Bgrt<'a> {
+    const SIGNATURE: [u8; 4] = *b"BGRT";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        let sdt = sdt.expect::<Self>(LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> Bgrt<'a> {
+    /// Get the underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Version of the table. Must be 1.
+    #[must_use]
+    pub fn version(&self) -> u16 {
+        self.sdt.read(36).unwrap()
+    }
+
+    /// Returns `true` if the image is currently displayed.
+    #[must_use]
+    pub fn is_displayed(&self) -> bool {
+        self.status() & 1 != 0
+    }
+
+    /// Clockwise rotation of the image relative to the display, in degrees.
+    #[must_use]
+    pub fn orientation(&self) -> u16 {
+        u16::from((self.status() >> 1) & 0b11) * 90
+    }
+
+    fn status(&self) -> u8 {
+        self.sdt.read(38).unwrap()
+    }
+
+    /// Format of the image.
+    #[must_use]
+    pub fn image_type(&self) -> BgrtImageType {
+        BgrtImageType(self.sdt.read(39).unwrap())
+    }
+
+    /// Physical address of the image.
+    #[must_use]
+    pub fn image_address(&self) -> u64 {
+        self.sdt.read(40).unwrap()
+    }
+
+    /// Horizontal and vertical offset of the upper left corner of the image
+    /// on the screen, in pixels.
+    #[must_use]
+    pub fn image_offset(&self) -> (u32, u32) {
+        (self.sdt.read(48).unwrap(), self.sdt.read(52).unwrap())
+    }
+}
+
+newtype_enum! {
+/// Format of the [`Bgrt`] image.
+pub enum BgrtImageType: u8 => {
+    /// Windows BMP image.
+    BITMAP = 0,
+}}
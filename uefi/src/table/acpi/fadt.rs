@@ -0,0 +1,232 @@
+use super::{AcpiError, AcpiTable, GenericAddress, Sdt};
+use bitflags::bitflags;
+
+/// Length of the ACPI 1.0 FADT, which all later revisions extend.
+const MIN_LENGTH: usize = 116;
+
+/// Fixed ACPI Description Table (`FACP`).
+///
+/// Describes the fixed hardware registers of the platform and points to
+/// the DSDT. Fields that were added in later ACPI revisions return `None`
+/// if the table is too short to contain them.
+#[derive(Clone, Copy, Debug)]
+pub struct Fadt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Fadt<'a> {
+    const SIGNATURE: [u8; 4] = *b"FACP";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        let sdt = sdt.expect::<Self>(MIN_LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> Fadt<'a> {
+    /// Get the underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Read a field that is present in every revision of the table.
+    fn field<T: crate::util::Plain>(&self, offset: usize) -> T {
+        // OK to unwrap: the minimum length was checked in `from_sdt`.
+        self.sdt.read(offset).unwrap()
+    }
+
+    /// Read the 64-bit field at `offset`, falling back to the 32-bit field
+    /// at `legacy_offset` if the former is missing or zero.
+    fn address(&self, offset: usize, legacy_offset: usize) -> u64 {
+        match self.sdt.read::<u64>(offset) {
+            Some(address) if address != 0 => address,
+            _ => u64::from(self.field::<u32>(legacy_offset)),
+        }
+    }
+
+    /// Physical address of the Firmware ACPI Control Structure (`FACS`).
+    #[must_use]
+    pub fn firmware_ctrl_address(&self) -> u64 {
+        self.address(132, 36)
+    }
+
+    /// Physical address of the Differentiated System Description Table.
+    ///
+    /// See also [`AcpiTables::dsdt`].
+    ///
+    /// [`AcpiTables::dsdt`]: super::AcpiTables::dsdt
+    #[must_use]
+    pub fn dsdt_address(&self) -> u64 {
+        self.address(140, 40)
+    }
+
+    /// Power management profile of the platform.
+    #[must_use]
+    pub fn preferred_pm_profile(&self) -> PmProfile {
+        PmProfile(self.field(45))
+    }
+
+    /// Interrupt used by the SCI (System Control Interrupt).
+    #[must_use]
+    pub fn sci_interrupt(&self) -> u16 {
+        self.field(46)
+    }
+
+    /// Legacy hardware features of IA-PC platforms.
+    #[must_use]
+    pub fn iapc_boot_arch(&self) -> IaPcBootArchFlags {
+        IaPcBootArchFlags::from_bits_retain(self.field(109))
+    }
+
+    /// Fixed feature flags.
+    #[must_use]
+    pub fn flags(&self) -> FadtFlags {
+        FadtFlags::from_bits_retain(self.field(112))
+    }
+
+    /// Returns `true` if the platform implements the hardware-reduced ACPI
+    /// model, which has no fixed hardware registers.
+    #[must_use]
+    pub fn is_hardware_reduced(&self) -> bool {
+        self.flags().contains(FadtFlags::HW_REDUCED_ACPI)
+    }
+
+    /// Register used to reset the system, if supported.
+    #[must_use]
+    pub fn reset_register(&self) -> Option<GenericAddress> {
+        if self.flags().contains(FadtFlags::RESET_REG_SUP) {
+            self.sdt.read(116)
+        } else {
+            None
+        }
+    }
+
+    /// Value to write to the [`reset_register`] to reset the system.
+    ///
+    /// [`reset_register`]: Self::reset_register
+    #[must_use]
+    pub fn reset_value(&self) -> Option<u8> {
+        self.sdt.read(128)
+    }
+
+    /// Boot architecture flags of ARM platforms.
+    #[must_use]
+    pub fn arm_boot_arch(&self) -> Option<ArmBootArchFlags> {
+        self.sdt.read(129).map(ArmBootArchFlags::from_bits_retain)
+    }
+
+    /// Minor version of the table. The major version is the
+    /// [`Sdt::revision`].
+    #[must_use]
+    pub fn minor_version(&self) -> Option<u8> {
+        self.sdt.read::<u8>(131).map(|version| version & 0xf)
+    }
+}
+
+newtype_enum! {
+/// Preferred power management profile, as reported by [`Fadt`].
+pub enum PmProfile: u8 => {
+    /// Unspecified.
+    UNSPECIFIED = 0,
+    /// Desktop computer.
+    DESKTOP = 1,
+    /// Laptop or other mobile device.
+    MOBILE = 2,
+    /// Workstation.
+    WORKSTATION = 3,
+    /// Enterprise server.
+    ENTERPRISE_SERVER = 4,
+    /// Small office or home office server.
+    SOHO_SERVER = 5,
+    /// Appliance PC.
+    APPLIANCE_PC = 6,
+    /// Performance server.
+    PERFORMANCE_SERVER = 7,
+    /// Tablet.
+    TABLET = 8,
+}}
+
+bitflags! {
+    /// Fixed feature flags of the [`Fadt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct FadtFlags: u32 {
+        /// The `WBINVD` instruction works correctly.
+        const WBINVD = 1 << 0;
+        /// The `WBINVD` instruction flushes caches but doesn't invalidate them.
+        const WBINVD_FLUSH = 1 << 1;
+        /// The C1 power state is supported on all processors.
+        const PROC_C1 = 1 << 2;
+        /// The C2 power state works on multiprocessor systems.
+        const P_LVL2_UP = 1 << 3;
+        /// The power button is a control method device.
+        const PWR_BUTTON = 1 << 4;
+        /// The sleep button is a control method device.
+        const SLP_BUTTON = 1 << 5;
+        /// RTC wake status is not in the fixed register space.
+        const FIX_RTC = 1 << 6;
+        /// The RTC alarm can wake the system from S4.
+        const RTC_S4 = 1 << 7;
+        /// The PM timer is 32 bits wide instead of 24.
+        const TMR_VAL_EXT = 1 << 8;
+        /// The system supports docking.
+        const DCK_CAP = 1 << 9;
+        /// The [`Fadt::reset_register`] is supported.
+        const RESET_REG_SUP = 1 << 10;
+        /// The system has no internal expansion capabilities.
+        const SEALED_CASE = 1 << 11;
+        /// The system cannot detect monitors or keyboards.
+        const HEADLESS = 1 << 12;
+        /// A native instruction must be executed after writing `SLP_TYPx`.
+        const CPU_SW_SLP = 1 << 13;
+        /// The platform supports `PCIEXP_WAKE_STS` and `PCIEXP_WAKE_EN`.
+        const PCI_EXP_WAK = 1 << 14;
+        /// OSPM should use a platform clock instead of the processor's.
+        const USE_PLATFORM_CLOCK = 1 << 15;
+        /// The RTC status is valid when waking from S4.
+        const S4_RTC_STS_VALID = 1 << 16;
+        /// The platform can be remotely powered on.
+        const REMOTE_POWER_ON_CAPABLE = 1 << 17;
+        /// All local APICs must use the cluster destination model.
+        const FORCE_APIC_CLUSTER_MODEL = 1 << 18;
+        /// All local APICs must use physical destination mode.
+        const FORCE_APIC_PHYSICAL_DESTINATION_MODE = 1 << 19;
+        /// The platform implements the hardware-reduced ACPI model.
+        const HW_REDUCED_ACPI = 1 << 20;
+        /// The platform can achieve power savings in S0 similar to S3.
+        const LOW_POWER_S0_IDLE_CAPABLE = 1 << 21;
+    }
+}
+
+bitflags! {
+    /// IA-PC boot architecture flags of the [`Fadt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct IaPcBootArchFlags: u16 {
+        /// Legacy devices such as serial ports may be present.
+        const LEGACY_DEVICES = 1 << 0;
+        /// An 8042 keyboard controller is present.
+        const I8042 = 1 << 1;
+        /// VGA hardware must not be probed.
+        const VGA_NOT_PRESENT = 1 << 2;
+        /// MSI must not be enabled.
+        const MSI_NOT_SUPPORTED = 1 << 3;
+        /// OSPM must not enable PCIe ASPM controls.
+        const PCIE_ASPM_CONTROLS = 1 << 4;
+        /// The CMOS RTC is not present.
+        const CMOS_RTC_NOT_PRESENT = 1 << 5;
+    }
+}
+
+bitflags! {
+    /// ARM boot architecture flags of the [`Fadt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct ArmBootArchFlags: u16 {
+        /// PSCI is implemented.
+        const PSCI_COMPLIANT = 1 << 0;
+        /// PSCI calls must use `HVC` instead of `SMC`.
+        const PSCI_USE_HVC = 1 << 1;
+    }
+}
@@ -0,0 +1,71 @@
+use super::{AcpiError, AcpiTable, GenericAddress, Sdt};
+
+/// Length of the table.
+const LENGTH: usize = 56;
+
+/// High Precision Event Timer table (`HPET`).
+#[derive(Clone, Copy, Debug)]
+pub struct Hpet<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Hpet<'a> {
+    const SIGNATURE: [u8; 4] = *b"HPET";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        let sdt = sdt.expect::<Self>(LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> Hpet<'a> {
+    /// Get the underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Hardware ID of the event timer block. This is a copy of the lower
+    /// half of the timer's capabilities register.
+    #[must_use]
+    pub fn event_timer_block_id(&self) -> u32 {
+        self.sdt.read(36).unwrap()
+    }
+
+    /// Number of comparators in the event timer block.
+    #[must_use]
+    pub fn comparator_count(&self) -> u8 {
+        ((self.event_timer_block_id() >> 8) & 0x1f) as u8 + 1
+    }
+
+    /// PCI vendor ID of the event timer block.
+    #[must_use]
+    pub fn pci_vendor_id(&self) -> u16 {
+        (self.event_timer_block_id() >> 16) as u16
+    }
+
+    /// Location of the timer registers.
+    #[must_use]
+    pub fn base_address(&self) -> GenericAddress {
+        self.sdt.read(40).unwrap()
+    }
+
+    /// Sequence number of the timer, if there are multiple HPET tables.
+    #[must_use]
+    pub fn hpet_number(&self) -> u8 {
+        self.sdt.read(52).unwrap()
+    }
+
+    /// Minimum clock tick, in periods of the main counter, that can be set
+    /// in periodic mode without losing interrupts.
+    #[must_use]
+    pub fn minimum_clock_tick(&self) -> u16 {
+        self.sdt.read(53).unwrap()
+    }
+
+    /// Page protection and OEM attributes.
+    #[must_use]
+    pub fn page_protection(&self) -> u8 {
+        self.sdt.read(55).unwrap()
+    }
+}
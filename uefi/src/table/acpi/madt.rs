@@ -0,0 +1,406 @@
+use super::{AcpiError, AcpiTable, Sdt};
+use crate::util::read_at;
+use bitflags::bitflags;
+
+/// Offset of the first interrupt controller structure.
+const ENTRIES_OFFSET: usize = 44;
+
+/// Multiple APIC Description Table (`APIC`).
+///
+/// Describes the interrupt controllers of the platform, and thereby also
+/// its processors.
+#[derive(Clone, Copy, Debug)]
+pub struct Madt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Madt<'a> {
+    const SIGNATURE: [u8; 4] = *b"APIC";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        let sdt = sdt.expect::<Self>(ENTRIES_OFFSET)?;
+
+        // Check that every entry lies within the table, so that iteration
+        // can't fail.
+        let mut data = &sdt.as_bytes()[ENTRIES_OFFSET..];
+        while !data.is_empty() {
+            let (_, rest) = MadtEntry::parse(data).ok_or(AcpiError::InvalidLength)?;
+            if data[1] < entry_min_length(data[0]) {
+                return Err(AcpiError::InvalidLength);
+            }
+            data = rest;
+        }
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> Madt<'a> {
+    /// Get the underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Physical address of the local APIC, taking into account any
+    /// [`MadtEntry::LocalApicAddressOverride`].
+    #[must_use]
+    pub fn local_apic_address(&self) -> u64 {
+        self.entries()
+            .find_map(|entry| match entry {
+                MadtEntry::LocalApicAddressOverride(address) => Some(address),
+                _ => None,
+            })
+            .unwrap_or_else(|| u64::from(self.sdt.read::<u32>(36).unwrap()))
+    }
+
+    /// Multiple APIC flags.
+    #[must_use]
+    pub fn flags(&self) -> MadtFlags {
+        MadtFlags::from_bits_retain(self.sdt.read(40).unwrap())
+    }
+
+    /// Get an iterator over the interrupt controller structures.
+    #[must_use]
+    pub fn entries(&self) -> MadtEntryIter<'a> {
+        MadtEntryIter {
+            data: &self.sdt.as_bytes()[ENTRIES_OFFSET..],
+        }
+    }
+
+    /// Number of enabled processors, counting local APIC, local x2APIC and
+    /// GIC CPU interface structures.
+    #[must_use]
+    pub fn enabled_processor_count(&self) -> usize {
+        self.entries()
+            .filter(|entry| match entry {
+                MadtEntry::LocalApic(apic) => apic.flags.contains(LocalApicFlags::ENABLED),
+                MadtEntry::LocalX2Apic(apic) => apic.flags.contains(LocalApicFlags::ENABLED),
+                MadtEntry::Gicc(gicc) => gicc.flags.contains(GiccFlags::ENABLED),
+                _ => false,
+            })
+            .count()
+    }
+}
+
+/// Iterator over the entries of a [`Madt`].
+#[derive(Clone, Debug)]
+pub struct MadtEntryIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for MadtEntryIter<'a> {
+    type Item = MadtEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (entry, rest) = MadtEntry::parse(self.data)?;
+        self.data = rest;
+        Some(entry)
+    }
+}
+
+/// Minimum length of the known entry types. Unknown types only need the
+/// two-byte entry header.
+const fn entry_min_length(entry_type: u8) -> u8 {
+    match entry_type {
+        0 => 8,
+        1 | 5 => 12,
+        2 => 10,
+        9 => 16,
+        0xb => 76,
+        _ => 2,
+    }
+}
+
+/// An interrupt controller structure in the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MadtEntry<'a> {
+    /// Processor local APIC (type 0).
+    LocalApic(LocalApic),
+
+    /// I/O APIC (type 1).
+    IoApic(IoApic),
+
+    /// Interrupt source override (type 2).
+    InterruptSourceOverride(InterruptSourceOverride),
+
+    /// 64-bit address of the local APIC, overriding the address in the
+    /// table header (type 5).
+    LocalApicAddressOverride(u64),
+
+    /// Processor local x2APIC (type 9).
+    LocalX2Apic(LocalX2Apic),
+
+    /// GIC CPU interface (type 0xB).
+    Gicc(Gicc),
+
+    /// Any other type of structure.
+    Other {
+        /// Type of the structure.
+        entry_type: u8,
+        /// Contents of the structure, including the two-byte header.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> MadtEntry<'a> {
+    /// Parse the entry at the start of `data`, returning it along with the
+    /// remaining data. Returns `None` if `data` is empty or the entry is
+    /// truncated.
+    fn parse(data: &'a [u8]) -> Option<(Self, &'a [u8])> {
+        let entry_type = *data.first()?;
+        let length = usize::from(*data.get(1)?);
+        if length < 2 || length > data.len() {
+            return None;
+        }
+        let (data, rest) = data.split_at(length);
+        if length < usize::from(entry_min_length(entry_type)) {
+            return Some((Self::Other { entry_type, data }, rest));
+        }
+
+        let u8_at = |offset| read_at::<u8>(data, offset).unwrap();
+        let u16_at = |offset| read_at::<u16>(data, offset).unwrap();
+        let u32_at = |offset| read_at::<u32>(data, offset).unwrap();
+        let u64_at = |offset| read_at::<u64>(data, offset).unwrap();
+        let entry = match entry_type {
+            0 => Self::LocalApic(LocalApic {
+                processor_uid: u8_at(2),
+                apic_id: u8_at(3),
+                flags: LocalApicFlags::from_bits_retain(u32_at(4)),
+            }),
+            1 => Self::IoApic(IoApic {
+                io_apic_id: u8_at(2),
+                address: u32_at(4),
+                global_system_interrupt_base: u32_at(8),
+            }),
+            2 => Self::InterruptSourceOverride(InterruptSourceOverride {
+                bus: u8_at(2),
+                source: u8_at(3),
+                global_system_interrupt: u32_at(4),
+                flags: u16_at(8),
+            }),
+            5 => Self::LocalApicAddressOverride(u64_at(4)),
+            9 => Self::LocalX2Apic(LocalX2Apic {
+                x2apic_id: u32_at(4),
+                flags: LocalApicFlags::from_bits_retain(u32_at(8)),
+                processor_uid: u32_at(12),
+            }),
+            0xb => Self::Gicc(Gicc {
+                cpu_interface_number: u32_at(4),
+                processor_uid: u32_at(8),
+                flags: GiccFlags::from_bits_retain(u32_at(12)),
+                physical_base_address: u64_at(32),
+                gicr_base_address: u64_at(60),
+                mpidr: u64_at(68),
+            }),
+            _ => Self::Other { entry_type, data },
+        };
+        Some((entry, rest))
+    }
+}
+
+/// Processor local APIC structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LocalApic {
+    /// ACPI processor UID.
+    pub processor_uid: u8,
+    /// Local APIC ID of the processor.
+    pub apic_id: u8,
+    /// Processor flags.
+    pub flags: LocalApicFlags,
+}
+
+/// Processor local x2APIC structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LocalX2Apic {
+    /// Local x2APIC ID of the processor.
+    pub x2apic_id: u32,
+    /// Processor flags.
+    pub flags: LocalApicFlags,
+    /// ACPI processor UID.
+    pub processor_uid: u32,
+}
+
+/// I/O APIC structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IoApic {
+    /// I/O APIC ID.
+    pub io_apic_id: u8,
+    /// Physical address of the I/O APIC registers.
+    pub address: u32,
+    /// First global system interrupt handled by this I/O APIC.
+    pub global_system_interrupt_base: u32,
+}
+
+/// Interrupt source override structure of the [`Madt`], mapping an ISA
+/// interrupt to a global system interrupt.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InterruptSourceOverride {
+    /// Bus of the interrupt source. Always 0 (ISA).
+    pub bus: u8,
+    /// Bus-relative interrupt source (IRQ).
+    pub source: u8,
+    /// Global system interrupt that the source signals.
+    pub global_system_interrupt: u32,
+    /// MPS INTI flags, describing polarity and trigger mode.
+    pub flags: u16,
+}
+
+/// GIC CPU interface structure of the [`Madt`], describing a processor on
+/// ARM platforms.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Gicc {
+    /// GIC CPU interface number.
+    pub cpu_interface_number: u32,
+    /// ACPI processor UID.
+    pub processor_uid: u32,
+    /// Processor flags.
+    pub flags: GiccFlags,
+    /// Physical address of the GIC CPU interface registers.
+    pub physical_base_address: u64,
+    /// Physical address of the GIC redistributor, if any.
+    pub gicr_base_address: u64,
+    /// Affinity fields of the processor's `MPIDR` register.
+    pub mpidr: u64,
+}
+
+bitflags! {
+    /// Flags of the [`Madt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct MadtFlags: u32 {
+        /// The system has dual 8259 PICs that must be disabled before the
+        /// APICs are used.
+        const PCAT_COMPAT = 1 << 0;
+    }
+}
+
+bitflags! {
+    /// Flags of [`LocalApic`] and [`LocalX2Apic`] structures.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct LocalApicFlags: u32 {
+        /// The processor is ready for use.
+        const ENABLED = 1 << 0;
+        /// The processor is disabled but can be enabled at runtime.
+        const ONLINE_CAPABLE = 1 << 1;
+    }
+}
+
+bitflags! {
+    /// Flags of [`Gicc`] structures.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct GiccFlags: u32 {
+        /// The processor is ready for use.
+        const ENABLED = 1 << 0;
+        /// The performance interrupt is edge-triggered.
+        const PERFORMANCE_INTERRUPT_EDGE = 1 << 1;
+        /// The VGIC maintenance interrupt is edge-triggered.
+        const VGIC_MAINTENANCE_INTERRUPT_EDGE = 1 << 2;
+        /// The processor is disabled but can be enabled at runtime.
+        const ONLINE_CAPABLE = 1 << 3;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::make_sdt;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_madt() {
+        let mut body = Vec::new();
+        body.extend(0xfee0_0000u32.to_le_bytes());
+        body.extend(1u32.to_le_bytes());
+        // Two local APICs, one disabled.
+        body.extend([0, 8, 0, 0, 1, 0, 0, 0]);
+        body.extend([0, 8, 1, 1, 0, 0, 0, 0]);
+        // I/O APIC.
+        body.extend([1, 12, 2, 0]);
+        body.extend(0xfec0_0000u32.to_le_bytes());
+        body.extend(0u32.to_le_bytes());
+        // IRQ 0 -> GSI 2.
+        body.extend([2, 10, 0, 0, 2, 0, 0, 0, 0, 0]);
+        // Local x2APIC.
+        body.extend([9, 16, 0, 0]);
+        body.extend(0x100u32.to_le_bytes());
+        body.extend(1u32.to_le_bytes());
+        body.extend(7u32.to_le_bytes());
+        // Unknown type.
+        body.extend([0x7f, 3, 0xaa]);
+        let data = make_sdt(b"APIC", 5, &body);
+
+        let madt = Madt::from_sdt(Sdt::new(&data).unwrap()).unwrap();
+        assert_eq!(madt.local_apic_address(), 0xfee0_0000);
+        assert_eq!(madt.flags(), MadtFlags::PCAT_COMPAT);
+        assert_eq!(madt.enabled_processor_count(), 2);
+
+        let entries: Vec<_> = madt.entries().collect();
+        assert_eq!(entries.len(), 6);
+        assert_eq!(
+            entries[1],
+            MadtEntry::LocalApic(LocalApic {
+                processor_uid: 1,
+                apic_id: 1,
+                flags: LocalApicFlags::empty(),
+            })
+        );
+        assert_eq!(
+            entries[2],
+            MadtEntry::IoApic(IoApic {
+                io_apic_id: 2,
+                address: 0xfec0_0000,
+                global_system_interrupt_base: 0,
+            })
+        );
+        assert_eq!(
+            entries[3],
+            MadtEntry::InterruptSourceOverride(InterruptSourceOverride {
+                bus: 0,
+                source: 0,
+                global_system_interrupt: 2,
+                flags: 0,
+            })
+        );
+        assert_eq!(
+            entries[4],
+            MadtEntry::LocalX2Apic(LocalX2Apic {
+                x2apic_id: 0x100,
+                flags: LocalApicFlags::ENABLED,
+                processor_uid: 7,
+            })
+        );
+        assert_eq!(
+            entries[5],
+            MadtEntry::Other {
+                entry_type: 0x7f,
+                data: &[0x7f, 3, 0xaa]
+            }
+        );
+    }
+
+    #[test]
+    fn test_madt_invalid() {
+        let mut body = [0; 8].to_vec();
+        // Entry extends past the end of the table.
+        body.extend([0, 8, 0, 0]);
+        let data = make_sdt(b"APIC", 5, &body);
+        let sdt = Sdt::new(&data).unwrap();
+        assert_eq!(Madt::from_sdt(sdt).unwrap_err(), AcpiError::InvalidLength);
+
+        // Local APIC entry that is too short.
+        let mut body = [0; 8].to_vec();
+        body.extend([0, 4, 0, 0]);
+        let data = make_sdt(b"APIC", 5, &body);
+        let sdt = Sdt::new(&data).unwrap();
+        assert_eq!(Madt::from_sdt(sdt).unwrap_err(), AcpiError::InvalidLength);
+
+        let data = make_sdt(b"APIX", 5, &[0; 8]);
+        let sdt = Sdt::new(&data).unwrap();
+        assert_eq!(
+            Madt::from_sdt(sdt).unwrap_err(),
+            AcpiError::InvalidSignature
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use super::{AcpiError, AcpiTable, Sdt};
+use crate::util::read_at;
+
+/// Offset of the first configuration space entry.
+const ENTRIES_OFFSET: usize = 44;
+
+/// Size of each configuration space entry.
+const ENTRY_SIZE: usize = 16;
+
+/// PCI Express memory mapped configuration space table (`MCFG`).
+#[derive(Clone, Copy, Debug)]
+pub struct Mcfg<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Mcfg<'a> {
+    const SIGNATURE: [u8; 4] = *b"MCFG";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        let sdt = sdt.expect::<Self>(ENTRIES_OFFSET)?;
+        if (sdt.as_bytes().len() - ENTRIES_OFFSET) % ENTRY_SIZE != 0 {
+            return Err(AcpiError::InvalidLength);
+        }
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> Mcfg<'a> {
+    /// Get the underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Get an iterator over the configuration space entries, one for each
+    /// range of buses of each PCI segment group.
+    pub fn entries(&self) -> impl Iterator<Item = McfgEntry> + 'a {
+        self.sdt.as_bytes()[ENTRIES_OFFSET..]
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| McfgEntry {
+                base_address: read_at(entry, 0).unwrap(),
+                segment_group: read_at(entry, 8).unwrap(),
+                start_bus: entry[10],
+                end_bus: entry[11],
+            })
+    }
+
+    /// Find the entry that covers `bus` of `segment_group`.
+    #[must_use]
+    pub fn find(&self, segment_group: u16, bus: u8) -> Option<McfgEntry> {
+        self.entries().find(|entry| {
+            entry.segment_group == segment_group && (entry.start_bus..=entry.end_bus).contains(&bus)
+        })
+    }
+}
+
+/// Memory mapped configuration space of a range of PCI buses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct McfgEntry {
+    /// Physical address of the configuration space. This corresponds to
+    /// bus 0, even if [`start_bus`] is not 0.
+    ///
+    /// [`start_bus`]: Self::start_bus
+    pub base_address: u64,
+    /// PCI segment group number.
+    pub segment_group: u16,
+    /// First bus covered by this entry.
+    pub start_bus: u8,
+    /// Last bus covered by this entry.
+    pub end_bus: u8,
+}
+
+impl McfgEntry {
+    /// Physical address of the configuration space of a function, or `None`
+    /// if the bus is not covered by this entry or the device or function
+    /// number is out of range.
+    #[must_use]
+    pub fn config_address(&self, bus: u8, device: u8, function: u8) -> Option<u64> {
+        if !(self.start_bus..=self.end_bus).contains(&bus) || device >= 32 || function >= 8 {
+            return None;
+        }
+        let offset =
+            (u64::from(bus) << 20) | (u64::from(device) << 15) | (u64::from(function) << 12);
+        Some(self.base_address + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::make_sdt;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_mcfg() {
+        let mut body = [0; 8].to_vec();
+        body.extend(0xb000_0000u64.to_le_bytes());
+        body.extend([0, 0, 0, 0xff, 0, 0, 0, 0]);
+        body.extend(0xc000_0000u64.to_le_bytes());
+        body.extend([1, 0, 0x10, 0x1f, 0, 0, 0, 0]);
+        let data = make_sdt(b"MCFG", 1, &body);
+
+        let mcfg = Mcfg::from_sdt(Sdt::new(&data).unwrap()).unwrap();
+        let entries: Vec<_> = mcfg.entries().collect();
+        assert_eq!(
+            entries,
+            [
+                McfgEntry {
+                    base_address: 0xb000_0000,
+                    segment_group: 0,
+                    start_bus: 0,
+                    end_bus: 0xff,
+                },
+                McfgEntry {
+                    base_address: 0xc000_0000,
+                    segment_group: 1,
+                    start_bus: 0x10,
+                    end_bus: 0x1f,
+                }
+            ]
+        );
+
+        let entry = mcfg.find(1, 0x12).unwrap();
+        assert_eq!(entry.config_address(0x12, 3, 1), Some(0xc121_9000));
+        assert_eq!(entry.config_address(0x20, 0, 0), None);
+        assert_eq!(entry.config_address(0x12, 32, 0), None);
+        assert!(mcfg.find(1, 0x20).is_none());
+
+        let data = make_sdt(b"MCFG", 1, &body[..20]);
+        let sdt = Sdt::new(&data).unwrap();
+        assert_eq!(Mcfg::from_sdt(sdt).unwrap_err(), AcpiError::InvalidLength);
+    }
+}
@@ -0,0 +1,752 @@
+//! ACPI table parsing.
+//!
+//! The firmware publishes the ACPI tables through the [`ACPI2_GUID`]
+//! configuration table entry (or [`ACPI_GUID`] on very old firmware), which
+//! points to the Root System Description Pointer ([`Rsdp`]). The RSDP
+//! points to the XSDT (or RSDT), which lists the addresses of all other
+//! tables.
+//!
+//! [`AcpiTables`] validates the signature, length and checksum of each of
+//! these structures before they are accessed. Individual tables are
+//! returned as an [`Sdt`], and typed views are provided for some common
+//! tables:
+//!
+//! * [`Fadt`]: Fixed ACPI Description Table
+//! * [`Madt`]: Multiple APIC Description Table
+//! * [`Mcfg`]: PCI Express memory mapped configuration space
+//! * [`Hpet`]: High Precision Event Timer
+//! * [`Bgrt`]: Boot Graphics Resource Table
+//! * [`Spcr`]: Serial Port Console Redirection table
+//!
+//! This module only parses the static tables; it does not interpret AML.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::acpi::{AcpiError, AcpiTables, Madt};
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn cpu_count(st: &SystemTable<Boot>) -> Result<usize, AcpiError> {
+//!     let tables = AcpiTables::from_system_table(st)?;
+//!     let madt = tables.get::<Madt>()?;
+//!     Ok(madt.enabled_processor_count())
+//! }
+//! ```
+//!
+//! [`ACPI_GUID`]: super::cfg::ACPI_GUID
+//! [`ACPI2_GUID`]: super::cfg::ACPI2_GUID
+
+mod bgrt;
+mod fadt;
+mod hpet;
+mod madt;
+mod mcfg;
+mod spcr;
+
+pub use bgrt::{Bgrt, BgrtImageType};
+pub use fadt::{ArmBootArchFlags, Fadt, FadtFlags, IaPcBootArchFlags, PmProfile};
+pub use hpet::Hpet;
+pub use madt::{
+    Gicc, GiccFlags, InterruptSourceOverride, IoApic, LocalApic, LocalApicFlags, LocalX2Apic, Madt,
+    MadtEntry, MadtEntryIter, MadtFlags,
+};
+pub use mcfg::{Mcfg, McfgEntry};
+pub use spcr::{SerialInterfaceType, Spcr, SpcrFlowControl, SpcrInterruptType};
+
+use super::cfg::{self, ConfigTable};
+use super::{Boot, SystemTable};
+use crate::util::{checksum_is_valid, read_at, Plain};
+use crate::Guid;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::{mem, slice};
+
+/// Errors returned when parsing ACPI tables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AcpiError {
+    /// The configuration table does not contain an RSDP.
+    RsdpNotFound,
+
+    /// No valid table with the given signature was found.
+    TableNotFound([u8; 4]),
+
+    /// A table has an unexpected signature.
+    InvalidSignature,
+
+    /// A table's checksum is incorrect.
+    InvalidChecksum,
+
+    /// A table's length is too small for its contents, or inconsistent
+    /// with the size of its entries.
+    InvalidLength,
+
+    /// A table address is null or cannot be represented as a pointer.
+    InvalidAddress,
+}
+
+impl Display for AcpiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RsdpNotFound => write!(f, "ACPI RSDP not found"),
+            Self::TableNotFound(signature) => {
+                write!(f, "ACPI table {} not found", signature.escape_ascii())
+            }
+            Self::InvalidSignature => write!(f, "invalid ACPI table signature"),
+            Self::InvalidChecksum => write!(f, "invalid ACPI table checksum"),
+            Self::InvalidLength => write!(f, "invalid ACPI table length"),
+            Self::InvalidAddress => write!(f, "invalid ACPI table address"),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl core::error::Error for AcpiError {}
+
+/// Root System Description Pointer, revision 1.
+///
+/// This is the structure pointed to by the [`ACPI_GUID`] configuration
+/// table entry. It only contains the address of the RSDT.
+///
+/// [`ACPI_GUID`]: cfg::ACPI_GUID
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed)]
+pub struct RsdpV1 {
+    /// Must be [`Rsdp::SIGNATURE`].
+    pub signature: [u8; 8],
+    /// Makes the bytes of this structure sum to zero.
+    pub checksum: u8,
+    /// OEM identifier.
+    pub oem_id: [u8; 6],
+    /// Revision of the structure. Zero for ACPI 1.0.
+    pub revision: u8,
+    /// Physical address of the RSDT.
+    pub rsdt_address: u32,
+}
+
+impl RsdpV1 {
+    /// Check the signature and checksum of the structure at `ptr`.
+    unsafe fn validate<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let table = &*ptr.cast::<Self>();
+        let bytes = slice::from_raw_parts(ptr.cast::<u8>(), mem::size_of::<Self>());
+        (table.signature == Rsdp::SIGNATURE && checksum_is_valid(bytes)).then_some(table)
+    }
+}
+
+unsafe impl ConfigTable for RsdpV1 {
+    const GUID: Guid = cfg::ACPI_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        Self::validate(ptr)
+    }
+}
+
+/// Root System Description Pointer, revision 2 or later.
+///
+/// This is the structure pointed to by the [`ACPI2_GUID`] configuration
+/// table entry. It extends [`RsdpV1`] with the address of the XSDT.
+///
+/// [`ACPI2_GUID`]: cfg::ACPI2_GUID
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed)]
+pub struct Rsdp {
+    /// Must be [`Rsdp::SIGNATURE`].
+    pub signature: [u8; 8],
+    /// Makes the first 20 bytes of this structure sum to zero.
+    pub checksum: u8,
+    /// OEM identifier.
+    pub oem_id: [u8; 6],
+    /// Revision of the structure. At least 2.
+    pub revision: u8,
+    /// Physical address of the RSDT.
+    pub rsdt_address: u32,
+    /// Length of the whole structure in bytes.
+    pub length: u32,
+    /// Physical address of the XSDT.
+    pub xsdt_address: u64,
+    /// Makes the bytes of the whole structure sum to zero.
+    pub extended_checksum: u8,
+    /// Reserved.
+    pub reserved: [u8; 3],
+}
+
+impl Rsdp {
+    /// Signature at the start of the RSDP.
+    pub const SIGNATURE: [u8; 8] = *b"RSD PTR ";
+}
+
+unsafe impl ConfigTable for Rsdp {
+    const GUID: Guid = cfg::ACPI2_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        // Only the first 20 bytes are valid until the revision is checked.
+        let v1 = RsdpV1::validate(ptr)?;
+        if v1.revision < 2 {
+            return None;
+        }
+        let table = &*ptr.cast::<Self>();
+        let length = table.length as usize;
+        if length < mem::size_of::<Self>() {
+            return None;
+        }
+        let bytes = slice::from_raw_parts(ptr.cast::<u8>(), length);
+        checksum_is_valid(bytes).then_some(table)
+    }
+}
+
+/// Header at the start of every System Description Table.
+///
+/// Corresponds to the C type `EFI_ACPI_DESCRIPTION_HEADER`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct SdtHeader {
+    /// Four-character table signature, e.g. `b"APIC"`.
+    pub signature: [u8; 4],
+    /// Length of the table in bytes, including the header.
+    pub length: u32,
+    /// Revision of the table format.
+    pub revision: u8,
+    /// Makes the bytes of the whole table sum to zero.
+    pub checksum: u8,
+    /// OEM identifier.
+    pub oem_id: [u8; 6],
+    /// OEM table identifier.
+    pub oem_table_id: [u8; 8],
+    /// OEM revision of the table.
+    pub oem_revision: u32,
+    /// Vendor ID of the tool that created the table.
+    pub creator_id: [u8; 4],
+    /// Revision of the tool that created the table.
+    pub creator_revision: u32,
+}
+
+unsafe impl Plain for SdtHeader {}
+
+/// A validated System Description Table.
+///
+/// The length and checksum in the header have been checked, so the table
+/// contents can be accessed safely.
+#[derive(Clone, Copy)]
+pub struct Sdt<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Sdt<'a> {
+    /// Size of [`SdtHeader`].
+    pub const HEADER_SIZE: usize = mem::size_of::<SdtHeader>();
+
+    /// Validate the table at the start of `data`.
+    ///
+    /// Any bytes beyond the length in the table header are ignored.
+    pub fn new(data: &'a [u8]) -> Result<Self, AcpiError> {
+        let length = read_at::<u32>(data, 4).ok_or(AcpiError::InvalidLength)? as usize;
+        if length < Self::HEADER_SIZE || length > data.len() {
+            return Err(AcpiError::InvalidLength);
+        }
+        let data = &data[..length];
+        if !checksum_is_valid(data) {
+            return Err(AcpiError::InvalidChecksum);
+        }
+        Ok(Self { data })
+    }
+
+    /// Validate the table at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a readable table header, and the number of bytes
+    /// given by its length field must be readable for the lifetime `'a`.
+    pub unsafe fn from_ptr(ptr: *const u8) -> Result<Self, AcpiError> {
+        let length = ptr.add(4).cast::<u32>().read_unaligned() as usize;
+        if length < Self::HEADER_SIZE {
+            return Err(AcpiError::InvalidLength);
+        }
+        Self::new(slice::from_raw_parts(ptr, length))
+    }
+
+    /// Validate the table at the physical address `address`.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`from_ptr`], with physical memory identity mapped.
+    ///
+    /// [`from_ptr`]: Self::from_ptr
+    unsafe fn from_address(address: u64) -> Result<Self, AcpiError> {
+        match usize::try_from(address) {
+            Ok(0) | Err(_) => Err(AcpiError::InvalidAddress),
+            Ok(address) => Self::from_ptr(address as *const u8),
+        }
+    }
+
+    /// Get the table header.
+    #[must_use]
+    pub fn header(&self) -> SdtHeader {
+        // OK to unwrap: the length was checked in `new`.
+        self.read(0).unwrap()
+    }
+
+    /// Get the table signature.
+    #[must_use]
+    pub fn signature(&self) -> [u8; 4] {
+        self.header().signature
+    }
+
+    /// Get the table revision.
+    #[must_use]
+    pub fn revision(&self) -> u8 {
+        self.header().revision
+    }
+
+    /// Get the bytes of the whole table, including the header.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Get the bytes of the table following the header.
+    #[must_use]
+    pub fn body(&self) -> &'a [u8] {
+        &self.data[Self::HEADER_SIZE..]
+    }
+
+    /// Check that the table has the signature of `T` and is at least
+    /// `min_length` bytes long.
+    fn expect<T: AcpiTable<'a>>(self, min_length: usize) -> Result<Self, AcpiError> {
+        if self.signature() != T::SIGNATURE {
+            Err(AcpiError::InvalidSignature)
+        } else if self.data.len() < min_length {
+            Err(AcpiError::InvalidLength)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Read a `T` at `offset` from the start of the table, or `None` if the
+    /// table is too short.
+    fn read<T: Plain>(&self, offset: usize) -> Option<T> {
+        read_at(self.data, offset)
+    }
+}
+
+impl Debug for Sdt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sdt")
+            .field("header", &self.header())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A typed view of an ACPI table.
+pub trait AcpiTable<'a>: Sized {
+    /// Signature of the table.
+    const SIGNATURE: [u8; 4];
+
+    /// Create the view, checking that the table has the right signature and
+    /// that its contents are consistent.
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError>;
+}
+
+/// Generic Address Structure, describing the location of a register.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C, packed)]
+pub struct GenericAddress {
+    /// Address space of the register.
+    pub address_space: AddressSpace,
+    /// Size of the register in bits.
+    pub register_bit_width: u8,
+    /// Bit offset of the register at the given address.
+    pub register_bit_offset: u8,
+    /// Access size: 0 for undefined, otherwise 1, 2, 3 or 4 for byte, word,
+    /// dword or qword access.
+    pub access_size: u8,
+    /// Address of the register in the address space.
+    pub address: u64,
+}
+
+unsafe impl Plain for GenericAddress {}
+
+newtype_enum! {
+/// Address space of a [`GenericAddress`].
+pub enum AddressSpace: u8 => {
+    /// System memory.
+    SYSTEM_MEMORY = 0x00,
+    /// System I/O ports.
+    SYSTEM_IO = 0x01,
+    /// PCI configuration space.
+    PCI_CONFIG = 0x02,
+    /// Embedded controller.
+    EMBEDDED_CONTROLLER = 0x03,
+    /// SMBus.
+    SMBUS = 0x04,
+    /// System CMOS.
+    SYSTEM_CMOS = 0x05,
+    /// PCI BAR target.
+    PCI_BAR_TARGET = 0x06,
+    /// IPMI.
+    IPMI = 0x07,
+    /// General purpose I/O.
+    GENERAL_PURPOSE_IO = 0x08,
+    /// Generic serial bus.
+    GENERIC_SERIAL_BUS = 0x09,
+    /// Platform Communications Channel.
+    PLATFORM_COMMUNICATIONS_CHANNEL = 0x0a,
+    /// Functional fixed hardware.
+    FUNCTIONAL_FIXED_HARDWARE = 0x7f,
+}}
+
+unsafe impl Plain for AddressSpace {}
+
+/// Validated root of the ACPI tables.
+///
+/// This wraps the XSDT, or the RSDT if there is no XSDT.
+#[derive(Clone, Copy, Debug)]
+pub struct AcpiTables<'a> {
+    root: Sdt<'a>,
+    entry_size: usize,
+}
+
+impl<'a> AcpiTables<'a> {
+    /// Find and validate the ACPI tables in the configuration table.
+    ///
+    /// The ACPI 2.0 RSDP is preferred, falling back to the ACPI 1.0 RSDP.
+    pub fn from_system_table(st: &'a SystemTable<Boot>) -> Result<Self, AcpiError> {
+        // SAFETY: while boot services are active, physical memory is
+        // identity mapped, and the firmware keeps the ACPI tables in memory
+        // that is not reused.
+        unsafe {
+            if let Some(rsdp) = st.find_config_table::<Rsdp>() {
+                Self::from_rsdp(rsdp)
+            } else if let Some(rsdp) = st.find_config_table::<RsdpV1>() {
+                Self::from_rsdp_v1(rsdp)
+            } else {
+                Err(AcpiError::RsdpNotFound)
+            }
+        }
+    }
+
+    /// Validate the XSDT pointed to by `rsdp`, falling back to the RSDT if
+    /// there is no XSDT.
+    ///
+    /// # Safety
+    ///
+    /// Physical memory must be identity mapped, and all tables reachable
+    /// from `rsdp` must remain valid for the lifetime `'a`.
+    pub unsafe fn from_rsdp(rsdp: &'a Rsdp) -> Result<Self, AcpiError> {
+        if rsdp.xsdt_address != 0 {
+            Self::from_root(rsdp.xsdt_address, *b"XSDT", mem::size_of::<u64>())
+        } else {
+            Self::from_root(
+                u64::from(rsdp.rsdt_address),
+                *b"RSDT",
+                mem::size_of::<u32>(),
+            )
+        }
+    }
+
+    /// Validate the RSDT pointed to by `rsdp`.
+    ///
+    /// # Safety
+    ///
+    /// Physical memory must be identity mapped, and all tables reachable
+    /// from `rsdp` must remain valid for the lifetime `'a`.
+    pub unsafe fn from_rsdp_v1(rsdp: &'a RsdpV1) -> Result<Self, AcpiError> {
+        Self::from_root(
+            u64::from(rsdp.rsdt_address),
+            *b"RSDT",
+            mem::size_of::<u32>(),
+        )
+    }
+
+    unsafe fn from_root(
+        address: u64,
+        signature: [u8; 4],
+        entry_size: usize,
+    ) -> Result<Self, AcpiError> {
+        let root = Sdt::from_address(address)?;
+        if root.signature() != signature {
+            return Err(AcpiError::InvalidSignature);
+        }
+        if root.body().len() % entry_size != 0 {
+            return Err(AcpiError::InvalidLength);
+        }
+        Ok(Self { root, entry_size })
+    }
+
+    /// Get the root table, either the XSDT or the RSDT.
+    #[must_use]
+    pub const fn root(&self) -> Sdt<'a> {
+        self.root
+    }
+
+    /// Get an iterator over the tables listed in the root table.
+    ///
+    /// Each table is validated as it is reached; tables that fail
+    /// validation are returned as errors.
+    #[must_use]
+    pub fn iter(&self) -> AcpiTableIter<'a> {
+        AcpiTableIter {
+            entries: self.root.body().chunks_exact(self.entry_size),
+        }
+    }
+
+    /// Get an iterator over all valid tables with the given signature.
+    ///
+    /// Some tables, such as `SSDT`, may appear more than once.
+    pub fn find_all(&self, signature: [u8; 4]) -> impl Iterator<Item = Sdt<'a>> {
+        self.iter()
+            .filter_map(Result::ok)
+            .filter(move |sdt| sdt.signature() == signature)
+    }
+
+    /// Find the first valid table with the given signature.
+    pub fn find(&self, signature: [u8; 4]) -> Result<Sdt<'a>, AcpiError> {
+        self.find_all(signature)
+            .next()
+            .ok_or(AcpiError::TableNotFound(signature))
+    }
+
+    /// Find a table and create a typed view of it.
+    pub fn get<T: AcpiTable<'a>>(&self) -> Result<T, AcpiError> {
+        T::from_sdt(self.find(T::SIGNATURE)?)
+    }
+
+    /// Get the Differentiated System Description Table, which is not listed
+    /// in the root table but referenced by the [`Fadt`].
+    pub fn dsdt(&self) -> Result<Sdt<'a>, AcpiError> {
+        let fadt = self.get::<Fadt>()?;
+        // SAFETY: the tables are valid according to the requirements of the
+        // constructor.
+        let dsdt = unsafe { Sdt::from_address(fadt.dsdt_address()) }?;
+        if dsdt.signature() == *b"DSDT" {
+            Ok(dsdt)
+        } else {
+            Err(AcpiError::InvalidSignature)
+        }
+    }
+}
+
+impl<'a> IntoIterator for &AcpiTables<'a> {
+    type Item = Result<Sdt<'a>, AcpiError>;
+    type IntoIter = AcpiTableIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the tables listed in the XSDT or RSDT.
+#[derive(Clone, Debug)]
+pub struct AcpiTableIter<'a> {
+    entries: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for AcpiTableIter<'a> {
+    type Item = Result<Sdt<'a>, AcpiError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+        let address = match *entry {
+            [a, b, c, d] => u64::from(u32::from_le_bytes([a, b, c, d])),
+            _ => u64::from_le_bytes(entry.try_into().unwrap()),
+        };
+        // SAFETY: the tables are valid according to the requirements of
+        // the `AcpiTables` constructor.
+        Some(unsafe { Sdt::from_address(address) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Build a table with a valid header and checksum.
+    pub(crate) fn make_sdt(signature: &[u8; 4], revision: u8, body: &[u8]) -> Vec<u8> {
+        let length = u32::try_from(Sdt::HEADER_SIZE + body.len()).unwrap();
+        let mut v = Vec::new();
+        v.extend(signature);
+        v.extend(length.to_le_bytes());
+        v.push(revision);
+        v.push(0);
+        v.extend(b"UEFIRS");
+        v.extend(b"TESTTABL");
+        v.extend(1u32.to_le_bytes());
+        v.extend(b"RUST");
+        v.extend(2u32.to_le_bytes());
+        v.extend(body);
+        fix_checksum(&mut v, 9);
+        v
+    }
+
+    fn fix_checksum(data: &mut [u8], index: usize) {
+        data[index] = 0;
+        let sum = data.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        data[index] = sum.wrapping_neg();
+    }
+
+    fn make_rsdp(rsdt: u32, xsdt: u64) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend(Rsdp::SIGNATURE);
+        v.push(0);
+        v.extend(b"UEFIRS");
+        v.push(2);
+        v.extend(rsdt.to_le_bytes());
+        v.extend(36u32.to_le_bytes());
+        v.extend(xsdt.to_le_bytes());
+        v.extend([0; 4]);
+        fix_checksum(&mut v[..20], 8);
+        fix_checksum(&mut v, 32);
+        v
+    }
+
+    fn make_fadt(dsdt: u64) -> Vec<u8> {
+        let mut body = [0; 276 - 36];
+        let body_offset = |offset: usize| offset - Sdt::HEADER_SIZE;
+        body[body_offset(45)] = 2;
+        body[body_offset(46)..][..2].copy_from_slice(&9u16.to_le_bytes());
+        body[body_offset(112)..][..4].copy_from_slice(&((1u32 << 20) | (1 << 10)).to_le_bytes());
+        body[body_offset(116)] = 1;
+        body[body_offset(120)..][..8].copy_from_slice(&0xcf9u64.to_le_bytes());
+        body[body_offset(128)] = 6;
+        body[body_offset(131)] = 3;
+        body[body_offset(140)..][..8].copy_from_slice(&dsdt.to_le_bytes());
+        make_sdt(b"FACP", 6, &body)
+    }
+
+    fn addr(table: &[u8]) -> u64 {
+        table.as_ptr() as u64
+    }
+
+    #[test]
+    fn test_sdt_header() {
+        assert_eq!(Sdt::HEADER_SIZE, 36);
+        assert_eq!(mem::size_of::<GenericAddress>(), 12);
+        assert_eq!(mem::size_of::<Rsdp>(), 36);
+        assert_eq!(mem::size_of::<RsdpV1>(), 20);
+
+        let mut data = make_sdt(b"TEST", 3, &[1, 2, 3]);
+        data.extend([0xff; 4]);
+        let sdt = Sdt::new(&data).unwrap();
+        assert_eq!(sdt.signature(), *b"TEST");
+        assert_eq!(sdt.revision(), 3);
+        assert_eq!(sdt.body(), [1, 2, 3]);
+        assert_eq!(sdt.as_bytes().len(), 39);
+        let header = sdt.header();
+        assert_eq!(header.oem_id, *b"UEFIRS");
+        assert_eq!(header.creator_id, *b"RUST");
+        assert_eq!(header.creator_revision, 2);
+
+        data[37] ^= 1;
+        assert_eq!(Sdt::new(&data).unwrap_err(), AcpiError::InvalidChecksum);
+        assert_eq!(Sdt::new(&data[..38]).unwrap_err(), AcpiError::InvalidLength);
+        assert_eq!(Sdt::new(&data[..4]).unwrap_err(), AcpiError::InvalidLength);
+    }
+
+    #[test]
+    fn test_rsdp() {
+        let rsdp = make_rsdp(0x1234, 0x5678);
+        let ptr = rsdp.as_ptr().cast();
+        let v2 = unsafe { Rsdp::from_ptr(ptr) }.unwrap();
+        assert_eq!({ v2.xsdt_address }, 0x5678);
+        let v1 = unsafe { RsdpV1::from_ptr(ptr) }.unwrap();
+        assert_eq!({ v1.rsdt_address }, 0x1234);
+
+        // Only the extended checksum is wrong.
+        let mut bad = rsdp.clone();
+        bad[33] = 1;
+        assert!(unsafe { Rsdp::from_ptr(bad.as_ptr().cast()) }.is_none());
+        assert!(unsafe { RsdpV1::from_ptr(bad.as_ptr().cast()) }.is_some());
+
+        // Revision 0 has no extended fields.
+        let mut old = rsdp[..20].to_vec();
+        old[15] = 0;
+        fix_checksum(&mut old, 8);
+        assert!(unsafe { Rsdp::from_ptr(old.as_ptr().cast()) }.is_none());
+        assert!(unsafe { RsdpV1::from_ptr(old.as_ptr().cast()) }.is_some());
+    }
+
+    #[test]
+    fn test_acpi_tables() {
+        let dsdt = make_sdt(b"DSDT", 2, &[0x10; 8]);
+        let fadt = make_fadt(addr(&dsdt));
+        let ssdt1 = make_sdt(b"SSDT", 2, &[1]);
+        let ssdt2 = make_sdt(b"SSDT", 2, &[2]);
+        let mut broken = make_sdt(b"BAD!", 1, &[]);
+        broken[9] ^= 1;
+
+        let mut entries = Vec::new();
+        for table in [&fadt, &ssdt1, &broken, &ssdt2] {
+            entries.extend(addr(table).to_le_bytes());
+        }
+        let xsdt = make_sdt(b"XSDT", 1, &entries);
+        let rsdp = make_rsdp(0, addr(&xsdt));
+
+        let rsdp = unsafe { Rsdp::from_ptr(rsdp.as_ptr().cast()) }.unwrap();
+        let tables = unsafe { AcpiTables::from_rsdp(rsdp) }.unwrap();
+        assert_eq!(tables.root().signature(), *b"XSDT");
+
+        let results: Vec<_> = tables.iter().collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[2].unwrap_err(), AcpiError::InvalidChecksum);
+
+        assert_eq!(tables.find(*b"SSDT").unwrap().body(), [1]);
+        assert_eq!(tables.find_all(*b"SSDT").count(), 2);
+        assert_eq!(
+            tables.find(*b"APIC").unwrap_err(),
+            AcpiError::TableNotFound(*b"APIC")
+        );
+        assert_eq!(
+            tables.get::<Madt>().unwrap_err(),
+            AcpiError::TableNotFound(*b"APIC")
+        );
+
+        let fadt = tables.get::<Fadt>().unwrap();
+        assert_eq!(fadt.preferred_pm_profile(), PmProfile::MOBILE);
+        assert_eq!(fadt.sci_interrupt(), 9);
+        assert!(fadt.is_hardware_reduced());
+        let reset = fadt.reset_register().unwrap();
+        assert_eq!(reset.address_space, AddressSpace::SYSTEM_IO);
+        assert_eq!({ reset.address }, 0xcf9);
+        assert_eq!(fadt.reset_value(), Some(6));
+        assert_eq!(fadt.minor_version(), Some(3));
+        assert_eq!(tables.dsdt().unwrap().body(), [0x10; 8]);
+    }
+
+    #[test]
+    fn test_acpi_tables_rsdt() {
+        let ssdt = make_sdt(b"SSDT", 2, &[]);
+        let entry = u32::try_from(addr(&ssdt)).unwrap_or(0);
+        let rsdt = make_sdt(b"RSDT", 1, &entry.to_le_bytes());
+        let Ok(rsdt_address) = u32::try_from(addr(&rsdt)) else {
+            // The heap is above 4 GiB, so the RSDT can't be referenced.
+            return;
+        };
+        let rsdp = make_rsdp(rsdt_address, 0);
+        let rsdp = unsafe { RsdpV1::from_ptr(rsdp.as_ptr().cast()) }.unwrap();
+        let tables = unsafe { AcpiTables::from_rsdp_v1(rsdp) }.unwrap();
+        assert_eq!(tables.root().signature(), *b"RSDT");
+        assert_eq!(tables.iter().count(), 1);
+        assert!(tables.find(*b"SSDT").is_ok());
+    }
+
+    #[test]
+    fn test_acpi_tables_invalid_root() {
+        let xsdt = make_sdt(b"XSDT", 1, &[0; 12]);
+        let rsdp = make_rsdp(0, addr(&xsdt));
+        let rsdp = unsafe { Rsdp::from_ptr(rsdp.as_ptr().cast()) }.unwrap();
+        assert_eq!(
+            unsafe { AcpiTables::from_rsdp(rsdp) }.unwrap_err(),
+            AcpiError::InvalidLength
+        );
+
+        let rsdp = make_rsdp(0, 0);
+        let rsdp = unsafe { Rsdp::from_ptr(rsdp.as_ptr().cast()) }.unwrap();
+        assert_eq!(
+            unsafe { AcpiTables::from_rsdp(rsdp) }.unwrap_err(),
+            AcpiError::InvalidAddress
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use super::{AcpiError, AcpiTable, GenericAddress, Sdt};
+use bitflags::bitflags;
+
+/// Length of revision 2 of the table, which all later revisions extend.
+const MIN_LENGTH: usize = 80;
+
+/// Serial Port Console Redirection table (`SPCR`).
+///
+/// Describes the serial port used by the firmware for console redirection,
+/// so that the OS can keep using it.
+#[derive(Clone, Copy, Debug)]
+pub struct Spcr<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Spcr<'a> {
+    const SIGNATURE: [u8; 4] = *b"SPCR";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        let sdt = sdt.expect::<Self>(MIN_LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> Spcr<'a> {
+    /// Get the underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Type of the serial port hardware.
+    #[must_use]
+    pub fn interface_type(&self) -> SerialInterfaceType {
+        SerialInterfaceType(self.sdt.read(36).unwrap())
+    }
+
+    /// Location of the serial port registers.
+    #[must_use]
+    pub fn base_address(&self) -> GenericAddress {
+        self.sdt.read(40).unwrap()
+    }
+
+    /// Interrupt controllers that the serial port interrupt is routed to.
+    #[must_use]
+    pub fn interrupt_type(&self) -> SpcrInterruptType {
+        SpcrInterruptType::from_bits_retain(self.sdt.read(52).unwrap())
+    }
+
+    /// PC-AT compatible IRQ of the serial port.
+    #[must_use]
+    pub fn irq(&self) -> u8 {
+        self.sdt.read(53).unwrap()
+    }
+
+    /// Global system interrupt of the serial port.
+    #[must_use]
+    pub fn global_system_interrupt(&self) -> u32 {
+        self.sdt.read(54).unwrap()
+    }
+
+    /// Baud rate used by the firmware, or `None` if the port is used
+    /// with whatever rate it is already configured for.
+    #[must_use]
+    pub fn baud_rate(&self) -> Option<u32> {
+        match self.sdt.read::<u8>(58).unwrap() {
+            3 => Some(9600),
+            4 => Some(19200),
+            6 => Some(57600),
+            7 => Some(115_200),
+            _ => None,
+        }
+    }
+
+    /// Parity setting. Zero means no parity.
+    #[must_use]
+    pub fn parity(&self) -> u8 {
+        self.sdt.read(59).unwrap()
+    }
+
+    /// Stop bits setting. One means one stop bit.
+    #[must_use]
+    pub fn stop_bits(&self) -> u8 {
+        self.sdt.read(60).unwrap()
+    }
+
+    /// Flow control settings.
+    #[must_use]
+    pub fn flow_control(&self) -> SpcrFlowControl {
+        SpcrFlowControl::from_bits_retain(self.sdt.read(61).unwrap())
+    }
+
+    /// Terminal type: 0 for VT100, 1 for extended VT100, 2 for VT-UTF8 and
+    /// 3 for ANSI.
+    #[must_use]
+    pub fn terminal_type(&self) -> u8 {
+        self.sdt.read(62).unwrap()
+    }
+}
+
+newtype_enum! {
+/// Serial port interface type, as reported by [`Spcr`].
+///
+/// These are the port subtypes of the Debug Port Table 2 (DBG2).
+pub enum SerialInterfaceType: u8 => {
+    /// Fully 16550-compatible.
+    FULL_16550 = 0x00,
+    /// Fully 16450-compatible.
+    FULL_16450 = 0x01,
+    /// ARM PL011 UART.
+    ARM_PL011 = 0x03,
+    /// ARM SBSA generic UART, restricted to 32-bit accesses.
+    ARM_SBSA_32BIT = 0x0d,
+    /// ARM SBSA generic UART.
+    ARM_SBSA = 0x0e,
+    /// ARM Debug Communications Channel.
+    ARM_DCC = 0x0f,
+    /// Broadcom BCM2835 mini UART.
+    BCM2835 = 0x10,
+    /// 16550-compatible, with the register layout described by the
+    /// [`GenericAddress`].
+    GENERIC_16550 = 0x12,
+}}
+
+bitflags! {
+    /// Interrupt controllers that the [`Spcr`] serial port interrupt is
+    /// routed to.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct SpcrInterruptType: u8 {
+        /// Dual 8259 PIC, using [`Spcr::irq`].
+        const PC_AT_8259 = 1 << 0;
+        /// I/O APIC, using [`Spcr::global_system_interrupt`].
+        const IO_APIC = 1 << 1;
+        /// I/O SAPIC, using [`Spcr::global_system_interrupt`].
+        const IO_SAPIC = 1 << 2;
+        /// ARM GIC, using [`Spcr::global_system_interrupt`].
+        const ARMH_GIC = 1 << 3;
+        /// RISC-V PLIC, using [`Spcr::global_system_interrupt`].
+        const RISCV_PLIC = 1 << 4;
+    }
+}
+
+bitflags! {
+    /// Flow control settings of the [`Spcr`] serial port.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct SpcrFlowControl: u8 {
+        /// DCD is required for transmit.
+        const DCD = 1 << 0;
+        /// RTS/CTS hardware flow control.
+        const RTS_CTS = 1 << 1;
+        /// XON/XOFF software flow control.
+        const XON_XOFF = 1 << 2;
+    }
+}
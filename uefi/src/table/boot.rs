@@ -7,17 +7,23 @@ use crate::proto::loaded_image::LoadedImage;
 use crate::proto::media::fs::SimpleFileSystem;
 use crate::proto::{Protocol, ProtocolPointer};
 use crate::util::opt_nonnull_to_ptr;
-use crate::{Char16, Error, Event, Guid, Handle, Result, Status, StatusExt};
-use core::cell::UnsafeCell;
+use crate::{guid, Char16, Error, Event, Guid, Handle, Result, ResultExt, Status, StatusExt};
+use core::cell::{Cell, UnsafeCell};
 use core::ffi::c_void;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ops::{Deref, DerefMut};
 use core::ptr::NonNull;
 use core::sync::atomic::{AtomicPtr, Ordering};
+use core::time::Duration;
 use core::{ptr, slice};
 
 #[cfg(feature = "alloc")]
-use alloc::vec::Vec;
+use {
+    alloc::{boxed::Box, vec::Vec},
+    core::pin::Pin,
+};
 
 pub use uefi_raw::table::boot::{
     EventType, InterfaceType, MemoryAttribute, MemoryDescriptor, MemoryType, Tpl,
@@ -516,8 +522,18 @@ impl BootServices {
     /// To be safe, ensure that error codes are handled properly.
     ///
     /// * [`uefi::Status::INVALID_PARAMETER`]
+    ///
+    /// If the event was created with [`create_event_with_callback`], its
+    /// callback is freed.
+    ///
+    /// [`create_event_with_callback`]: Self::create_event_with_callback
     pub fn close_event(&self, event: Event) -> Result {
-        unsafe { (self.0.close_event)(event.as_ptr()).to_result() }
+        let status = unsafe { (self.0.close_event)(event.as_ptr()) };
+        #[cfg(feature = "alloc")]
+        if status.is_success() {
+            unsafe { remove_event_callback(self, event.as_ptr()) };
+        }
+        status.to_result()
     }
 
     /// Checks to see if an event is signaled, without blocking execution to wait for it.
@@ -1217,6 +1233,40 @@ impl BootServices {
         }
     }
 
+    /// Close a protocol interface that was opened with [`open_protocol`]
+    /// without keeping the returned [`ScopedProtocol`], for example by a
+    /// driver that keeps a protocol open from its `start` function until
+    /// its `stop` function.
+    ///
+    /// # Safety
+    ///
+    /// There must be no [`ScopedProtocol`] left for the opened interface,
+    /// and no other references to it.
+    ///
+    /// # Errors
+    ///
+    /// See section `EFI_BOOT_SERVICES.CloseProtocol()` in the UEFI Specification for more details.
+    ///
+    /// * [`uefi::Status::INVALID_PARAMETER`]
+    /// * [`uefi::Status::NOT_FOUND`]
+    ///
+    /// [`open_protocol`]: BootServices::open_protocol
+    pub unsafe fn close_protocol(
+        &self,
+        handle: Handle,
+        protocol: &Guid,
+        agent: Handle,
+        controller: Option<Handle>,
+    ) -> Result {
+        (self.0.close_protocol)(
+            handle.as_ptr(),
+            protocol,
+            agent.as_ptr(),
+            Handle::opt_to_ptr(controller),
+        )
+        .to_result()
+    }
+
     /// Test whether a handle supports a protocol.
     ///
     /// # Errors
@@ -1282,6 +1332,46 @@ impl BootServices {
         })
     }
 
+    /// Get the list of agents that currently have the `protocol` interface on
+    /// `handle` open, along with the attributes they opened it with.
+    ///
+    /// This is how the driver model tracks which driver manages a controller
+    /// (entries opened [`ByDriver`]) and which child controllers a bus driver
+    /// has created (entries opened [`ByChildController`]).
+    ///
+    /// # Errors
+    ///
+    /// See section `EFI_BOOT_SERVICES.OpenProtocolInformation()` in the UEFI Specification for more details.
+    ///
+    /// * [`uefi::Status::NOT_FOUND`]
+    /// * [`uefi::Status::OUT_OF_RESOURCES`]
+    ///
+    /// [`ByDriver`]: OpenProtocolAttributes::ByDriver
+    /// [`ByChildController`]: OpenProtocolAttributes::ByChildController
+    pub fn open_protocol_information(
+        &self,
+        handle: Handle,
+        protocol: &Guid,
+    ) -> Result<OpenProtocolInformation<'_>> {
+        let mut entries = ptr::null();
+        let mut count = 0;
+
+        let mut status = unsafe {
+            (self.0.open_protocol_information)(handle.as_ptr(), protocol, &mut entries, &mut count)
+        };
+
+        // An empty list may be returned without a buffer.
+        if !status.is_error() && entries.is_null() && count != 0 {
+            status = Status::OUT_OF_RESOURCES;
+        }
+
+        status.to_result_with_val(|| OpenProtocolInformation {
+            boot_services: self,
+            entries: entries.cast::<OpenProtocolInformationEntry>(),
+            count,
+        })
+    }
+
     /// Returns an array of handles that support the requested protocol in a buffer allocated from
     /// pool.
     ///
@@ -1348,6 +1438,213 @@ impl BootServices {
 
 #[cfg(feature = "alloc")]
 impl BootServices {
+    /// Creates an event whose notification function is the closure
+    /// `callback`.
+    ///
+    /// This is a safe alternative to [`create_event`]. The closure is
+    /// stored with the event, and freed when the event is closed with
+    /// [`close_event`]. It receives the notified event as its argument.
+    ///
+    /// `event_ty` must contain exactly one of [`EventType::NOTIFY_WAIT`] and
+    /// [`EventType::NOTIFY_SIGNAL`], and may contain [`EventType::TIMER`].
+    /// Other event types, such as [`EventType::SIGNAL_EXIT_BOOT_SERVICES`],
+    /// have callbacks that run after boot services have been exited, and
+    /// so can only be created with [`create_event`].
+    ///
+    /// The closure runs at `notify_tpl`, which must be [`Tpl::CALLBACK`] or
+    /// [`Tpl::NOTIFY`]. Boot services that are restricted to lower task
+    /// priority levels, such as [`wait_for_event`], must not be used in the
+    /// closure.
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::INVALID_PARAMETER`]: `event_ty` or `notify_tpl`
+    ///   doesn't meet the constraints above.
+    ///
+    /// See section `EFI_BOOT_SERVICES.CreateEvent()` in the UEFI Specification
+    /// for other errors.
+    ///
+    /// [`create_event`]: Self::create_event
+    /// [`close_event`]: Self::close_event
+    /// [`wait_for_event`]: Self::wait_for_event
+    pub fn create_event_with_callback(
+        &self,
+        event_ty: EventType,
+        notify_tpl: Tpl,
+        callback: Box<dyn FnMut(Event)>,
+    ) -> Result<Event> {
+        let notify = event_ty & (EventType::NOTIFY_WAIT | EventType::NOTIFY_SIGNAL);
+        let valid = (EventType::TIMER | notify).contains(event_ty)
+            && (notify == EventType::NOTIFY_WAIT || notify == EventType::NOTIFY_SIGNAL);
+        if !valid {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        self.create_callback_event(event_ty, notify_tpl, None, callback)
+    }
+
+    /// Creates an event in the event `group`, whose notification function
+    /// is the closure `callback`.
+    ///
+    /// The closure is called when any event of the group is signaled, and
+    /// receives the notified event as its argument. The event is closed,
+    /// and the closure freed, when the returned [`GroupEvent`] is dropped.
+    /// Use [`GroupEvent::leak`] to keep the event for the rest of the
+    /// lifetime of the image.
+    ///
+    /// The closure runs at `notify_tpl`, with the same constraints as in
+    /// [`create_event_with_callback`].
+    ///
+    /// The closures of [`EventGroup::EXIT_BOOT_SERVICES`] and
+    /// [`EventGroup::VIRTUAL_ADDRESS_CHANGE`] run while boot services are
+    /// being exited, or after they have been exited. Events in these groups
+    /// can only be created with [`create_group_event_unchecked`].
+    ///
+    /// This operation is only supported starting with UEFI 2.0.
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::INVALID_PARAMETER`]: `group` is one of the groups
+    ///   above, or `notify_tpl` is invalid.
+    /// * [`uefi::Status::UNSUPPORTED`]: the firmware doesn't support event
+    ///   groups.
+    ///
+    /// See section `EFI_BOOT_SERVICES.CreateEventEx()` in the UEFI
+    /// Specification for other errors.
+    ///
+    /// [`create_event_with_callback`]: Self::create_event_with_callback
+    /// [`create_group_event_unchecked`]: Self::create_group_event_unchecked
+    pub fn create_group_event(
+        &self,
+        group: EventGroup,
+        notify_tpl: Tpl,
+        callback: Box<dyn FnMut(Event)>,
+    ) -> Result<GroupEvent<'_>> {
+        if group == EventGroup::EXIT_BOOT_SERVICES || group == EventGroup::VIRTUAL_ADDRESS_CHANGE {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        self.create_callback_event(
+            EventType::NOTIFY_SIGNAL,
+            notify_tpl,
+            Some(&group.0),
+            callback,
+        )
+        .map(|event| GroupEvent {
+            boot_services: self,
+            event: Some(event),
+            callback: None,
+        })
+    }
+
+    /// Creates an event in the event `group`, whose notification function
+    /// is the closure `callback`, without checking `group`.
+    ///
+    /// This is the same as [`create_group_event`], but also allows
+    /// [`EventGroup::EXIT_BOOT_SERVICES`] and
+    /// [`EventGroup::VIRTUAL_ADDRESS_CHANGE`]. The notification function of
+    /// the event only calls the closure, without using boot services, so
+    /// that it can run after boot services have been exited.
+    ///
+    /// # Safety
+    ///
+    /// If the closure may run while boot services are being exited, it must
+    /// not use memory allocation services, directly or indirectly, and must
+    /// not depend on timer events. If it may run after boot services have
+    /// been exited, it must only use runtime services, and it and the data
+    /// it uses must be in runtime memory, such as the memory of a runtime
+    /// driver. The closure is freed with the global allocator when the
+    /// [`GroupEvent`] is dropped, so an event whose closure may run after
+    /// boot services have been exited must be leaked with
+    /// [`GroupEvent::leak`].
+    ///
+    /// The closure must not close its own event.
+    ///
+    /// [`create_group_event`]: Self::create_group_event
+    pub unsafe fn create_group_event_unchecked(
+        &self,
+        group: EventGroup,
+        notify_tpl: Tpl,
+        callback: Box<dyn FnMut(Event)>,
+    ) -> Result<GroupEvent<'_>> {
+        if notify_tpl != Tpl::CALLBACK && notify_tpl != Tpl::NOTIFY {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        let callback = Box::into_raw(Box::new(callback));
+        let event = self.create_event_ex(
+            EventType::NOTIFY_SIGNAL,
+            notify_tpl,
+            Some(call_group_callback),
+            NonNull::new(callback.cast()),
+            Some(NonNull::from(&group.0)),
+        );
+        match event {
+            Ok(event) => Ok(GroupEvent {
+                boot_services: self,
+                event: Some(event),
+                callback: NonNull::new(callback),
+            }),
+            Err(err) => {
+                drop(Box::from_raw(callback));
+                Err(err)
+            }
+        }
+    }
+
+    /// Creates an event with an [`EventCallback`], optionally in an event
+    /// `group`.
+    fn create_callback_event(
+        &self,
+        event_ty: EventType,
+        notify_tpl: Tpl,
+        group: Option<&Guid>,
+        callback: Box<dyn FnMut(Event)>,
+    ) -> Result<Event> {
+        if notify_tpl != Tpl::CALLBACK && notify_tpl != Tpl::NOTIFY {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        let node = Box::into_raw(Box::new(EventCallback {
+            event: ptr::null_mut(),
+            boot_services: self,
+            callback,
+            running: false,
+            closed: false,
+            next: ptr::null_mut(),
+        }));
+        let event = unsafe {
+            match group {
+                Some(group) => self.create_event_ex(
+                    event_ty,
+                    notify_tpl,
+                    Some(call_event_callback),
+                    NonNull::new(node.cast()),
+                    Some(NonNull::from(group)),
+                ),
+                None => self.create_event(
+                    event_ty,
+                    notify_tpl,
+                    Some(call_event_callback),
+                    NonNull::new(node.cast()),
+                ),
+            }
+        };
+        match event {
+            Ok(event) => {
+                unsafe {
+                    (*node).event = event.as_ptr();
+                    let _guard = self.raise_tpl(Tpl::HIGH_LEVEL);
+                    (*node).next = EVENT_CALLBACKS.load(Ordering::Acquire);
+                    EVENT_CALLBACKS.store(node, Ordering::Release);
+                }
+                Ok(event)
+            }
+            Err(err) => {
+                drop(unsafe { Box::from_raw(node) });
+                Err(err)
+            }
+        }
+    }
+
     /// Returns all the handles implementing a certain protocol.
     ///
     /// # Errors
@@ -1377,6 +1674,130 @@ impl BootServices {
         // Emit output, with warnings
         Ok(handles)
     }
+
+    /// Installs the Rust implementation `interface` of protocol `P` on
+    /// `handle`, or on a new handle if `handle` is `None`.
+    ///
+    /// The interface is uninstalled, and then freed, when the returned
+    /// [`InstalledProtocol`] is dropped. If it can't be uninstalled, for
+    /// example because a driver still has it open, it is leaked instead.
+    ///
+    /// Note that other code which opened the interface with
+    /// [`OpenProtocolAttributes::GetProtocol`] isn't notified when it is
+    /// uninstalled, and must not use it after that.
+    ///
+    /// # Errors
+    ///
+    /// See [`install_protocol_interface`].
+    ///
+    /// [`install_protocol_interface`]: Self::install_protocol_interface
+    pub fn install_protocol<P: Protocol + 'static>(
+        &self,
+        handle: Option<Handle>,
+        interface: Pin<Box<P>>,
+    ) -> Result<InstalledProtocol<'_, P>> {
+        let interface = Box::into_raw(unsafe { Pin::into_inner_unchecked(interface) });
+        match unsafe { self.install_protocol_interface(handle, &P::GUID, interface.cast()) } {
+            Ok(handle) => Ok(InstalledProtocol {
+                boot_services: self,
+                handle,
+                interface,
+            }),
+            Err(err) => {
+                drop(unsafe { Box::from_raw(interface) });
+                Err(err)
+            }
+        }
+    }
+
+    /// Installs all the protocol `interfaces` on `handle`, or on a new
+    /// handle if `handle` is `None`. Either all the interfaces are
+    /// installed, or none are.
+    ///
+    /// This is the equivalent of `InstallMultipleProtocolInterfaces`. The
+    /// interfaces are uninstalled, and then freed, when the returned
+    /// [`InstalledProtocols`] is dropped, as with [`install_protocol`].
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::INVALID_PARAMETER`]: `interfaces` is empty.
+    ///
+    /// See [`install_protocol_interface`] for other errors.
+    ///
+    /// [`install_protocol`]: Self::install_protocol
+    /// [`install_protocol_interface`]: Self::install_protocol_interface
+    pub fn install_protocols(
+        &self,
+        handle: Option<Handle>,
+        mut interfaces: ProtocolInterfaces,
+    ) -> Result<InstalledProtocols<'_>> {
+        if interfaces.0.is_empty() {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        // Like the firmware, delay notifications until all the interfaces
+        // are installed.
+        let _guard = unsafe { self.raise_tpl(Tpl::NOTIFY) };
+        let mut handle = handle;
+        for (i, interface) in interfaces.0.iter().enumerate() {
+            let result = unsafe {
+                self.install_protocol_interface(handle, &interface.protocol, interface.ptr)
+            };
+            match result {
+                Ok(new_handle) => handle = Some(new_handle),
+                Err(err) => {
+                    for interface in interfaces.0[..i].iter().rev() {
+                        // Ignore the result, nothing else can have opened
+                        // the interface yet.
+                        let _ = unsafe {
+                            self.uninstall_protocol_interface(
+                                handle.unwrap(),
+                                &interface.protocol,
+                                interface.ptr,
+                            )
+                        };
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(InstalledProtocols {
+            boot_services: self,
+            // OK to unwrap: at least one interface was installed.
+            handle: handle.unwrap(),
+            interfaces: mem::take(&mut interfaces.0),
+        })
+    }
+
+    /// Set the function that unloads the image, which is called when
+    /// [`unload_image`] is called with the image handle. This replaces the
+    /// handler that was set before.
+    ///
+    /// Only drivers can be unloaded. The handler must undo everything the
+    /// driver did that outlives its image, for example by disconnecting
+    /// the controllers that it manages and uninstalling its protocols with
+    /// [`DetachedProtocols::uninstall`]. If it returns an error, the image
+    /// isn't unloaded, and the handler is kept unless it set a new one.
+    ///
+    /// # Errors
+    ///
+    /// See [`open_protocol_exclusive`] for opening the [`LoadedImage`]
+    /// protocol of the image.
+    ///
+    /// [`unload_image`]: Self::unload_image
+    /// [`open_protocol_exclusive`]: Self::open_protocol_exclusive
+    pub fn set_unload_handler(&self, handler: Box<dyn FnMut(&BootServices) -> Result>) -> Result {
+        let mut loaded_image = self.open_protocol_exclusive::<LoadedImage>(self.image_handle())?;
+        let handler = Box::into_raw(Box::new(handler));
+        let old_handler = UNLOAD_HANDLER.swap(handler, Ordering::AcqRel);
+        if !old_handler.is_null() {
+            drop(unsafe { Box::from_raw(old_handler) });
+        }
+        // Safety: this is the loaded image of this image.
+        unsafe { loaded_image.set_unload(call_unload_handler) };
+        Ok(())
+    }
 }
 
 impl super::Table for BootServices {
@@ -1862,9 +2283,226 @@ impl<'guid> SearchType<'guid> {
     }
 }
 
+/// Event group, identified by a GUID.
+///
+/// When an event of a group is signaled, all the events of the group are
+/// signaled. The firmware signals the well-known groups defined here when
+/// the corresponding action happens.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct EventGroup(pub Guid);
+
+impl EventGroup {
+    /// Signaled when `ExitBootServices` is called, after
+    /// [`BEFORE_EXIT_BOOT_SERVICES`].
+    ///
+    /// [`BEFORE_EXIT_BOOT_SERVICES`]: Self::BEFORE_EXIT_BOOT_SERVICES
+    pub const EXIT_BOOT_SERVICES: Self = Self(guid!("27abf055-b1b8-4c26-8048-748f37baa2df"));
+
+    /// Signaled at the start of `ExitBootServices`, while boot services are
+    /// still fully available.
+    pub const BEFORE_EXIT_BOOT_SERVICES: Self = Self(guid!("8be0e274-3970-4b44-80c5-1ab9502f3bfc"));
+
+    /// Signaled when `SetVirtualAddressMap` is called, so that runtime
+    /// drivers can convert their pointers to virtual addresses.
+    pub const VIRTUAL_ADDRESS_CHANGE: Self = Self(guid!("13fa7698-c831-49c7-87ea-8f43fcc25196"));
+
+    /// Signaled when the memory map changes.
+    pub const MEMORY_MAP_CHANGE: Self = Self(guid!("78bee926-692f-48fd-9edb-01422ef0d7ab"));
+
+    /// Signaled when the boot manager is about to load and start a boot
+    /// option.
+    pub const READY_TO_BOOT: Self = Self(guid!("7ce88fb3-4bd7-4679-87a8-a8d8dee50d2b"));
+
+    /// Signaled when `ResetSystem` is called, before the system is reset.
+    pub const RESET_SYSTEM: Self = Self(guid!("62da6a56-13fb-485a-a8da-a3dd7912cb6b"));
+}
+
+/// Event in an [`EventGroup`], created by
+/// [`BootServices::create_group_event`] or
+/// [`BootServices::create_group_event_unchecked`].
+///
+/// The event is closed, and its closure freed, when this is dropped.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct GroupEvent<'a> {
+    boot_services: &'a BootServices,
+    /// The event; only `None` while dropping or leaking.
+    event: Option<Event>,
+    /// Closure of an event created with
+    /// [`BootServices::create_group_event_unchecked`], which must be freed
+    /// after the event is closed.
+    callback: Option<NonNull<GroupCallback>>,
+}
+
+#[cfg(feature = "alloc")]
+impl GroupEvent<'_> {
+    /// The event.
+    #[must_use]
+    pub fn event(&self) -> &Event {
+        // OK to unwrap: the event is only taken when `self` is consumed.
+        self.event.as_ref().unwrap()
+    }
+
+    /// Signal the event group, which calls the closures of all the events
+    /// in the group.
+    pub fn signal(&self) -> Result {
+        self.boot_services.signal_event(self.event())
+    }
+
+    /// Keep the event open for the rest of the lifetime of the image, and
+    /// return it.
+    #[must_use]
+    pub fn leak(mut self) -> Event {
+        self.callback = None;
+        // OK to unwrap: the event is only taken when `self` is consumed.
+        self.event.take().unwrap()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for GroupEvent<'_> {
+    fn drop(&mut self) {
+        if let Some(event) = self.event.take() {
+            // The closure must not be freed if the event is still open.
+            if self.boot_services.close_event(event).is_err() {
+                return;
+            }
+        }
+        if let Some(callback) = self.callback.take() {
+            drop(unsafe { Box::from_raw(callback.as_ptr()) });
+        }
+    }
+}
+
 /// Raw event notification function
 type EventNotifyFn = unsafe extern "efiapi" fn(event: Event, context: Option<NonNull<c_void>>);
 
+/// Closure of an event created with [`BootServices::create_event_with_callback`].
+///
+/// The closures of all open events are kept in a linked list, so that
+/// [`BootServices::close_event`] can free them. The list is only modified at
+/// [`Tpl::HIGH_LEVEL`], so that notification functions can't interrupt the
+/// modification.
+#[cfg(feature = "alloc")]
+struct EventCallback {
+    event: *mut c_void,
+    boot_services: *const BootServices,
+    callback: Box<dyn FnMut(Event)>,
+    /// The closure is currently running.
+    running: bool,
+    /// The event was closed while the closure was running, so the closure
+    /// must be freed when it returns.
+    closed: bool,
+    next: *mut EventCallback,
+}
+
+/// Head of the list of [`EventCallback`]s.
+#[cfg(feature = "alloc")]
+static EVENT_CALLBACKS: AtomicPtr<EventCallback> = AtomicPtr::new(ptr::null_mut());
+
+/// Closure of an event created with
+/// [`BootServices::create_group_event_unchecked`].
+#[cfg(feature = "alloc")]
+type GroupCallback = Box<dyn FnMut(Event)>;
+
+/// Handler set with [`BootServices::set_unload_handler`].
+#[cfg(feature = "alloc")]
+type UnloadHandler = Box<dyn FnMut(&BootServices) -> Result>;
+
+#[cfg(feature = "alloc")]
+static UNLOAD_HANDLER: AtomicPtr<UnloadHandler> = AtomicPtr::new(ptr::null_mut());
+
+/// Unload function of the image, which calls the [`UNLOAD_HANDLER`].
+#[cfg(feature = "alloc")]
+extern "efiapi" fn call_unload_handler(_image_handle: Handle) -> Status {
+    // Take the handler out while it runs, so that it isn't freed if it sets
+    // a new handler.
+    let handler = UNLOAD_HANDLER.swap(ptr::null_mut(), Ordering::AcqRel);
+    if handler.is_null() {
+        return Status::UNSUPPORTED;
+    }
+    let st = super::system_table_boot();
+    match unsafe { (*handler)(st.boot_services()) } {
+        Ok(()) => {
+            drop(unsafe { Box::from_raw(handler) });
+            Status::SUCCESS
+        }
+        Err(err) => {
+            // Put the handler back, unless it was replaced.
+            let restored = UNLOAD_HANDLER.compare_exchange(
+                ptr::null_mut(),
+                handler,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            );
+            if restored.is_err() {
+                drop(unsafe { Box::from_raw(handler) });
+            }
+            err.status()
+        }
+    }
+}
+
+/// Notification function of events created with
+/// [`BootServices::create_event_with_callback`]. The context is the event's
+/// [`EventCallback`].
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn call_event_callback(event: Event, context: Option<NonNull<c_void>>) {
+    let Some(node) = context else {
+        return;
+    };
+    let node = node.as_ptr().cast::<EventCallback>();
+
+    // The closure may close its own event, so the node must not be freed
+    // until the closure returns.
+    (*node).running = true;
+    ((*node).callback)(event);
+
+    let _guard = (*(*node).boot_services).raise_tpl(Tpl::HIGH_LEVEL);
+    (*node).running = false;
+    if (*node).closed {
+        drop(Box::from_raw(node));
+    }
+}
+
+/// Notification function of events created with
+/// [`BootServices::create_group_event_unchecked`]. The context is the
+/// event's [`GroupCallback`].
+///
+/// Unlike [`call_event_callback`], this doesn't use boot services, since it
+/// may run after they have been exited.
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn call_group_callback(event: Event, context: Option<NonNull<c_void>>) {
+    if let Some(callback) = context {
+        (*callback.as_ptr().cast::<GroupCallback>())(event);
+    }
+}
+
+/// Remove the [`EventCallback`] of the closed `event` from the list and free
+/// it, if there is one.
+#[cfg(feature = "alloc")]
+unsafe fn remove_event_callback(bt: &BootServices, event: *mut c_void) {
+    let _guard = bt.raise_tpl(Tpl::HIGH_LEVEL);
+
+    // Find the link pointing to the node of the event.
+    let mut link = EVENT_CALLBACKS.as_ptr();
+    while !(*link).is_null() && (**link).event != event {
+        link = ptr::addr_of_mut!((**link).next);
+    }
+    let node = *link;
+    if node.is_null() {
+        return;
+    }
+
+    *link = (*node).next;
+    if (*node).running {
+        (*node).closed = true;
+    } else {
+        drop(Box::from_raw(node));
+    }
+}
+
 /// Timer events manipulation.
 #[derive(Debug)]
 pub enum TimerTrigger {
@@ -1880,6 +2518,171 @@ pub enum TimerTrigger {
     Relative(u64),
 }
 
+/// Convert `duration` to timer ticks of 100ns, rounding up.
+pub(crate) fn timer_ticks(duration: Duration) -> u64 {
+    let ticks = (duration.as_nanos() + 99) / 100;
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+}
+
+impl TimerTrigger {
+    /// Trigger that signals the event every `period`, rounded up to 100ns.
+    #[must_use]
+    pub fn periodic(period: Duration) -> Self {
+        Self::Periodic(timer_ticks(period))
+    }
+
+    /// Trigger that signals the event once after `delay`, rounded up to
+    /// 100ns.
+    #[must_use]
+    pub fn relative(delay: Duration) -> Self {
+        Self::Relative(timer_ticks(delay))
+    }
+}
+
+/// Timer event, which is closed when dropped.
+///
+/// The timer is signaled when it expires, and its signaled state is cleared
+/// when it is checked or waited for. It can also be waited for together
+/// with other events by passing [`Timer::event`] to
+/// [`BootServices::wait_for_event`].
+#[derive(Debug)]
+pub struct Timer<'a> {
+    boot_services: &'a BootServices,
+    event: Event,
+}
+
+impl<'a> Timer<'a> {
+    /// Create a timer that isn't started.
+    pub fn new(bt: &'a BootServices) -> Result<Self> {
+        let event = unsafe { bt.create_event(EventType::TIMER, Tpl::CALLBACK, None, None) }?;
+        Ok(Self {
+            boot_services: bt,
+            event,
+        })
+    }
+
+    /// Create a timer that expires once after `delay`.
+    pub fn one_shot(bt: &'a BootServices, delay: Duration) -> Result<Self> {
+        let timer = Self::new(bt)?;
+        timer.start_one_shot(delay)?;
+        Ok(timer)
+    }
+
+    /// Create a timer that expires every `period`.
+    pub fn periodic(bt: &'a BootServices, period: Duration) -> Result<Self> {
+        let timer = Self::new(bt)?;
+        timer.start_periodic(period)?;
+        Ok(timer)
+    }
+
+    /// (Re)start the timer to expire once after `delay`.
+    pub fn start_one_shot(&self, delay: Duration) -> Result {
+        self.boot_services
+            .set_timer(&self.event, TimerTrigger::relative(delay))
+    }
+
+    /// (Re)start the timer to expire every `period`.
+    pub fn start_periodic(&self, period: Duration) -> Result {
+        self.boot_services
+            .set_timer(&self.event, TimerTrigger::periodic(period))
+    }
+
+    /// Stop the timer. This doesn't clear the signaled state if the timer
+    /// already expired.
+    pub fn cancel(&self) -> Result {
+        self.boot_services
+            .set_timer(&self.event, TimerTrigger::Cancel)
+    }
+
+    /// Returns true if the timer expired since it was last checked or
+    /// waited for.
+    pub fn check(&self) -> Result<bool> {
+        self.boot_services
+            .check_event(unsafe { self.event.unsafe_clone() })
+    }
+
+    /// Wait until the timer expires.
+    ///
+    /// This must be called at [`Tpl::APPLICATION`].
+    pub fn wait(&self) -> Result {
+        let mut events = [unsafe { self.event.unsafe_clone() }];
+        self.boot_services
+            .wait_for_event(&mut events)
+            .discard_errdata()
+            .map(|_| ())
+    }
+
+    /// The timer event.
+    #[must_use]
+    pub const fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl Drop for Timer<'_> {
+    fn drop(&mut self) {
+        // Ignore the result, we can't do anything about an error here.
+        let _ = self
+            .boot_services
+            .close_event(unsafe { self.event.unsafe_clone() });
+    }
+}
+
+/// Timeout for loops that poll for a condition.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::time::Duration;
+/// use uefi::table::boot::{BootServices, Deadline};
+/// use uefi::Status;
+///
+/// fn wait_until_ready(bt: &BootServices, is_ready: impl Fn() -> bool) -> uefi::Result {
+///     let deadline = Deadline::new(bt, Duration::from_secs(5))?;
+///     while !is_ready() {
+///         if deadline.has_expired() {
+///             return Err(Status::TIMEOUT.into());
+///         }
+///         bt.stall(100);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Deadline<'a> {
+    timer: Timer<'a>,
+    expired: Cell<bool>,
+}
+
+impl<'a> Deadline<'a> {
+    /// Create a deadline that expires after `timeout`.
+    pub fn new(bt: &'a BootServices, timeout: Duration) -> Result<Self> {
+        Ok(Self {
+            timer: Timer::one_shot(bt, timeout)?,
+            expired: Cell::new(false),
+        })
+    }
+
+    /// Returns true if the deadline has expired.
+    ///
+    /// Once the deadline has expired, this always returns true. If the
+    /// timer can't be checked, the deadline is treated as expired, so that
+    /// loops checking it terminate.
+    #[must_use]
+    pub fn has_expired(&self) -> bool {
+        if !self.expired.get() {
+            self.expired.set(self.timer.check().unwrap_or(true));
+        }
+        self.expired.get()
+    }
+
+    /// The timer event of the deadline, which is signaled when it expires.
+    #[must_use]
+    pub const fn event(&self) -> &Event {
+        self.timer.event()
+    }
+}
+
 /// Protocol interface [`Guids`][Guid] that are installed on a [`Handle`] as
 /// returned by [`BootServices::protocols_per_handle`].
 #[derive(Debug)]
@@ -1920,6 +2723,88 @@ impl<'a> ProtocolsPerHandle<'a> {
     }
 }
 
+/// Agents that have a protocol interface open, as returned by
+/// [`BootServices::open_protocol_information`].
+#[derive(Debug)]
+pub struct OpenProtocolInformation<'a> {
+    // The pointer returned by `open_protocol_information` has to be freed
+    // with `free_pool`, so keep a reference to boot services for that purpose.
+    boot_services: &'a BootServices,
+
+    entries: *const OpenProtocolInformationEntry,
+    count: usize,
+}
+
+impl<'a> Drop for OpenProtocolInformation<'a> {
+    fn drop(&mut self) {
+        if !self.entries.is_null() {
+            // Ignore the result, we can't do anything about an error here.
+            let _ = unsafe { self.boot_services.free_pool(self.entries as *mut u8) };
+        }
+    }
+}
+
+impl<'a> Deref for OpenProtocolInformation<'a> {
+    type Target = [OpenProtocolInformationEntry];
+
+    fn deref(&self) -> &Self::Target {
+        if self.entries.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.entries, self.count) }
+        }
+    }
+}
+
+/// Describes one agent that has a protocol interface open. Part of
+/// [`OpenProtocolInformation`].
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct OpenProtocolInformationEntry(uefi_raw::table::boot::OpenProtocolInformationEntry);
+
+impl OpenProtocolInformationEntry {
+    /// Handle of the image or driver that opened the protocol interface.
+    #[must_use]
+    pub fn agent(&self) -> Option<Handle> {
+        unsafe { Handle::from_ptr(self.0.agent_handle) }
+    }
+
+    /// Controller that the protocol interface was opened for, if any.
+    #[must_use]
+    pub fn controller(&self) -> Option<Handle> {
+        unsafe { Handle::from_ptr(self.0.controller_handle) }
+    }
+
+    /// Raw attributes the protocol interface was opened with. These are
+    /// combinations of the [`OpenProtocolAttributes`] values.
+    #[must_use]
+    pub const fn attributes(&self) -> u32 {
+        self.0.attributes
+    }
+
+    /// Number of times the agent has opened the protocol interface.
+    #[must_use]
+    pub const fn open_count(&self) -> u32 {
+        self.0.open_count
+    }
+
+    /// Whether the protocol interface was opened with
+    /// [`OpenProtocolAttributes::ByDriver`], i.e. the agent is a driver
+    /// that manages the controller.
+    #[must_use]
+    pub const fn is_by_driver(&self) -> bool {
+        self.0.attributes & OpenProtocolAttributes::ByDriver as u32 != 0
+    }
+
+    /// Whether the protocol interface was opened with
+    /// [`OpenProtocolAttributes::ByChildController`], i.e. the controller
+    /// is a child of the handle the protocol is installed on.
+    #[must_use]
+    pub const fn is_by_child_controller(&self) -> bool {
+        self.0.attributes & OpenProtocolAttributes::ByChildController as u32 != 0
+    }
+}
+
 /// A buffer that contains an array of [`Handles`][Handle] that support the
 /// requested protocol. Returned by [`BootServices::locate_handle_buffer`].
 #[derive(Debug)]
@@ -1964,13 +2849,498 @@ impl<'a> HandleBuffer<'a> {
 #[repr(transparent)]
 pub struct ProtocolSearchKey(NonNull<c_void>);
 
+/// Watcher for handles on which an interface of protocol `P` is installed,
+/// built on [`BootServices::register_protocol_notify`].
+///
+/// Each handle on which an interface of `P` is installed or reinstalled
+/// after the watcher was created is returned once, in the order the
+/// interfaces were installed. The notification is unregistered when the
+/// watcher is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::proto::media::block::BlockIO;
+/// use uefi::table::boot::{BootServices, ProtocolWatcher};
+/// use uefi::Handle;
+///
+/// fn connect(bt: &BootServices, controller: Handle) -> uefi::Result {
+///     let watcher = ProtocolWatcher::<BlockIO>::new(bt)?;
+///     bt.connect_controller(controller, None, None, true)?;
+///     for handle in watcher.handles() {
+///         // Use the new block device on `handle`.
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct ProtocolWatcher<'a, P: ProtocolPointer + ?Sized> {
+    boot_services: &'a BootServices,
+    event: Event,
+    key: ProtocolSearchKey,
+    _protocol: PhantomData<*const P>,
+}
+
+impl<'a, P: ProtocolPointer + ?Sized> ProtocolWatcher<'a, P> {
+    /// Start watching for new interfaces of `P`. Use [`next_handle`],
+    /// [`handles`] or [`wait`] to get the handles they were installed on.
+    ///
+    /// [`next_handle`]: Self::next_handle
+    /// [`handles`]: Self::handles
+    /// [`wait`]: Self::wait
+    pub fn new(bt: &'a BootServices) -> Result<Self> {
+        let event = unsafe { bt.create_event(EventType::empty(), Tpl::CALLBACK, None, None) }?;
+        Self::register(bt, event)
+    }
+
+    /// Start watching for new interfaces of `P`, calling `callback` with
+    /// each handle they are installed on.
+    ///
+    /// The closure runs at `notify_tpl`, with the same constraints as in
+    /// [`BootServices::create_event_with_callback`]. As the closure
+    /// receives the handles, [`next_handle`] and [`handles`] don't return
+    /// them, and [`wait`] can't be used.
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::INVALID_PARAMETER`]: `notify_tpl` is invalid.
+    ///
+    /// See [`BootServices::register_protocol_notify`] for other errors.
+    ///
+    /// [`next_handle`]: Self::next_handle
+    /// [`handles`]: Self::handles
+    /// [`wait`]: Self::wait
+    #[cfg(feature = "alloc")]
+    pub fn with_callback(
+        bt: &'a BootServices,
+        notify_tpl: Tpl,
+        mut callback: Box<dyn FnMut(Handle)>,
+    ) -> Result<Self> {
+        // The search key is only known once the event is registered, which
+        // is before the closure can be called.
+        let key = alloc::rc::Rc::new(Cell::new(None));
+        let callback_key = key.clone();
+        // The closure can't borrow `bt`, but boot services stay valid as
+        // long as the event can be signaled.
+        let boot_services: *const BootServices = bt;
+        let event = bt.create_callback_event(
+            EventType::NOTIFY_SIGNAL,
+            notify_tpl,
+            None,
+            Box::new(move |_event| {
+                let Some(key) = callback_key.get() else {
+                    return;
+                };
+                let bt = unsafe { &*boot_services };
+                while let Ok(Some(handle)) = next_notified_handle(bt, key) {
+                    callback(handle);
+                }
+            }),
+        )?;
+        let watcher = Self::register(bt, event)?;
+        key.set(Some(watcher.key));
+        Ok(watcher)
+    }
+
+    fn register(bt: &'a BootServices, event: Event) -> Result<Self> {
+        match bt.register_protocol_notify(&P::GUID, unsafe { event.unsafe_clone() }) {
+            Ok((event, SearchType::ByRegisterNotify(key))) => Ok(Self {
+                boot_services: bt,
+                event,
+                key,
+                _protocol: PhantomData,
+            }),
+            Ok(_) => unreachable!(),
+            Err(err) => {
+                let _ = bt.close_event(event);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the next handle on which an interface of `P` was installed,
+    /// or `None` if there is none yet.
+    ///
+    /// # Errors
+    ///
+    /// See [`BootServices::locate_handle`].
+    pub fn next_handle(&self) -> Result<Option<Handle>> {
+        next_notified_handle(self.boot_services, self.key)
+    }
+
+    /// Returns an iterator over the handles on which an interface of `P`
+    /// was installed since they were last returned. The iterator ends when
+    /// there are no more handles, and doesn't wait for new ones.
+    pub fn handles(&self) -> impl Iterator<Item = Handle> + '_ {
+        core::iter::from_fn(|| self.next_handle().ok().flatten())
+    }
+
+    /// Wait until an interface of `P` is installed, and return the handle
+    /// it was installed on. Handles installed before this call are
+    /// returned immediately.
+    ///
+    /// This must be called at [`Tpl::APPLICATION`].
+    ///
+    /// # Errors
+    ///
+    /// See [`BootServices::locate_handle`] and
+    /// [`BootServices::wait_for_event`].
+    pub fn wait(&self) -> Result<Handle> {
+        loop {
+            if let Some(handle) = self.next_handle()? {
+                return Ok(handle);
+            }
+            let mut events = [unsafe { self.event.unsafe_clone() }];
+            self.boot_services
+                .wait_for_event(&mut events)
+                .discard_errdata()?;
+        }
+    }
+
+    /// The event signaled when an interface of `P` is installed.
+    ///
+    /// The event can be waited for together with other events by passing
+    /// it to [`BootServices::wait_for_event`], unless the watcher was
+    /// created with [`with_callback`].
+    ///
+    /// [`with_callback`]: Self::with_callback
+    #[must_use]
+    pub const fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl<P: ProtocolPointer + ?Sized> Debug for ProtocolWatcher<'_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProtocolWatcher")
+            .field("protocol", &P::GUID)
+            .field("event", &self.event)
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl<P: ProtocolPointer + ?Sized> Drop for ProtocolWatcher<'_, P> {
+    fn drop(&mut self) {
+        // Closing the event also unregisters the notification. Ignore the
+        // result, we can't do anything about an error here.
+        let _ = self
+            .boot_services
+            .close_event(unsafe { self.event.unsafe_clone() });
+    }
+}
+
+/// Returns the next handle for the registration `key`, or `None` if there
+/// is none.
+fn next_notified_handle(bt: &BootServices, key: ProtocolSearchKey) -> Result<Option<Handle>> {
+    // Only one handle is returned for each call.
+    let mut handle = [MaybeUninit::uninit()];
+    match bt.locate_handle(SearchType::ByRegisterNotify(key), Some(&mut handle)) {
+        Ok(_) => Ok(Some(unsafe { handle[0].assume_init() })),
+        Err(err) if err.status() == Status::NOT_FOUND => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Protocol interface implemented in Rust and installed with
+/// [`BootServices::install_protocol`]. The interface is uninstalled when
+/// this is dropped.
+#[cfg(feature = "alloc")]
+pub struct InstalledProtocol<'a, P: Protocol + 'static> {
+    boot_services: &'a BootServices,
+    handle: Handle,
+    interface: *mut P,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, P: Protocol + 'static> InstalledProtocol<'a, P> {
+    /// The handle on which the interface is installed.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// The installed interface.
+    #[must_use]
+    pub fn interface(&self) -> Pin<&P> {
+        unsafe { Pin::new_unchecked(&*self.interface) }
+    }
+
+    /// Replaces the installed interface with `new_interface`, and returns
+    /// the old interface.
+    ///
+    /// Drivers that opened the old interface are stopped, and started again
+    /// with the new one.
+    ///
+    /// # Errors
+    ///
+    /// If the interface can't be replaced, `new_interface` is freed. See
+    /// [`BootServices::reinstall_protocol_interface`].
+    pub fn reinstall(&mut self, new_interface: Pin<Box<P>>) -> Result<Pin<Box<P>>> {
+        let new_interface = Box::into_raw(unsafe { Pin::into_inner_unchecked(new_interface) });
+        let result = unsafe {
+            self.boot_services.reinstall_protocol_interface(
+                self.handle,
+                &P::GUID,
+                self.interface.cast(),
+                new_interface.cast(),
+            )
+        };
+        let new_interface = unsafe { Box::from_raw(new_interface) };
+        match result {
+            Ok(()) => {
+                let old_interface = mem::replace(&mut self.interface, Box::into_raw(new_interface));
+                Ok(Box::into_pin(unsafe { Box::from_raw(old_interface) }))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Uninstalls the interface, and returns it.
+    ///
+    /// # Errors
+    ///
+    /// `self` is returned as error data if the interface can't be
+    /// uninstalled. See [`BootServices::uninstall_protocol_interface`].
+    pub fn uninstall(self) -> Result<Pin<Box<P>>, Self> {
+        let result = unsafe {
+            self.boot_services.uninstall_protocol_interface(
+                self.handle,
+                &P::GUID,
+                self.interface.cast(),
+            )
+        };
+        match result {
+            Ok(()) => {
+                let interface = unsafe { Box::from_raw(self.interface) };
+                mem::forget(self);
+                Ok(Box::into_pin(interface))
+            }
+            Err(err) => Err(Error::new(err.status(), self)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Protocol + 'static> Debug for InstalledProtocol<'_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstalledProtocol")
+            .field("protocol", &P::GUID)
+            .field("handle", &self.handle)
+            .field("interface", &self.interface)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Protocol + 'static> Drop for InstalledProtocol<'_, P> {
+    fn drop(&mut self) {
+        let result = unsafe {
+            self.boot_services.uninstall_protocol_interface(
+                self.handle,
+                &P::GUID,
+                self.interface.cast(),
+            )
+        };
+        // If the interface is still installed it must not be freed.
+        if result.is_ok() {
+            drop(unsafe { Box::from_raw(self.interface) });
+        }
+    }
+}
+
+/// Type-erased protocol interface implemented in Rust.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+struct ProtocolInterface {
+    protocol: Guid,
+    ptr: *mut c_void,
+    free: unsafe fn(*mut c_void),
+}
+
+#[cfg(feature = "alloc")]
+impl ProtocolInterface {
+    unsafe fn free(&self) {
+        (self.free)(self.ptr);
+    }
+}
+
+/// Frees an interface of type `P` created by [`ProtocolInterfaces::push`].
+#[cfg(feature = "alloc")]
+unsafe fn free_interface<P>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr.cast::<P>()));
+}
+
+/// List of protocol interfaces implemented in Rust, to install on a handle
+/// with [`BootServices::install_protocols`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+pub struct ProtocolInterfaces(Vec<ProtocolInterface>);
+
+#[cfg(feature = "alloc")]
+impl ProtocolInterfaces {
+    /// Create an empty list.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Add the implementation `interface` of protocol `P` to the list.
+    pub fn push<P: Protocol + 'static>(&mut self, interface: Pin<Box<P>>) {
+        let interface = Box::into_raw(unsafe { Pin::into_inner_unchecked(interface) });
+        self.0.push(ProtocolInterface {
+            protocol: P::GUID,
+            ptr: interface.cast(),
+            free: free_interface::<P>,
+        });
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for ProtocolInterfaces {
+    fn drop(&mut self) {
+        for interface in &self.0 {
+            unsafe { interface.free() };
+        }
+    }
+}
+
+/// Protocol interfaces implemented in Rust and installed with
+/// [`BootServices::install_protocols`]. The interfaces are uninstalled
+/// when this is dropped.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct InstalledProtocols<'a> {
+    boot_services: &'a BootServices,
+    handle: Handle,
+    interfaces: Vec<ProtocolInterface>,
+}
+
+#[cfg(feature = "alloc")]
+impl InstalledProtocols<'_> {
+    /// The handle on which the interfaces are installed.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Uninstalls and frees all the interfaces. Either all the interfaces
+    /// are uninstalled, or none are.
+    ///
+    /// This is the equivalent of `UninstallMultipleProtocolInterfaces`.
+    ///
+    /// # Errors
+    ///
+    /// `self` is returned as error data if an interface can't be
+    /// uninstalled. See [`BootServices::uninstall_protocol_interface`].
+    pub fn uninstall(mut self) -> Result<(), Self> {
+        match self.uninstall_all() {
+            Ok(()) => Ok(()),
+            Err(err) => Err(Error::new(err.status(), self)),
+        }
+    }
+
+    /// Keep the interfaces installed when this is dropped, and return a
+    /// [`DetachedProtocols`] that can uninstall them later.
+    ///
+    /// Unlike `InstalledProtocols`, [`DetachedProtocols`] doesn't borrow
+    /// the boot services, so it can be stored by the handler registered
+    /// with [`BootServices::set_unload_handler`].
+    #[must_use]
+    pub fn detach(mut self) -> DetachedProtocols {
+        let detached = DetachedProtocols {
+            handle: self.handle,
+            interfaces: mem::take(&mut self.interfaces),
+        };
+        mem::forget(self);
+        detached
+    }
+
+    /// Uninstalls and frees all the interfaces, or reinstalls them if one
+    /// of them can't be uninstalled.
+    fn uninstall_all(&mut self) -> Result {
+        uninstall_interfaces(self.boot_services, self.handle, &mut self.interfaces)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for InstalledProtocols<'_> {
+    fn drop(&mut self) {
+        // If the interfaces are still installed they must not be freed.
+        if self.uninstall_all().is_err() {
+            self.interfaces.clear();
+        }
+    }
+}
+
+/// Protocol interfaces installed with [`BootServices::install_protocols`],
+/// that stay installed when this is dropped. See
+/// [`InstalledProtocols::detach`].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct DetachedProtocols {
+    handle: Handle,
+    interfaces: Vec<ProtocolInterface>,
+}
+
+#[cfg(feature = "alloc")]
+impl DetachedProtocols {
+    /// The handle on which the interfaces are installed.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Uninstalls and frees all the interfaces. Either all the interfaces
+    /// are uninstalled, or none are.
+    ///
+    /// # Errors
+    ///
+    /// `self` is returned as error data if an interface can't be
+    /// uninstalled. See [`BootServices::uninstall_protocol_interface`].
+    pub fn uninstall(mut self, bt: &BootServices) -> Result<(), Self> {
+        match uninstall_interfaces(bt, self.handle, &mut self.interfaces) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(Error::new(err.status(), self)),
+        }
+    }
+}
+
+/// Uninstalls and frees all the `interfaces` of `handle`, or reinstalls
+/// them if one of them can't be uninstalled.
+#[cfg(feature = "alloc")]
+fn uninstall_interfaces(
+    bt: &BootServices,
+    handle: Handle,
+    interfaces: &mut Vec<ProtocolInterface>,
+) -> Result {
+    for (i, interface) in interfaces.iter().enumerate() {
+        let result =
+            unsafe { bt.uninstall_protocol_interface(handle, &interface.protocol, interface.ptr) };
+        if let Err(err) = result {
+            for interface in interfaces[..i].iter().rev() {
+                // Ignore the result, there's nothing more we can do if the
+                // interface can't be reinstalled.
+                let _ = unsafe {
+                    bt.install_protocol_interface(Some(handle), &interface.protocol, interface.ptr)
+                };
+            }
+            return Err(err);
+        }
+    }
+    for interface in interfaces.drain(..) {
+        unsafe { interface.free() };
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use core::mem::{size_of, size_of_val};
 
     use crate::table::boot::{MemoryAttribute, MemoryMap, MemoryType};
 
-    use super::{MemoryDescriptor, MemoryMapIter};
+    use super::{timer_ticks, MemoryDescriptor, MemoryMapIter};
+    use core::time::Duration;
 
     fn buffer_to_map(buffer: &mut [MemoryDescriptor]) -> MemoryMap {
         let byte_buffer = {
@@ -2095,4 +3465,13 @@ mod tests {
         }
         true
     }
+
+    #[test]
+    fn test_timer_ticks() {
+        assert_eq!(timer_ticks(Duration::ZERO), 0);
+        assert_eq!(timer_ticks(Duration::from_nanos(1)), 1);
+        assert_eq!(timer_ticks(Duration::from_micros(1)), 10);
+        assert_eq!(timer_ticks(Duration::from_secs(1)), 10_000_000);
+        assert_eq!(timer_ticks(Duration::MAX), u64::MAX);
+    }
 }
@@ -6,10 +6,68 @@
 //!
 //! This module contains the actual entries of the configuration table,
 //! as well as GUIDs for many known vendor tables.
+//!
+//! Tables that implement the [`ConfigTable`] trait can be looked up with
+//! [`SystemTable::find_config_table`], which takes care of finding the entry
+//! and validating the table's contents.
+//!
+//! [`SystemTable::find_config_table`]: super::SystemTable::find_config_table
 
 use crate::{guid, Guid};
 use bitflags::bitflags;
 use core::ffi::c_void;
+use core::mem;
+
+/// A vendor table that can be found in the configuration table.
+///
+/// # Example
+///
+/// ```
+/// use core::ffi::c_void;
+/// use uefi::{guid, Guid};
+/// use uefi::table::cfg::ConfigTable;
+///
+/// #[repr(C)]
+/// struct ExampleTable {
+///     signature: [u8; 4],
+///     value: u32,
+/// }
+///
+/// unsafe impl ConfigTable for ExampleTable {
+///     const GUID: Guid = guid!("3bdb3089-5662-42df-840e-3922ed6467c9");
+///
+///     unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+///         let table = &*ptr.cast::<Self>();
+///         (table.signature == *b"EXMP").then_some(table)
+///     }
+/// }
+/// ```
+///
+/// # Safety
+///
+/// The implementation must ensure that the table is only accessed through
+/// the returned reference if the entry identified by [`GUID`] has the
+/// expected layout. Any part of the table that may be invalid, such as a
+/// signature, length or checksum, must be checked before returning it.
+///
+/// [`GUID`]: Self::GUID
+pub unsafe trait ConfigTable {
+    /// GUID of the configuration table entry that points to the table.
+    const GUID: Guid;
+
+    /// Validate the table at `ptr` and create a reference to it.
+    ///
+    /// Returns `None` if the table is not valid.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be the non-null address of a configuration table entry
+    /// whose GUID is [`GUID`], and the table must remain valid for the
+    /// lifetime `'a`.
+    ///
+    /// [`GUID`]: Self::GUID
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self>;
+}
 
 /// Contains a set of GUID / pointer for a vendor-specific table.
 ///
@@ -45,7 +103,17 @@ pub const ESRT_GUID: Guid = guid!("b122a263-3661-4f68-9929-78f8b0d62180");
 /// about the UEFI implementation.
 pub const PROPERTIES_TABLE_GUID: Guid = guid!("880aaca3-4adc-4a04-9079-b747340825e5");
 
+/// Entry pointing to the memory attributes table.
+///
+/// See [`MemoryAttributesTable`].
+///
+/// [`MemoryAttributesTable`]: super::memory_attributes::MemoryAttributesTable
+pub const MEMORY_ATTRIBUTES_TABLE_GUID: Guid = guid!("dcfa911d-26eb-469f-a220-38b7dc461220");
+
 /// This table contains additional information about the UEFI implementation.
+///
+/// This table was deprecated in UEFI 2.6 in favor of the memory attributes
+/// table.
 #[repr(C)]
 #[derive(Debug)]
 pub struct PropertiesTable {
@@ -61,6 +129,22 @@ pub struct PropertiesTable {
     pub memory_protection: MemoryProtectionAttribute,
 }
 
+impl PropertiesTable {
+    /// The only valid value of the `version` field.
+    pub const VERSION: u32 = 0x1_0000;
+}
+
+unsafe impl ConfigTable for PropertiesTable {
+    const GUID: Guid = PROPERTIES_TABLE_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let table = &*ptr.cast::<Self>();
+        let valid =
+            table.version == Self::VERSION && table.length as usize >= mem::size_of::<Self>();
+        valid.then_some(table)
+    }
+}
+
 bitflags! {
     /// Flags describing memory protection.
     #[repr(transparent)]
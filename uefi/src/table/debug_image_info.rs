@@ -0,0 +1,367 @@
+//! Debug image info table.
+//!
+//! The [`DEBUG_IMAGE_INFO_GUID`] configuration table entry points to a list
+//! of the images that are currently loaded. It is intended for debuggers,
+//! but it can also be used to map a code address, such as the address of a
+//! fault or the return addresses of a backtrace, to the image that contains
+//! it. [`DebugImage::pdb_path`] gives the path of the image's debug symbols,
+//! which identifies the image when symbolizing module-relative addresses.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::debug_image_info::DebugImageInfoTable;
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn print_location(st: &SystemTable<Boot>, address: usize) {
+//!     let image = st
+//!         .find_config_table::<DebugImageInfoTable>()
+//!         .and_then(|table| table.find_image(address));
+//!     match image {
+//!         Some(image) => {
+//!             let offset = address - image.base();
+//!             match image.pdb_path() {
+//!                 Some(path) => log::error!("at {path:?}+{offset:#x}"),
+//!                 None => log::error!("at {:#x}+{offset:#x}", image.base()),
+//!             }
+//!         }
+//!         None => log::error!("at {address:#x}"),
+//!     }
+//! }
+//! ```
+//!
+//! [`DEBUG_IMAGE_INFO_GUID`]: super::cfg::DEBUG_IMAGE_INFO_GUID
+
+use super::cfg::{self, ConfigTable};
+use crate::proto::loaded_image::LoadedImage;
+use crate::util::read_at;
+use crate::{Guid, Handle};
+use core::ffi::{c_void, CStr};
+use core::{ptr, slice};
+use uefi_raw::table::debug_image_info::{
+    DebugImageInfo, DebugImageInfoNormal, DebugImageInfoTableHeader, DebugImageInfoType,
+};
+
+pub use uefi_raw::table::debug_image_info::DebugImageInfoUpdateStatus;
+
+/// Debug image info table.
+///
+/// The firmware updates the table in place when images are loaded or
+/// unloaded, so references into the table shouldn't be held across calls
+/// that load or unload images.
+///
+/// Corresponds to the C type `EFI_DEBUG_IMAGE_INFO_TABLE_HEADER`.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct DebugImageInfoTable(DebugImageInfoTableHeader);
+
+impl DebugImageInfoTable {
+    /// Update status of the table.
+    ///
+    /// If [`DebugImageInfoUpdateStatus::UPDATE_IN_PROGRESS`] is set, the
+    /// firmware was interrupted while updating the table, and the entries
+    /// may be inconsistent.
+    #[must_use]
+    pub fn update_status(&self) -> DebugImageInfoUpdateStatus {
+        unsafe { ptr::read_volatile(&self.0.update_status) }
+    }
+
+    /// Number of entries in the table, including unused entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        unsafe { ptr::read_volatile(&self.0.table_size) as usize }
+    }
+
+    /// Returns true if the table has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the loaded images in the table.
+    ///
+    /// Unused entries and entries of unknown types are skipped.
+    #[must_use]
+    pub fn images(&self) -> DebugImages<'_> {
+        let table = unsafe { ptr::read_volatile(&self.0.efi_debug_image_info_table) };
+        let entries = if table.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(table.cast_const(), self.len()) }
+        };
+        DebugImages {
+            entries: entries.iter(),
+        }
+    }
+
+    /// Find the loaded image that contains `address`.
+    #[must_use]
+    pub fn find_image(&self, address: usize) -> Option<DebugImage<'_>> {
+        self.images().find(|image| image.contains(address))
+    }
+}
+
+unsafe impl ConfigTable for DebugImageInfoTable {
+    const GUID: Guid = cfg::DEBUG_IMAGE_INFO_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        Some(&*ptr.cast::<Self>())
+    }
+}
+
+/// Iterator over the loaded images of the debug image info table, returned
+/// by [`DebugImageInfoTable::images`].
+#[derive(Debug, Clone)]
+pub struct DebugImages<'a> {
+    entries: slice::Iter<'a, DebugImageInfo>,
+}
+
+impl<'a> Iterator for DebugImages<'a> {
+    type Item = DebugImage<'a>;
+
+    fn next(&mut self) -> Option<DebugImage<'a>> {
+        self.entries.find_map(|entry| unsafe {
+            let entry_type = entry.image_info_type;
+            if entry_type.is_null() || *entry_type != DebugImageInfoType::NORMAL {
+                return None;
+            }
+            DebugImage::from_raw(&*entry.normal_image)
+        })
+    }
+}
+
+/// Loaded image listed in the debug image info table.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugImage<'a> {
+    loaded_image: &'a LoadedImage,
+    handle: Handle,
+}
+
+impl<'a> DebugImage<'a> {
+    unsafe fn from_raw(entry: &'a DebugImageInfoNormal) -> Option<Self> {
+        let loaded_image = entry.loaded_image_protocol_instance;
+        if loaded_image.is_null() {
+            return None;
+        }
+        Some(Self {
+            loaded_image: &*loaded_image.cast::<LoadedImage>(),
+            handle: Handle::from_ptr(entry.image_handle)?,
+        })
+    }
+
+    /// Handle of the image.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Loaded image protocol of the image.
+    #[must_use]
+    pub const fn loaded_image(&self) -> &'a LoadedImage {
+        self.loaded_image
+    }
+
+    /// Address where the image was loaded.
+    #[must_use]
+    pub fn base(&self) -> usize {
+        self.loaded_image.info().0 as usize
+    }
+
+    /// Size in bytes of the loaded image.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.loaded_image.info().1 as usize
+    }
+
+    /// Returns true if `address` is within the loaded image.
+    #[must_use]
+    pub fn contains(&self, address: usize) -> bool {
+        address
+            .checked_sub(self.base())
+            .is_some_and(|offset| offset < self.size())
+    }
+
+    /// Path of the debug symbols (PDB) file of the image, taken from the
+    /// CodeView entry of the image's PE debug directory.
+    ///
+    /// Returns `None` if the image isn't a PE image or has no CodeView
+    /// entry.
+    #[must_use]
+    pub fn pdb_path(&self) -> Option<&'a CStr> {
+        let (base, size) = self.loaded_image.info();
+        if base.is_null() {
+            return None;
+        }
+        let image = unsafe { slice::from_raw_parts(base.cast::<u8>(), size as usize) };
+        pdb_path(image)
+    }
+}
+
+/// Index of the debug directory in the data directories of a PE image.
+const DEBUG_DIRECTORY_INDEX: usize = 6;
+
+/// Size of an entry of the PE debug directory.
+const DEBUG_DIRECTORY_ENTRY_SIZE: usize = 28;
+
+/// Debug directory entry type of CodeView data.
+const DEBUG_TYPE_CODEVIEW: u32 = 2;
+
+/// Find the PDB path in the CodeView debug data of the loaded PE `image`.
+fn pdb_path(image: &[u8]) -> Option<&CStr> {
+    if image.get(..2)? != b"MZ" {
+        return None;
+    }
+    let pe = read_at::<u32>(image, 0x3c)? as usize;
+    if image.get(pe..pe.checked_add(4)?)? != b"PE\0\0" {
+        return None;
+    }
+
+    // The optional header follows the 4-byte signature and the 20-byte
+    // COFF header. The offsets of its data directories depend on whether
+    // the image is PE32 or PE32+.
+    let optional_header = pe + 24;
+    let (count_offset, directories_offset) = match read_at::<u16>(image, optional_header)? {
+        0x10b => (92, 96),
+        0x20b => (108, 112),
+        _ => return None,
+    };
+    let directory_count = read_at::<u32>(image, optional_header + count_offset)? as usize;
+    if directory_count <= DEBUG_DIRECTORY_INDEX {
+        return None;
+    }
+    let directory = optional_header + directories_offset + DEBUG_DIRECTORY_INDEX * 8;
+    let debug_rva = read_at::<u32>(image, directory)? as usize;
+    let debug_size = read_at::<u32>(image, directory + 4)? as usize;
+
+    // The image is loaded, so RVAs are offsets into the image.
+    let debug_directory = image.get(debug_rva..debug_rva.checked_add(debug_size)?)?;
+    let codeview = debug_directory
+        .chunks_exact(DEBUG_DIRECTORY_ENTRY_SIZE)
+        .find(|entry| read_at::<u32>(entry, 12) == Some(DEBUG_TYPE_CODEVIEW))?;
+    let data_size = read_at::<u32>(codeview, 16)? as usize;
+    let data_rva = read_at::<u32>(codeview, 20)? as usize;
+    let data = image.get(data_rva..data_rva.checked_add(data_size)?)?;
+
+    let path_offset = match data.get(..4)? {
+        b"RSDS" => 24,
+        b"NB10" => 16,
+        _ => return None,
+    };
+    CStr::from_bytes_until_nul(data.get(path_offset..)?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use uefi_raw::protocol::loaded_image::LoadedImageProtocol;
+    use uefi_raw::table::boot::MemoryType;
+
+    fn write(image: &mut [u8], offset: usize, bytes: &[u8]) {
+        image[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Create a PE32+ image with a CodeView debug entry.
+    fn pe_image(codeview: &[u8]) -> Vec<u8> {
+        let mut image = vec![0; 0x400];
+        write(&mut image, 0, b"MZ");
+        write(&mut image, 0x3c, &0x80u32.to_le_bytes());
+        write(&mut image, 0x80, b"PE\0\0");
+        let optional_header = 0x80 + 24;
+        write(&mut image, optional_header, &0x20bu16.to_le_bytes());
+        write(&mut image, optional_header + 108, &16u32.to_le_bytes());
+        let directory = optional_header + 112 + 6 * 8;
+        write(&mut image, directory, &0x200u32.to_le_bytes());
+        write(&mut image, directory + 4, &28u32.to_le_bytes());
+        write(&mut image, 0x200 + 12, &2u32.to_le_bytes());
+        write(
+            &mut image,
+            0x200 + 16,
+            &(codeview.len() as u32).to_le_bytes(),
+        );
+        write(&mut image, 0x200 + 20, &0x300u32.to_le_bytes());
+        write(&mut image, 0x300, codeview);
+        image
+    }
+
+    #[test]
+    fn test_pdb_path() {
+        let mut codeview = vec![0; 24];
+        write(&mut codeview, 0, b"RSDS");
+        codeview.extend_from_slice(b"/build/app.pdb\0");
+        let image = pe_image(&codeview);
+        assert_eq!(
+            pdb_path(&image),
+            CStr::from_bytes_with_nul(b"/build/app.pdb\0").ok()
+        );
+
+        // Unknown CodeView signature.
+        let image = pe_image(b"XXXX");
+        assert_eq!(pdb_path(&image), None);
+
+        // Not a PE image.
+        assert_eq!(pdb_path(&[0; 0x40]), None);
+    }
+
+    #[test]
+    fn test_images() {
+        let loaded_image = |base: usize, size: u64| LoadedImageProtocol {
+            revision: 0x1000,
+            parent_handle: ptr::null_mut(),
+            system_table: ptr::null(),
+            device_handle: ptr::null_mut(),
+            file_path: ptr::null(),
+            reserved: ptr::null(),
+            load_options_size: 0,
+            load_options: ptr::null(),
+            image_base: base as *const c_void,
+            image_size: size,
+            image_code_type: MemoryType::LOADER_CODE,
+            image_data_type: MemoryType::LOADER_DATA,
+            unload: None,
+        };
+        let images = [loaded_image(0x10000, 0x2000), loaded_image(0x20000, 0x1000)];
+        let normal = |index: usize| DebugImageInfoNormal {
+            image_info_type: DebugImageInfoType::NORMAL,
+            loaded_image_protocol_instance: &images[index],
+            image_handle: (0x1000 + index) as *mut c_void,
+        };
+        let normal = [normal(0), normal(1)];
+        let unknown = DebugImageInfoType(2);
+        let mut entries = [
+            DebugImageInfo {
+                normal_image: &normal[0],
+            },
+            DebugImageInfo {
+                image_info_type: ptr::null(),
+            },
+            DebugImageInfo {
+                image_info_type: &unknown,
+            },
+            DebugImageInfo {
+                normal_image: &normal[1],
+            },
+        ];
+        let header = DebugImageInfoTableHeader {
+            update_status: DebugImageInfoUpdateStatus::TABLE_MODIFIED,
+            table_size: entries.len() as u32,
+            efi_debug_image_info_table: entries.as_mut_ptr(),
+        };
+        let table = unsafe { DebugImageInfoTable::from_ptr(ptr::addr_of!(header).cast()) }.unwrap();
+
+        assert_eq!(
+            table.update_status(),
+            DebugImageInfoUpdateStatus::TABLE_MODIFIED
+        );
+        assert_eq!(table.len(), 4);
+        let bases: Vec<_> = table.images().map(|image| image.base()).collect();
+        assert_eq!(bases, [0x10000, 0x20000]);
+
+        let image = table.find_image(0x11fff).unwrap();
+        assert_eq!(image.base(), 0x10000);
+        assert_eq!(image.handle().as_ptr(), normal[0].image_handle);
+        assert!(table.find_image(0x12000).is_none());
+        assert_eq!(table.find_image(0x20800).unwrap().size(), 0x1000);
+    }
+}
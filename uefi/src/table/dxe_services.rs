@@ -0,0 +1,379 @@
+//! PI DXE services table.
+//!
+//! On firmware based on the Platform Initialization (PI) specification, the
+//! [`DXE_SERVICES_GUID`] configuration table entry points to the DXE
+//! services table. It gives access to the Global Coherency Domain (GCD),
+//! which tracks how the memory and I/O space of the system is used, and to
+//! the DXE dispatcher.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::dxe_services::{DxeServices, GcdMemoryType};
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn print_mmio(st: &SystemTable<Boot>) -> uefi::Result {
+//!     let Some(dxe) = st.find_config_table::<DxeServices>() else {
+//!         return Ok(());
+//!     };
+//!     for desc in &dxe.memory_space_map(st.boot_services())? {
+//!         if desc.gcd_memory_type == GcdMemoryType::MEMORY_MAPPED_IO {
+//!             let end = desc.base_address + desc.length;
+//!             log::info!("MMIO: {:#x}..{end:#x}", desc.base_address);
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`DXE_SERVICES_GUID`]: super::cfg::DXE_SERVICES_GUID
+
+use super::boot::{BootServices, MemoryAttribute};
+use super::cfg::{self, ConfigTable};
+use crate::data_types::PhysicalAddress;
+use crate::{Guid, Handle, Result, StatusExt};
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::{self, MaybeUninit};
+use core::ops::Deref;
+use core::{ptr, slice};
+use uefi_raw::table::dxe_services::DxeServices as RawDxeServices;
+
+pub use uefi_raw::table::dxe_services::{
+    GcdAllocateType, GcdIoSpaceDescriptor, GcdIoType, GcdMemorySpaceDescriptor, GcdMemoryType,
+};
+
+/// PI DXE services table.
+///
+/// Corresponds to the C type `EFI_DXE_SERVICES`.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct DxeServices(RawDxeServices);
+
+impl DxeServices {
+    /// Add a region of memory space to the GCD.
+    ///
+    /// `capabilities` are the memory attributes that the region supports.
+    pub fn add_memory_space(
+        &self,
+        memory_type: GcdMemoryType,
+        base_address: PhysicalAddress,
+        length: u64,
+        capabilities: MemoryAttribute,
+    ) -> Result {
+        unsafe { (self.0.add_memory_space)(memory_type, base_address, length, capabilities) }
+            .to_result()
+    }
+
+    /// Allocate `length` bytes of memory space of type `memory_type` from
+    /// the GCD, on behalf of `image` and optionally `device`.
+    ///
+    /// `alignment` is the base-2 logarithm of the alignment of the region.
+    /// `base_address` is only used by the [`GcdAllocateType::ADDRESS`] and
+    /// `MAX_ADDRESS_*` allocation types. Returns the start address of the
+    /// allocated region.
+    #[allow(clippy::too_many_arguments)]
+    pub fn allocate_memory_space(
+        &self,
+        allocate_type: GcdAllocateType,
+        memory_type: GcdMemoryType,
+        alignment: usize,
+        length: u64,
+        base_address: PhysicalAddress,
+        image: Handle,
+        device: Option<Handle>,
+    ) -> Result<PhysicalAddress> {
+        let mut address = base_address;
+        unsafe {
+            (self.0.allocate_memory_space)(
+                allocate_type,
+                memory_type,
+                alignment,
+                length,
+                &mut address,
+                image.as_ptr(),
+                Handle::opt_to_ptr(device),
+            )
+        }
+        .to_result_with_val(|| address)
+    }
+
+    /// Free a region of memory space allocated with
+    /// [`allocate_memory_space`].
+    ///
+    ///
+    /// # Safety
+    ///
+    /// The region may be handed out again, so the caller must make sure
+    /// that no memory or MMIO in the region is still in use.
+    ///
+    /// [`allocate_memory_space`]: Self::allocate_memory_space
+    pub unsafe fn free_memory_space(&self, base_address: PhysicalAddress, length: u64) -> Result {
+        (self.0.free_memory_space)(base_address, length).to_result()
+    }
+
+    /// Remove a region of memory space from the GCD.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that no memory or MMIO in the region is
+    /// still in use.
+    pub unsafe fn remove_memory_space(&self, base_address: PhysicalAddress, length: u64) -> Result {
+        (self.0.remove_memory_space)(base_address, length).to_result()
+    }
+
+    /// Get the descriptor of the memory space region containing
+    /// `base_address`.
+    pub fn get_memory_space_descriptor(
+        &self,
+        base_address: PhysicalAddress,
+    ) -> Result<GcdMemorySpaceDescriptor> {
+        let mut descriptor = MaybeUninit::uninit();
+        unsafe { (self.0.get_memory_space_descriptor)(base_address, descriptor.as_mut_ptr()) }
+            .to_result_with_val(|| unsafe { descriptor.assume_init() })
+    }
+
+    /// Set the memory attributes of a region of memory space.
+    ///
+    /// The attributes must be supported by the capabilities of the region.
+    ///
+    /// # Safety
+    ///
+    /// This changes the page tables and caching of the region, so the
+    /// caller must make sure that no memory still in use loses access.
+    pub unsafe fn set_memory_space_attributes(
+        &self,
+        base_address: PhysicalAddress,
+        length: u64,
+        attributes: MemoryAttribute,
+    ) -> Result {
+        (self.0.set_memory_space_attributes)(base_address, length, attributes).to_result()
+    }
+
+    /// Set the memory attributes that a region of memory space supports.
+    ///
+    /// This is only available since PI 1.2; [`Status::UNSUPPORTED`] is
+    /// returned if the table is too old.
+    ///
+    /// [`Status::UNSUPPORTED`]: crate::Status::UNSUPPORTED
+    pub fn set_memory_space_capabilities(
+        &self,
+        base_address: PhysicalAddress,
+        length: u64,
+        capabilities: MemoryAttribute,
+    ) -> Result {
+        if (self.0.header.size as usize) < mem::size_of::<RawDxeServices>() {
+            return Err(crate::Status::UNSUPPORTED.into());
+        }
+        unsafe { (self.0.set_memory_space_capabilities)(base_address, length, capabilities) }
+            .to_result()
+    }
+
+    /// Get the memory space map of the GCD.
+    ///
+    /// The descriptors are sorted by address and cover the whole memory
+    /// space of the processor. The map is allocated from pool memory and
+    /// freed with `bt` when the returned [`GcdMap`] is dropped.
+    pub fn memory_space_map<'a>(
+        &self,
+        bt: &'a BootServices,
+    ) -> Result<GcdMap<'a, GcdMemorySpaceDescriptor>> {
+        let mut len = 0;
+        let mut buffer = ptr::null_mut();
+        unsafe { (self.0.get_memory_space_map)(&mut len, &mut buffer) }.to_result_with_val(|| {
+            GcdMap {
+                boot_services: bt,
+                buffer,
+                len,
+            }
+        })
+    }
+
+    /// Add a region of I/O space to the GCD.
+    pub fn add_io_space(
+        &self,
+        io_type: GcdIoType,
+        base_address: PhysicalAddress,
+        length: u64,
+    ) -> Result {
+        unsafe { (self.0.add_io_space)(io_type, base_address, length) }.to_result()
+    }
+
+    /// Allocate `length` bytes of I/O space of type `io_type` from the GCD,
+    /// on behalf of `image` and optionally `device`.
+    ///
+    /// The parameters are the same as for
+    /// [`allocate_memory_space`](Self::allocate_memory_space).
+    #[allow(clippy::too_many_arguments)]
+    pub fn allocate_io_space(
+        &self,
+        allocate_type: GcdAllocateType,
+        io_type: GcdIoType,
+        alignment: usize,
+        length: u64,
+        base_address: PhysicalAddress,
+        image: Handle,
+        device: Option<Handle>,
+    ) -> Result<PhysicalAddress> {
+        let mut address = base_address;
+        unsafe {
+            (self.0.allocate_io_space)(
+                allocate_type,
+                io_type,
+                alignment,
+                length,
+                &mut address,
+                image.as_ptr(),
+                Handle::opt_to_ptr(device),
+            )
+        }
+        .to_result_with_val(|| address)
+    }
+
+    /// Free a region of I/O space allocated with
+    /// [`allocate_io_space`](Self::allocate_io_space).
+    ///
+    /// # Safety
+    ///
+    /// The region may be handed out again, so the caller must make sure
+    /// that no I/O ports in the region are still in use.
+    pub unsafe fn free_io_space(&self, base_address: PhysicalAddress, length: u64) -> Result {
+        (self.0.free_io_space)(base_address, length).to_result()
+    }
+
+    /// Remove a region of I/O space from the GCD.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that no I/O ports in the region are still
+    /// in use.
+    pub unsafe fn remove_io_space(&self, base_address: PhysicalAddress, length: u64) -> Result {
+        (self.0.remove_io_space)(base_address, length).to_result()
+    }
+
+    /// Get the descriptor of the I/O space region containing
+    /// `base_address`.
+    pub fn get_io_space_descriptor(
+        &self,
+        base_address: PhysicalAddress,
+    ) -> Result<GcdIoSpaceDescriptor> {
+        let mut descriptor = MaybeUninit::uninit();
+        unsafe { (self.0.get_io_space_descriptor)(base_address, descriptor.as_mut_ptr()) }
+            .to_result_with_val(|| unsafe { descriptor.assume_init() })
+    }
+
+    /// Get the I/O space map of the GCD.
+    ///
+    /// See [`memory_space_map`](Self::memory_space_map).
+    pub fn io_space_map<'a>(
+        &self,
+        bt: &'a BootServices,
+    ) -> Result<GcdMap<'a, GcdIoSpaceDescriptor>> {
+        let mut len = 0;
+        let mut buffer = ptr::null_mut();
+        unsafe { (self.0.get_io_space_map)(&mut len, &mut buffer) }.to_result_with_val(|| GcdMap {
+            boot_services: bt,
+            buffer,
+            len,
+        })
+    }
+
+    /// Load and start the drivers of the firmware volumes that haven't been
+    /// dispatched yet.
+    ///
+    /// Returns [`Status::NOT_FOUND`] if no driver was dispatched.
+    ///
+    /// [`Status::NOT_FOUND`]: crate::Status::NOT_FOUND
+    pub fn dispatch(&self) -> Result {
+        unsafe { (self.0.dispatch)() }.to_result()
+    }
+
+    /// Clear the Schedule on Request (SOR) flag of the driver `file_name` in
+    /// the firmware volume `firmware_volume`, so that the next
+    /// [`dispatch`](Self::dispatch) can start it.
+    pub fn schedule(&self, firmware_volume: Handle, file_name: &Guid) -> Result {
+        unsafe { (self.0.schedule)(firmware_volume.as_ptr(), file_name) }.to_result()
+    }
+
+    /// Promote the driver `file_name` in the firmware volume
+    /// `firmware_volume` from the untrusted to the trusted state.
+    pub fn trust(&self, firmware_volume: Handle, file_name: &Guid) -> Result {
+        unsafe { (self.0.trust)(firmware_volume.as_ptr(), file_name) }.to_result()
+    }
+
+    /// Create a firmware volume handle for the firmware volume in `volume`,
+    /// so that its drivers can be dispatched.
+    ///
+    /// # Safety
+    ///
+    /// The firmware accesses the volume through its address, so `volume`
+    /// must stay valid, and its contents unchanged, until the firmware
+    /// volume handle is removed. The drivers in the volume run with full
+    /// privileges, so `volume` must come from a trusted source.
+    pub unsafe fn process_firmware_volume(&self, volume: &[u8]) -> Result<Handle> {
+        let mut handle = ptr::null_mut();
+        (self.0.process_firmware_volume)(volume.as_ptr().cast(), volume.len(), &mut handle)
+            .to_result_with_val(|| Handle::from_ptr(handle).unwrap())
+    }
+}
+
+impl super::Table for DxeServices {
+    const SIGNATURE: u64 = RawDxeServices::SIGNATURE;
+}
+
+unsafe impl ConfigTable for DxeServices {
+    const GUID: Guid = cfg::DXE_SERVICES_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let table = &*ptr.cast::<Self>();
+        (table.0.header.signature == RawDxeServices::SIGNATURE).then_some(table)
+    }
+}
+
+/// Map of the memory or I/O space of the GCD, returned by
+/// [`DxeServices::memory_space_map`] and [`DxeServices::io_space_map`].
+///
+/// The descriptors can be accessed through [`Deref`] to a slice.
+pub struct GcdMap<'a, T> {
+    // The map returned by the firmware has to be freed with `free_pool`, so
+    // keep a reference to boot services for that purpose.
+    boot_services: &'a BootServices,
+    buffer: *mut T,
+    len: usize,
+}
+
+impl<T> Drop for GcdMap<'_, T> {
+    fn drop(&mut self) {
+        if !self.buffer.is_null() {
+            // Ignore the result, we can't do anything about an error here.
+            let _ = unsafe { self.boot_services.free_pool(self.buffer.cast::<u8>()) };
+        }
+    }
+}
+
+impl<T> Deref for GcdMap<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        if self.buffer.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.buffer, self.len) }
+        }
+    }
+}
+
+impl<'b, T> IntoIterator for &'b GcdMap<'_, T> {
+    type Item = &'b T;
+    type IntoIter = slice::Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Debug> Debug for GcdMap<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
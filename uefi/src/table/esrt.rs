@@ -0,0 +1,156 @@
+//! EFI System Resource Table (ESRT).
+//!
+//! The ESRT lists the firmware resources of the system that can be updated
+//! with capsules. Each entry identifies a resource by its firmware class
+//! GUID, which is also the GUID of capsules that update it, and records the
+//! current version and the result of the last update attempt.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::esrt::Esrt;
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn print_update_status(st: &SystemTable<Boot>) {
+//!     let Some(esrt) = st.find_config_table::<Esrt>() else {
+//!         return;
+//!     };
+//!     for entry in esrt.entries() {
+//!         log::info!(
+//!             "{}: version {:#x}, last update {}",
+//!             entry.fw_class,
+//!             entry.fw_version,
+//!             entry.last_attempt_status
+//!         );
+//!     }
+//! }
+//! ```
+
+use super::cfg::{self, ConfigTable};
+use crate::Guid;
+use core::ffi::c_void;
+use ptr_meta::Pointee;
+use uefi_raw::table::esrt::SystemResourceTable;
+
+pub use uefi_raw::table::esrt::{FirmwareType, LastAttemptStatus, SystemResourceEntry};
+
+/// EFI System Resource Table.
+///
+/// Corresponds to the C type `EFI_SYSTEM_RESOURCE_TABLE`.
+#[derive(Debug, Eq, PartialEq, Pointee)]
+#[repr(C)]
+pub struct Esrt {
+    resource_count: u32,
+    resource_count_max: u32,
+    resource_version: u64,
+    entries: [SystemResourceEntry],
+}
+
+impl Esrt {
+    /// Number of entries that fit in the memory allocated for the table.
+    #[must_use]
+    pub const fn resource_count_max(&self) -> u32 {
+        self.resource_count_max
+    }
+
+    /// Version of the table format.
+    #[must_use]
+    pub const fn resource_version(&self) -> u64 {
+        self.resource_version
+    }
+
+    /// Get the entries of the table.
+    #[must_use]
+    pub const fn entries(&self) -> &[SystemResourceEntry] {
+        &self.entries
+    }
+
+    /// Find the entry for the firmware resource identified by `fw_class`.
+    #[must_use]
+    pub fn find(&self, fw_class: &Guid) -> Option<&SystemResourceEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.fw_class == *fw_class)
+    }
+}
+
+unsafe impl ConfigTable for Esrt {
+    const GUID: Guid = cfg::ESRT_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let header = &*ptr.cast::<SystemResourceTable>();
+        let valid = header.fw_resource_version == SystemResourceTable::VERSION
+            && header.fw_resource_count <= header.fw_resource_count_max;
+        if !valid {
+            return None;
+        }
+        let table: *const Self =
+            ptr_meta::from_raw_parts(ptr.cast(), header.fw_resource_count as usize);
+        Some(&*table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use alloc::vec::Vec;
+    use core::mem;
+    use uefi_raw::capsule::CapsuleFlags;
+
+    #[test]
+    fn test_esrt() {
+        let entry = SystemResourceEntry {
+            fw_class: guid!("3bdb3089-5662-42df-840e-3922ed6467c9"),
+            fw_type: FirmwareType::SYSTEM_FIRMWARE,
+            fw_version: 7,
+            lowest_supported_fw_version: 5,
+            capsule_flags: CapsuleFlags::PERSIST_ACROSS_RESET,
+            last_attempt_version: 8,
+            last_attempt_status: LastAttemptStatus::ERROR_AUTH_ERROR,
+        };
+
+        // Use a `u64` buffer to get the alignment of the table.
+        let mut buf = Vec::<u64>::new();
+        buf.push(1 | (2 << 32));
+        buf.push(SystemResourceTable::VERSION);
+        let entry_words = mem::size_of::<SystemResourceEntry>() / mem::size_of::<u64>();
+        buf.resize(2 + entry_words * 2, 0);
+        unsafe {
+            buf.as_mut_ptr()
+                .add(2)
+                .cast::<SystemResourceEntry>()
+                .write(entry);
+        }
+
+        let esrt = unsafe { Esrt::from_ptr(buf.as_ptr().cast()) }.unwrap();
+        assert_eq!(esrt.resource_version(), 1);
+        assert_eq!(esrt.resource_count_max(), 2);
+        assert_eq!(esrt.entries(), [entry]);
+        assert_eq!(esrt.find(&entry.fw_class), Some(&entry));
+        assert_eq!(esrt.find(&Guid::ZERO), None);
+
+        // Count larger than the maximum.
+        buf[0] = 3 | (2 << 32);
+        assert!(unsafe { Esrt::from_ptr(buf.as_ptr().cast()) }.is_none());
+    }
+
+    #[test]
+    fn test_last_attempt_status() {
+        use alloc::string::ToString;
+
+        assert!(LastAttemptStatus::SUCCESS.is_success());
+        assert!(!LastAttemptStatus::ERROR_AUTH_ERROR.is_success());
+        assert_eq!(
+            LastAttemptStatus::ERROR_AUTH_ERROR.to_string(),
+            "authentication error"
+        );
+        assert_eq!(LastAttemptStatus(0x1003).vendor_code(), Some(3));
+        assert_eq!(LastAttemptStatus(0x1003).to_string(), "vendor error 0x3");
+        assert_eq!(LastAttemptStatus(0x4001).vendor_code(), None);
+        assert_eq!(
+            LastAttemptStatus(0x4001).to_string(),
+            "unknown status 0x4001"
+        );
+    }
+}
@@ -0,0 +1,474 @@
+//! PI Hand-Off Block (HOB) list parsing.
+//!
+//! On firmware based on the Platform Initialization (PI) specification, the
+//! [`HAND_OFF_BLOCK_LIST_GUID`] configuration table entry points to the HOB
+//! list that the PEI phase passed to the DXE phase. It describes the memory
+//! and I/O resources of the system, the memory allocated during PEI, and
+//! the firmware volumes found by PEI, among others.
+//!
+//! [`HobList`] validates the layout of the list and iterates over its HOBs
+//! as typed [`Hob`]s.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::hob::{Hob, HobError, HobList, ResourceType};
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn print_resources(st: &SystemTable<Boot>) -> Result<(), HobError> {
+//!     let hobs = HobList::from_system_table(st)?;
+//!     for hob in &hobs {
+//!         match hob {
+//!             Hob::ResourceDescriptor(resource)
+//!                 if resource.resource_type == ResourceType::SYSTEM_MEMORY =>
+//!             {
+//!                 let start = resource.physical_start;
+//!                 let end = start + resource.resource_length;
+//!                 log::info!("DRAM: {start:#x}..{end:#x}");
+//!             }
+//!             Hob::FirmwareVolume(fv) => {
+//!                 log::info!("FV: {:#x} ({:#x} bytes)", fv.base_address, fv.length);
+//!             }
+//!             _ => {}
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`HAND_OFF_BLOCK_LIST_GUID`]: super::cfg::HAND_OFF_BLOCK_LIST_GUID
+
+use super::cfg::{self, ConfigTable};
+use super::{Boot, SystemTable};
+use crate::util::{read_at, Plain};
+use crate::Guid;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::{mem, slice};
+
+pub use uefi_raw::hob::{
+    BootMode, CpuHob, FirmwareVolume2Hob, FirmwareVolumeHob, GuidExtensionHob, HandoffInfoTable,
+    HobHeader, HobType, MemoryAllocationHob, ResourceAttribute, ResourceDescriptorHob,
+    ResourceType,
+};
+
+unsafe impl Plain for HobHeader {}
+unsafe impl Plain for HandoffInfoTable {}
+unsafe impl Plain for MemoryAllocationHob {}
+unsafe impl Plain for ResourceDescriptorHob {}
+unsafe impl Plain for GuidExtensionHob {}
+unsafe impl Plain for FirmwareVolumeHob {}
+unsafe impl Plain for FirmwareVolume2Hob {}
+unsafe impl Plain for CpuHob {}
+
+/// Errors returned when parsing the HOB list.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HobError {
+    /// The configuration table does not contain a HOB list.
+    NotFound,
+
+    /// The end-of-list address in the handoff information table is before
+    /// the start of the list, or cannot be represented as a pointer.
+    InvalidAddress,
+
+    /// The HOB at the given offset extends past the end of the list, or its
+    /// length is too small.
+    InvalidHob {
+        /// Offset of the HOB in the list.
+        offset: usize,
+    },
+}
+
+impl Display for HobError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "HOB list not found"),
+            Self::InvalidAddress => write!(f, "invalid HOB list end address"),
+            Self::InvalidHob { offset } => write!(f, "invalid HOB at offset {offset}"),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl core::error::Error for HobError {}
+
+unsafe impl ConfigTable for HandoffInfoTable {
+    const GUID: Guid = cfg::HAND_OFF_BLOCK_LIST_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let table = &*ptr.cast::<Self>();
+        let valid = table.header.hob_type == HobType::HANDOFF
+            && usize::from(table.header.hob_length) >= mem::size_of::<Self>();
+        valid.then_some(table)
+    }
+}
+
+/// Size of the header at the start of each HOB.
+const HEADER_SIZE: usize = mem::size_of::<HobHeader>();
+
+/// A validated HOB list.
+#[derive(Clone, Copy)]
+pub struct HobList<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> HobList<'a> {
+    /// Find and validate the HOB list in the configuration table.
+    pub fn from_system_table(st: &'a SystemTable<Boot>) -> Result<Self, HobError> {
+        let handoff = st
+            .find_config_table::<HandoffInfoTable>()
+            .ok_or(HobError::NotFound)?;
+        // SAFETY: while boot services are active, physical memory is
+        // identity mapped, and the HOB list is in memory that is not
+        // reused.
+        unsafe { Self::from_handoff(handoff) }
+    }
+
+    /// Validate the HOB list starting with the `handoff` HOB.
+    ///
+    /// # Safety
+    ///
+    /// Physical memory must be identity mapped, and the whole list, up to
+    /// the end-of-list HOB at `handoff.end_of_hob_list`, must remain valid
+    /// for the lifetime `'a`.
+    pub unsafe fn from_handoff(handoff: &'a HandoffInfoTable) -> Result<Self, HobError> {
+        let start = handoff as *const HandoffInfoTable as u64;
+        let length = handoff
+            .end_of_hob_list
+            .checked_sub(start)
+            .and_then(|length| usize::try_from(length).ok())
+            .and_then(|length| length.checked_add(HEADER_SIZE))
+            .ok_or(HobError::InvalidAddress)?;
+        let data = slice::from_raw_parts(start as *const u8, length);
+        Self::new(data)
+    }
+
+    /// Validate the HOBs in `data`.
+    ///
+    /// The list ends at the end-of-list HOB, or at the end of `data` if
+    /// there is none.
+    pub fn new(data: &'a [u8]) -> Result<Self, HobError> {
+        let mut offset = 0;
+        while offset < data.len() {
+            let header = parse_header(&data[offset..]).ok_or(HobError::InvalidHob { offset })?;
+            offset += usize::from(header.hob_length);
+            if header.hob_type == HobType::END_OF_HOB_LIST {
+                break;
+            }
+        }
+        Ok(Self {
+            data: &data[..offset],
+        })
+    }
+
+    /// Get the bytes of the HOB list.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Get the phase handoff information table, which is the first HOB of
+    /// the list.
+    #[must_use]
+    pub fn handoff(&self) -> Option<HandoffInfoTable> {
+        match self.iter().next()? {
+            Hob::Handoff(handoff) => Some(handoff),
+            _ => None,
+        }
+    }
+
+    /// Get an iterator over the HOBs in the list, excluding unused HOBs and
+    /// the end-of-list HOB.
+    #[must_use]
+    pub const fn iter(&self) -> HobIter<'a> {
+        HobIter { data: self.data }
+    }
+
+    /// Find the data of the first GUID extension HOB named `name`.
+    #[must_use]
+    pub fn find_guid_extension(&self, name: &Guid) -> Option<&'a [u8]> {
+        self.iter().find_map(|hob| match hob {
+            Hob::GuidExtension {
+                name: hob_name,
+                data,
+            } if hob_name == *name => Some(data),
+            _ => None,
+        })
+    }
+}
+
+impl Debug for HobList<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HobList")
+            .field("length", &self.data.len())
+            .finish()
+    }
+}
+
+impl<'a> IntoIterator for &HobList<'a> {
+    type Item = Hob<'a>;
+    type IntoIter = HobIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Read the header of the HOB at the start of `data`, checking that the
+/// HOB fits in `data`.
+fn parse_header(data: &[u8]) -> Option<HobHeader> {
+    let header = read_at::<HobHeader>(data, 0)?;
+    let length = usize::from(header.hob_length);
+    (length >= HEADER_SIZE && length <= data.len()).then_some(header)
+}
+
+/// Iterator over the HOBs of a [`HobList`].
+#[derive(Clone, Debug)]
+pub struct HobIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for HobIter<'a> {
+    type Item = Hob<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // The data was validated when `HobList` was created.
+            let header = parse_header(self.data)?;
+            let (hob, rest) = self.data.split_at(usize::from(header.hob_length));
+            self.data = rest;
+            match header.hob_type {
+                HobType::END_OF_HOB_LIST => {
+                    self.data = &[];
+                    return None;
+                }
+                HobType::UNUSED => continue,
+                _ => return Some(Hob::parse(header, hob)),
+            }
+        }
+    }
+}
+
+/// A HOB, parsed according to its type.
+///
+/// HOBs of known types that are too short to be valid are returned as
+/// [`Hob::Other`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Hob<'a> {
+    /// Phase handoff information table.
+    Handoff(HandoffInfoTable),
+
+    /// Memory allocation.
+    MemoryAllocation(MemoryAllocationHob),
+
+    /// Resource descriptor.
+    ResourceDescriptor(ResourceDescriptorHob),
+
+    /// GUID extension.
+    GuidExtension {
+        /// GUID defining the format of `data`.
+        name: Guid,
+        /// Data following the GUID extension header.
+        data: &'a [u8],
+    },
+
+    /// Firmware volume.
+    FirmwareVolume(FirmwareVolumeHob),
+
+    /// Firmware volume extracted from a file.
+    FirmwareVolume2(FirmwareVolume2Hob),
+
+    /// CPU address space sizes.
+    Cpu(CpuHob),
+
+    /// HOB of another type.
+    Other {
+        /// HOB header.
+        header: HobHeader,
+        /// All bytes of the HOB, including the header.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> Hob<'a> {
+    fn parse(header: HobHeader, data: &'a [u8]) -> Self {
+        let hob = match header.hob_type {
+            HobType::HANDOFF => read_at(data, 0).map(Self::Handoff),
+            HobType::MEMORY_ALLOCATION => read_at(data, 0).map(Self::MemoryAllocation),
+            HobType::RESOURCE_DESCRIPTOR => read_at(data, 0).map(Self::ResourceDescriptor),
+            HobType::GUID_EXTENSION => {
+                read_at::<GuidExtensionHob>(data, 0).map(|hob| Self::GuidExtension {
+                    name: hob.name,
+                    data: &data[mem::size_of::<GuidExtensionHob>()..],
+                })
+            }
+            HobType::FV => read_at(data, 0).map(Self::FirmwareVolume),
+            HobType::FV2 => read_at(data, 0).map(Self::FirmwareVolume2),
+            HobType::CPU => read_at(data, 0).map(Self::Cpu),
+            _ => None,
+        };
+        hob.unwrap_or(Self::Other { header, data })
+    }
+
+    /// Type of the HOB.
+    #[must_use]
+    pub const fn hob_type(&self) -> HobType {
+        match self {
+            Self::Handoff(_) => HobType::HANDOFF,
+            Self::MemoryAllocation(_) => HobType::MEMORY_ALLOCATION,
+            Self::ResourceDescriptor(_) => HobType::RESOURCE_DESCRIPTOR,
+            Self::GuidExtension { .. } => HobType::GUID_EXTENSION,
+            Self::FirmwareVolume(_) => HobType::FV,
+            Self::FirmwareVolume2(_) => HobType::FV2,
+            Self::Cpu(_) => HobType::CPU,
+            Self::Other { header, .. } => header.hob_type,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use crate::table::boot::MemoryType;
+    use alloc::vec::Vec;
+
+    fn header(hob_type: HobType, hob_length: usize) -> HobHeader {
+        HobHeader {
+            hob_type,
+            hob_length: hob_length as u16,
+            reserved: 0,
+        }
+    }
+
+    fn push<T>(buf: &mut Vec<u8>, value: T) {
+        let bytes = unsafe {
+            slice::from_raw_parts((&value as *const T).cast::<u8>(), mem::size_of::<T>())
+        };
+        buf.extend_from_slice(bytes);
+    }
+
+    #[test]
+    fn test_hob_list() {
+        let resource = ResourceDescriptorHob {
+            header: header(HobType::RESOURCE_DESCRIPTOR, 48),
+            owner: Guid::ZERO,
+            resource_type: ResourceType::SYSTEM_MEMORY,
+            resource_attribute: ResourceAttribute::PRESENT | ResourceAttribute::TESTED,
+            physical_start: 0x10_0000,
+            resource_length: 0x7ff0_0000,
+        };
+        let allocation = MemoryAllocationHob {
+            header: header(HobType::MEMORY_ALLOCATION, 48),
+            name: Guid::ZERO,
+            memory_base_address: 0x7000_0000,
+            memory_length: 0x1000,
+            memory_type: MemoryType::BOOT_SERVICES_DATA,
+            reserved: [0; 4],
+        };
+        let fv = FirmwareVolumeHob {
+            header: header(HobType::FV, 24),
+            base_address: 0xffc0_0000,
+            length: 0x40_0000,
+        };
+        let cpu = CpuHob {
+            header: header(HobType::CPU, 16),
+            size_of_memory_space: 48,
+            size_of_io_space: 16,
+            reserved: [0; 6],
+        };
+        let ext_name = guid!("3bdb3089-5662-42df-840e-3922ed6467c9");
+
+        let mut data = Vec::new();
+        push(
+            &mut data,
+            HandoffInfoTable {
+                header: header(HobType::HANDOFF, 56),
+                version: HandoffInfoTable::VERSION,
+                boot_mode: BootMode::FULL_CONFIGURATION,
+                memory_top: 0x8000_0000,
+                memory_bottom: 0x7000_0000,
+                free_memory_top: 0x7f00_0000,
+                free_memory_bottom: 0x7100_0000,
+                end_of_hob_list: 0,
+            },
+        );
+        push(&mut data, resource);
+        push(&mut data, allocation);
+        push(
+            &mut data,
+            GuidExtensionHob {
+                header: header(HobType::GUID_EXTENSION, 32),
+                name: ext_name,
+            },
+        );
+        data.extend([1, 2, 3, 4, 5, 6, 7, 8]);
+        push(&mut data, header(HobType::UNUSED, 16));
+        data.extend([0; 8]);
+        push(&mut data, fv);
+        push(&mut data, cpu);
+        push(&mut data, header(HobType::END_OF_HOB_LIST, 8));
+        // Trailing data after the end of the list is ignored.
+        data.extend([0xff; 8]);
+
+        let hobs = HobList::new(&data).unwrap();
+        assert_eq!(hobs.as_bytes().len(), data.len() - 8);
+        assert_eq!(hobs.handoff().unwrap().memory_top, 0x8000_0000);
+
+        let list: Vec<_> = hobs.iter().collect();
+        assert_eq!(list.len(), 6);
+        assert_eq!(list[1], Hob::ResourceDescriptor(resource));
+        assert_eq!(list[2], Hob::MemoryAllocation(allocation));
+        assert_eq!(
+            list[3],
+            Hob::GuidExtension {
+                name: ext_name,
+                data: &[1, 2, 3, 4, 5, 6, 7, 8],
+            }
+        );
+        assert_eq!(list[4], Hob::FirmwareVolume(fv));
+        assert_eq!(list[5], Hob::Cpu(cpu));
+        assert_eq!(list[5].hob_type(), HobType::CPU);
+        assert_eq!(
+            hobs.find_guid_extension(&ext_name),
+            Some([1, 2, 3, 4, 5, 6, 7, 8].as_slice())
+        );
+        assert_eq!(hobs.find_guid_extension(&Guid::ZERO), None);
+
+        // HOB extending past the end of the data.
+        assert_eq!(
+            HobList::new(&data[..120]).unwrap_err(),
+            HobError::InvalidHob { offset: 56 + 48 }
+        );
+    }
+
+    #[test]
+    fn test_from_handoff() {
+        // Use a `u64` buffer to get the alignment of the HOBs.
+        let mut buf = [0u64; 9];
+        let end = buf.as_ptr() as u64 + 56;
+        let handoff = HandoffInfoTable {
+            header: header(HobType::HANDOFF, 56),
+            version: HandoffInfoTable::VERSION,
+            boot_mode: BootMode::S3_RESUME,
+            memory_top: 0,
+            memory_bottom: 0,
+            free_memory_top: 0,
+            free_memory_bottom: 0,
+            end_of_hob_list: end,
+        };
+        unsafe {
+            buf.as_mut_ptr().cast::<HandoffInfoTable>().write(handoff);
+            buf.as_mut_ptr()
+                .add(7)
+                .cast::<HobHeader>()
+                .write(header(HobType::END_OF_HOB_LIST, 8));
+        }
+
+        let handoff = unsafe { HandoffInfoTable::from_ptr(buf.as_ptr().cast()) }.unwrap();
+        let hobs = unsafe { HobList::from_handoff(handoff) }.unwrap();
+        assert_eq!(hobs.as_bytes().len(), 64);
+        assert_eq!(hobs.handoff().unwrap().boot_mode, BootMode::S3_RESUME);
+        assert_eq!(hobs.iter().count(), 1);
+    }
+}
@@ -0,0 +1,315 @@
+//! EFI Memory Attributes Table.
+//!
+//! The memory map marks all memory used by runtime services with
+//! [`MemoryAttribute::RUNTIME`], but doesn't say which parts of a runtime
+//! image contain code and which contain data. The memory attributes table
+//! splits the runtime regions into parts that are either read-only
+//! ([`MemoryAttribute::READ_ONLY`]) or non-executable
+//! ([`MemoryAttribute::EXECUTE_PROTECT`]), so that an OS can map runtime
+//! services with W^X permissions.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::boot::MemoryMap;
+//! use uefi::table::memory_attributes::MemoryAttributesTable;
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn map_runtime_services(st: &SystemTable<Boot>, memory_map: &MemoryMap) {
+//!     let Some(table) = st.find_config_table::<MemoryAttributesTable>() else {
+//!         return;
+//!     };
+//!     for region in table.runtime_regions(memory_map) {
+//!         // Map `region` with the permissions given by `region.att`.
+//!     }
+//! }
+//! ```
+//!
+//! The table must be looked up before exiting boot services, since the
+//! system table can't be used afterwards. The table itself is stored in
+//! runtime services data, so it remains valid.
+
+use super::boot::{MemoryAttribute, MemoryDescriptor, MemoryMap, MemoryMapIter, MemoryType};
+use super::cfg::{self, ConfigTable};
+use crate::table::boot::PAGE_SIZE;
+use crate::Guid;
+use core::ffi::c_void;
+use core::mem;
+use ptr_meta::Pointee;
+use uefi_raw::table::memory_attributes::MemoryAttributesTable as RawMemoryAttributesTable;
+
+pub use uefi_raw::table::memory_attributes::MemoryAttributesTableFlags;
+
+/// EFI Memory Attributes Table.
+///
+/// Corresponds to the C type `EFI_MEMORY_ATTRIBUTES_TABLE`.
+#[derive(Debug, Pointee)]
+#[repr(C)]
+pub struct MemoryAttributesTable {
+    version: u32,
+    number_of_entries: u32,
+    descriptor_size: u32,
+    flags: MemoryAttributesTableFlags,
+    entries: [u8],
+}
+
+impl MemoryAttributesTable {
+    /// Version of the table format.
+    #[must_use]
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Flags of the table.
+    #[must_use]
+    pub fn flags(&self) -> MemoryAttributesTableFlags {
+        if self.version >= RawMemoryAttributesTable::VERSION_2 {
+            self.flags
+        } else {
+            MemoryAttributesTableFlags::empty()
+        }
+    }
+
+    /// Number of memory descriptors in the table.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.number_of_entries as usize
+    }
+
+    /// Returns true if the table has no memory descriptors.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.number_of_entries == 0
+    }
+
+    /// Get the memory descriptor at `index`.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&MemoryDescriptor> {
+        if index >= self.len() {
+            return None;
+        }
+        let offset = index * self.descriptor_size as usize;
+        // The alignment and size of the entries were checked on creation.
+        Some(unsafe { &*self.entries.as_ptr().add(offset).cast::<MemoryDescriptor>() })
+    }
+
+    /// Returns an iterator over the memory descriptors in the table.
+    ///
+    /// The descriptors are sorted by physical address. Their virtual
+    /// addresses are not set.
+    pub fn entries(&self) -> impl ExactSizeIterator<Item = &MemoryDescriptor> + '_ {
+        // OK to unwrap: the index is in bounds.
+        (0..self.len()).map(|index| self.get(index).unwrap())
+    }
+
+    /// Combine the table with the `memory_map` to get the runtime regions,
+    /// with the memory protections that should be used when mapping them.
+    ///
+    /// Runtime regions of the memory map that are described by the table
+    /// are replaced by the entries of the table, which get the virtual
+    /// addresses of the containing region. Other runtime regions, and the
+    /// parts of described regions that no entry covers, are returned with
+    /// the attributes of the memory map, except that memory that isn't
+    /// runtime code is marked as non-executable.
+    ///
+    /// The memory map should be sorted with [`MemoryMap::sort`].
+    #[must_use]
+    pub fn runtime_regions<'a>(&'a self, memory_map: &'a MemoryMap<'a>) -> RuntimeRegions<'a> {
+        RuntimeRegions {
+            table: self,
+            regions: memory_map.entries(),
+            current: None,
+        }
+    }
+}
+
+unsafe impl ConfigTable for MemoryAttributesTable {
+    const GUID: Guid = cfg::MEMORY_ATTRIBUTES_TABLE_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let header = &*ptr.cast::<RawMemoryAttributesTable>();
+        let descriptor_size = header.descriptor_size as usize;
+        let valid = (RawMemoryAttributesTable::VERSION_1..=RawMemoryAttributesTable::VERSION_2)
+            .contains(&header.version)
+            && descriptor_size >= mem::size_of::<MemoryDescriptor>()
+            && descriptor_size % mem::align_of::<MemoryDescriptor>() == 0
+            && ptr.align_offset(mem::align_of::<MemoryDescriptor>()) == 0;
+        if !valid {
+            return None;
+        }
+        let table: *const Self = ptr_meta::from_raw_parts(
+            ptr.cast(),
+            header.number_of_entries as usize * descriptor_size,
+        );
+        Some(&*table)
+    }
+}
+
+/// Iterator over the runtime regions of the memory map, returned by
+/// [`MemoryAttributesTable::runtime_regions`].
+#[derive(Debug, Clone)]
+pub struct RuntimeRegions<'a> {
+    table: &'a MemoryAttributesTable,
+    regions: MemoryMapIter<'a>,
+    /// Region of the memory map that is described by the table, the
+    /// physical address up to which it was returned, and the index of the
+    /// next table entry to check for it.
+    current: Option<(&'a MemoryDescriptor, u64, usize)>,
+}
+
+impl Iterator for RuntimeRegions<'_> {
+    type Item = MemoryDescriptor;
+
+    fn next(&mut self) -> Option<MemoryDescriptor> {
+        loop {
+            if let Some((region, done, index)) = self.current {
+                let entry = self
+                    .table
+                    .entries()
+                    .enumerate()
+                    .skip(index)
+                    .find(|(_, entry)| contains(region, entry));
+                match entry {
+                    Some((index, entry)) if entry.phys_start > done => {
+                        self.current = Some((region, entry.phys_start, index));
+                        return Some(uncovered(region, done, entry.phys_start));
+                    }
+                    Some((index, entry)) => {
+                        self.current = Some((region, done.max(end(entry)), index + 1));
+                        return Some(MemoryDescriptor {
+                            virt_start: region.virt_start + (entry.phys_start - region.phys_start),
+                            ..*entry
+                        });
+                    }
+                    None => {
+                        self.current = None;
+                        if done < end(region) {
+                            return Some(uncovered(region, done, end(region)));
+                        }
+                    }
+                }
+            }
+
+            let region = self
+                .regions
+                .find(|region| region.att.contains(MemoryAttribute::RUNTIME))?;
+            if self.table.entries().any(|entry| contains(region, entry)) {
+                self.current = Some((region, region.phys_start, 0));
+                continue;
+            }
+            return Some(uncovered(region, region.phys_start, end(region)));
+        }
+    }
+}
+
+/// Physical end address of the memory of `desc`.
+const fn end(desc: &MemoryDescriptor) -> u64 {
+    desc.phys_start + desc.page_count * PAGE_SIZE as u64
+}
+
+/// Returns true if the memory of `entry` is within `region`.
+fn contains(region: &MemoryDescriptor, entry: &MemoryDescriptor) -> bool {
+    entry.phys_start >= region.phys_start && end(entry) <= end(region)
+}
+
+/// Part of `region` from `start` to `end` that isn't described by the table.
+fn uncovered(region: &MemoryDescriptor, start: u64, end: u64) -> MemoryDescriptor {
+    let mut desc = MemoryDescriptor {
+        phys_start: start,
+        virt_start: region.virt_start + (start - region.phys_start),
+        page_count: (end - start) / PAGE_SIZE as u64,
+        ..*region
+    };
+    if desc.ty != MemoryType::RUNTIME_SERVICES_CODE {
+        desc.att |= MemoryAttribute::EXECUTE_PROTECT;
+    }
+    desc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use core::slice;
+
+    fn desc(
+        ty: MemoryType,
+        phys_start: u64,
+        page_count: u64,
+        att: MemoryAttribute,
+    ) -> MemoryDescriptor {
+        MemoryDescriptor {
+            ty,
+            phys_start,
+            virt_start: 0,
+            page_count,
+            att,
+        }
+    }
+
+    #[test]
+    fn test_runtime_regions() {
+        let rt = MemoryAttribute::RUNTIME;
+        let ro = rt | MemoryAttribute::READ_ONLY;
+        let xp = rt | MemoryAttribute::EXECUTE_PROTECT;
+        let code = MemoryType::RUNTIME_SERVICES_CODE;
+        let data = MemoryType::RUNTIME_SERVICES_DATA;
+
+        let entries = [
+            desc(code, 0x10000, 1, xp),
+            desc(code, 0x11000, 2, ro),
+            desc(code, 0x14000, 1, xp),
+        ];
+        let mut table = Vec::<u64>::new();
+        let entry_words = mem::size_of::<MemoryDescriptor>() / mem::size_of::<u64>();
+        table.resize(2 + entries.len() * entry_words, 0);
+        table[0] = 2 | (3 << 32);
+        table[1] = mem::size_of::<MemoryDescriptor>() as u64;
+        unsafe {
+            table
+                .as_mut_ptr()
+                .add(2)
+                .cast::<MemoryDescriptor>()
+                .copy_from_nonoverlapping(entries.as_ptr(), entries.len());
+        }
+        let table = unsafe { MemoryAttributesTable::from_ptr(table.as_ptr().cast()) }.unwrap();
+        assert_eq!(table.version(), 2);
+        assert_eq!(table.flags(), MemoryAttributesTableFlags::empty());
+        assert_eq!(table.len(), 3);
+        assert!(table.entries().eq(&entries));
+
+        let mut map_entries = [
+            desc(
+                MemoryType::CONVENTIONAL,
+                0x0,
+                0x10,
+                MemoryAttribute::WRITE_BACK,
+            ),
+            desc(code, 0x10000, 6, rt),
+            desc(data, 0x16000, 2, rt),
+        ];
+        map_entries[1].virt_start = 0xffff_0000_0001_0000;
+        let buf = unsafe {
+            slice::from_raw_parts_mut(
+                map_entries.as_mut_ptr().cast::<u8>(),
+                mem::size_of_val(&map_entries),
+            )
+        };
+        let map = MemoryMap::from_raw(buf, mem::size_of::<MemoryDescriptor>());
+
+        let regions: Vec<_> = table.runtime_regions(&map).collect();
+        let mut expected = [
+            desc(code, 0x10000, 1, xp),
+            desc(code, 0x11000, 2, ro),
+            // Not described by the table.
+            desc(code, 0x13000, 1, rt),
+            desc(code, 0x14000, 1, xp),
+            desc(code, 0x15000, 1, rt),
+            desc(data, 0x16000, 2, xp),
+        ];
+        for desc in &mut expected[..5] {
+            desc.virt_start = 0xffff_0000_0000_0000 + desc.phys_start;
+        }
+        assert_eq!(regions, expected);
+    }
+}
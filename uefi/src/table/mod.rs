@@ -1,8 +1,15 @@
 //! Standard UEFI tables.
 
+pub mod acpi;
 pub mod boot;
 pub mod cfg;
+pub mod debug_image_info;
+pub mod dxe_services;
+pub mod esrt;
+pub mod hob;
+pub mod memory_attributes;
 pub mod runtime;
+pub mod smbios;
 
 mod header;
 mod system;
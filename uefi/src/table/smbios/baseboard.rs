@@ -0,0 +1,94 @@
+use super::{impl_typed_structure, Structure};
+
+/// Baseboard (or module) information structure (type 2).
+#[derive(Clone, Copy, Debug)]
+pub struct BaseboardInformation<'a>(Structure<'a>);
+
+impl_typed_structure!(BaseboardInformation, 2, 0x08);
+
+impl<'a> BaseboardInformation<'a> {
+    /// Name of the board manufacturer.
+    #[must_use]
+    pub fn manufacturer(&self) -> Option<&'a str> {
+        self.0.string_at(0x04)
+    }
+
+    /// Product name of the board.
+    #[must_use]
+    pub fn product(&self) -> Option<&'a str> {
+        self.0.string_at(0x05)
+    }
+
+    /// Version of the board.
+    #[must_use]
+    pub fn version(&self) -> Option<&'a str> {
+        self.0.string_at(0x06)
+    }
+
+    /// Serial number of the board.
+    #[must_use]
+    pub fn serial_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x07)
+    }
+
+    /// Asset tag of the board.
+    #[must_use]
+    pub fn asset_tag(&self) -> Option<&'a str> {
+        self.0.string_at(0x08)
+    }
+
+    /// Feature flags, e.g. whether the board is replaceable.
+    #[must_use]
+    pub fn feature_flags(&self) -> Option<u8> {
+        self.0.read(0x09)
+    }
+
+    /// Location of the board within the chassis.
+    #[must_use]
+    pub fn location_in_chassis(&self) -> Option<&'a str> {
+        self.0.string_at(0x0a)
+    }
+
+    /// Handle of the chassis structure (type 3) containing the board.
+    #[must_use]
+    pub fn chassis_handle(&self) -> Option<u16> {
+        self.0.read(0x0b)
+    }
+
+    /// Type of the board.
+    #[must_use]
+    pub fn board_type(&self) -> Option<BaseboardType> {
+        self.0.read(0x0d).map(BaseboardType)
+    }
+}
+
+newtype_enum! {
+/// Type of a board, as reported by [`BaseboardInformation`].
+pub enum BaseboardType: u8 => {
+    /// Unknown.
+    UNKNOWN = 0x01,
+    /// Other.
+    OTHER = 0x02,
+    /// Server blade.
+    SERVER_BLADE = 0x03,
+    /// Connectivity switch.
+    CONNECTIVITY_SWITCH = 0x04,
+    /// System management module.
+    SYSTEM_MANAGEMENT_MODULE = 0x05,
+    /// Processor module.
+    PROCESSOR_MODULE = 0x06,
+    /// I/O module.
+    IO_MODULE = 0x07,
+    /// Memory module.
+    MEMORY_MODULE = 0x08,
+    /// Daughter board.
+    DAUGHTER_BOARD = 0x09,
+    /// Motherboard, including processor, memory and I/O.
+    MOTHERBOARD = 0x0a,
+    /// Processor and memory module.
+    PROCESSOR_MEMORY_MODULE = 0x0b,
+    /// Processor and I/O module.
+    PROCESSOR_IO_MODULE = 0x0c,
+    /// Interconnect board.
+    INTERCONNECT_BOARD = 0x0d,
+}}
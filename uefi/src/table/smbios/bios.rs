@@ -0,0 +1,68 @@
+use super::{impl_typed_structure, Structure};
+
+/// BIOS information structure (type 0).
+#[derive(Clone, Copy, Debug)]
+pub struct BiosInformation<'a>(Structure<'a>);
+
+impl_typed_structure!(BiosInformation, 0, 0x12);
+
+impl<'a> BiosInformation<'a> {
+    /// Name of the BIOS vendor.
+    #[must_use]
+    pub fn vendor(&self) -> Option<&'a str> {
+        self.0.string_at(0x04)
+    }
+
+    /// Free-form version of the BIOS.
+    #[must_use]
+    pub fn version(&self) -> Option<&'a str> {
+        self.0.string_at(0x05)
+    }
+
+    /// Release date of the BIOS, in `mm/dd/yyyy` format.
+    #[must_use]
+    pub fn release_date(&self) -> Option<&'a str> {
+        self.0.string_at(0x08)
+    }
+
+    /// Size of the physical device containing the BIOS, in bytes.
+    #[must_use]
+    pub fn rom_size(&self) -> Option<u64> {
+        match self.0.read::<u8>(0x09)? {
+            0xff => {
+                // The extended size is used for sizes of 16 MiB and more.
+                let extended = self.0.read::<u16>(0x18)?;
+                let size = u64::from(extended & 0x3fff);
+                match extended >> 14 {
+                    0 => Some(size << 20),
+                    1 => Some(size << 30),
+                    _ => None,
+                }
+            }
+            size => Some((u64::from(size) + 1) << 16),
+        }
+    }
+
+    /// Characteristics of the BIOS, such as the supported boot methods.
+    #[must_use]
+    pub fn characteristics(&self) -> u64 {
+        // OK to unwrap: the minimum length was checked on creation.
+        self.0.read(0x0a).unwrap()
+    }
+
+    /// Major and minor release of the system firmware, or `None` if not
+    /// supported.
+    #[must_use]
+    pub fn bios_release(&self) -> Option<(u8, u8)> {
+        let release = (self.0.read(0x14)?, self.0.read(0x15)?);
+        (release != (0xff, 0xff)).then_some(release)
+    }
+
+    /// Major and minor release of the embedded controller firmware, or
+    /// `None` if not supported.
+    #[must_use]
+    pub fn embedded_controller_release(&self) -> Option<(u8, u8)> {
+        let release = (self.0.read(0x16)?, self.0.read(0x17)?);
+        (release != (0xff, 0xff)).then_some(release)
+    }
+}
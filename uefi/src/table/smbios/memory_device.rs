@@ -0,0 +1,277 @@
+use super::{impl_typed_structure, Structure};
+
+/// Memory device structure (type 17), describing a memory slot and the
+/// module installed in it.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryDevice<'a>(Structure<'a>);
+
+impl_typed_structure!(MemoryDevice, 17, 0x15);
+
+impl<'a> MemoryDevice<'a> {
+    /// Handle of the physical memory array structure (type 16) containing
+    /// the device.
+    #[must_use]
+    pub fn physical_memory_array_handle(&self) -> u16 {
+        // OK to unwrap: the minimum length was checked on creation.
+        self.0.read(0x04).unwrap()
+    }
+
+    /// Total width of the device in bits, including error correction bits,
+    /// or `None` if unknown.
+    #[must_use]
+    pub fn total_width(&self) -> Option<u16> {
+        self.0.read(0x08).filter(|width| *width != 0xffff)
+    }
+
+    /// Data width of the device in bits, or `None` if unknown.
+    #[must_use]
+    pub fn data_width(&self) -> Option<u16> {
+        self.0.read(0x0a).filter(|width| *width != 0xffff)
+    }
+
+    /// Size of the installed module in bytes, or `None` if unknown.
+    ///
+    /// A size of zero means that no module is installed.
+    #[must_use]
+    pub fn size(&self) -> Option<u64> {
+        match self.0.read::<u16>(0x0c).unwrap() {
+            0xffff => None,
+            // The extended size is used for sizes of 32 GiB - 1 MiB and more.
+            0x7fff => {
+                let extended = self.0.read::<u32>(0x1c)?;
+                Some(u64::from(extended & 0x7fff_ffff) << 20)
+            }
+            size if size & 0x8000 != 0 => Some(u64::from(size & 0x7fff) << 10),
+            size => Some(u64::from(size) << 20),
+        }
+    }
+
+    /// Returns `true` if a module is installed in the slot.
+    #[must_use]
+    pub fn is_installed(&self) -> bool {
+        self.size() != Some(0)
+    }
+
+    /// Form factor of the device.
+    #[must_use]
+    pub fn form_factor(&self) -> MemoryFormFactor {
+        MemoryFormFactor(self.0.read(0x0e).unwrap())
+    }
+
+    /// Label of the socket or board position, e.g. `"DIMM 0"`.
+    #[must_use]
+    pub fn device_locator(&self) -> Option<&'a str> {
+        self.0.string_at(0x10)
+    }
+
+    /// Label of the bank containing the device.
+    #[must_use]
+    pub fn bank_locator(&self) -> Option<&'a str> {
+        self.0.string_at(0x11)
+    }
+
+    /// Type of the memory.
+    #[must_use]
+    pub fn memory_type(&self) -> MemoryType {
+        MemoryType(self.0.read(0x12).unwrap())
+    }
+
+    /// Maximum speed of the device in MT/s, or `None` if unknown.
+    #[must_use]
+    pub fn speed(&self) -> Option<u32> {
+        self.speed_at(0x15, 0x54)
+    }
+
+    /// Speed that the device is configured for in MT/s, or `None` if
+    /// unknown.
+    #[must_use]
+    pub fn configured_speed(&self) -> Option<u32> {
+        self.speed_at(0x20, 0x58)
+    }
+
+    /// Read a speed from the word field at `offset`, or from the dword
+    /// field at `extended_offset` if the speed doesn't fit in a word.
+    fn speed_at(&self, offset: usize, extended_offset: usize) -> Option<u32> {
+        match self.0.read::<u16>(offset)? {
+            0 => None,
+            0xffff => self.0.read(extended_offset).filter(|speed| *speed != 0),
+            speed => Some(u32::from(speed)),
+        }
+    }
+
+    /// Name of the module manufacturer.
+    #[must_use]
+    pub fn manufacturer(&self) -> Option<&'a str> {
+        self.0.string_at(0x17)
+    }
+
+    /// Serial number of the module.
+    #[must_use]
+    pub fn serial_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x18)
+    }
+
+    /// Asset tag of the module.
+    #[must_use]
+    pub fn asset_tag(&self) -> Option<&'a str> {
+        self.0.string_at(0x19)
+    }
+
+    /// Part number of the module.
+    #[must_use]
+    pub fn part_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x1a)
+    }
+
+    /// Number of ranks of the module, or `None` if unknown.
+    #[must_use]
+    pub fn rank(&self) -> Option<u8> {
+        self.0
+            .read::<u8>(0x1b)
+            .map(|attributes| attributes & 0xf)
+            .filter(|rank| *rank != 0)
+    }
+}
+
+newtype_enum! {
+/// Form factor of a [`MemoryDevice`].
+pub enum MemoryFormFactor: u8 => {
+    /// Other.
+    OTHER = 0x01,
+    /// Unknown.
+    UNKNOWN = 0x02,
+    /// SIMM.
+    SIMM = 0x03,
+    /// SIP.
+    SIP = 0x04,
+    /// Chip.
+    CHIP = 0x05,
+    /// DIP.
+    DIP = 0x06,
+    /// ZIP.
+    ZIP = 0x07,
+    /// Proprietary card.
+    PROPRIETARY_CARD = 0x08,
+    /// DIMM.
+    DIMM = 0x09,
+    /// TSOP.
+    TSOP = 0x0a,
+    /// Row of chips.
+    ROW_OF_CHIPS = 0x0b,
+    /// RIMM.
+    RIMM = 0x0c,
+    /// SODIMM.
+    SODIMM = 0x0d,
+    /// SRIMM.
+    SRIMM = 0x0e,
+    /// FB-DIMM.
+    FB_DIMM = 0x0f,
+    /// Die.
+    DIE = 0x10,
+}}
+
+newtype_enum! {
+/// Type of the memory of a [`MemoryDevice`].
+pub enum MemoryType: u8 => {
+    /// Other.
+    OTHER = 0x01,
+    /// Unknown.
+    UNKNOWN = 0x02,
+    /// DRAM.
+    DRAM = 0x03,
+    /// SDRAM.
+    SDRAM = 0x0f,
+    /// DDR.
+    DDR = 0x12,
+    /// DDR2.
+    DDR2 = 0x13,
+    /// DDR2 FB-DIMM.
+    DDR2_FB_DIMM = 0x14,
+    /// DDR3.
+    DDR3 = 0x18,
+    /// FBD2.
+    FBD2 = 0x19,
+    /// DDR4.
+    DDR4 = 0x1a,
+    /// LPDDR.
+    LPDDR = 0x1b,
+    /// LPDDR2.
+    LPDDR2 = 0x1c,
+    /// LPDDR3.
+    LPDDR3 = 0x1d,
+    /// LPDDR4.
+    LPDDR4 = 0x1e,
+    /// Logical non-volatile device.
+    LOGICAL_NON_VOLATILE_DEVICE = 0x1f,
+    /// HBM.
+    HBM = 0x20,
+    /// HBM2.
+    HBM2 = 0x21,
+    /// DDR5.
+    DDR5 = 0x22,
+    /// LPDDR5.
+    LPDDR5 = 0x23,
+    /// HBM3.
+    HBM3 = 0x24,
+}}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::make_structure;
+    use super::super::{SmbiosTable, SmbiosVersion, TypedStructure};
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_memory_device() {
+        let mut body = [0; 0x28 - 4];
+        let mut set = |offset: usize, bytes: &[u8]| {
+            body[offset - 4..][..bytes.len()].copy_from_slice(bytes);
+        };
+        set(0x04, &0x1000u16.to_le_bytes());
+        set(0x08, &72u16.to_le_bytes());
+        set(0x0a, &64u16.to_le_bytes());
+        set(0x0c, &0x7fffu16.to_le_bytes());
+        set(0x0e, &[0x09]);
+        set(0x10, &[1, 2, 0x1a]);
+        set(0x15, &3200u16.to_le_bytes());
+        set(0x17, &[3, 4, 0, 5]);
+        set(0x1b, &[2]);
+        set(0x1c, &0x10000u32.to_le_bytes());
+        let mut data = make_structure(17, 0x1100, &body, &["DIMM 0", "BANK 0", "Acme", "1234"]);
+        data.extend(make_structure(17, 0x1101, &body[..0x15 - 4], &[]));
+
+        let table = SmbiosTable::new(&data, SmbiosVersion::new(3, 2)).unwrap();
+        let dimms: Vec<_> = table.structures_of_type::<MemoryDevice>().collect();
+        assert_eq!(dimms.len(), 2);
+
+        let dimm = dimms[0];
+        assert_eq!(dimm.physical_memory_array_handle(), 0x1000);
+        assert_eq!(dimm.total_width(), Some(72));
+        assert_eq!(dimm.data_width(), Some(64));
+        assert_eq!(dimm.size(), Some(64 << 30));
+        assert!(dimm.is_installed());
+        assert_eq!(dimm.form_factor(), MemoryFormFactor::DIMM);
+        assert_eq!(dimm.device_locator(), Some("DIMM 0"));
+        assert_eq!(dimm.bank_locator(), Some("BANK 0"));
+        assert_eq!(dimm.memory_type(), MemoryType::DDR4);
+        assert_eq!(dimm.speed(), Some(3200));
+        assert_eq!(dimm.configured_speed(), None);
+        assert_eq!(dimm.manufacturer(), Some("Acme"));
+        assert_eq!(dimm.serial_number(), Some("1234"));
+        assert_eq!(dimm.asset_tag(), None);
+        assert_eq!(dimm.rank(), Some(2));
+
+        // Fields added in later versions are missing.
+        let dimm = dimms[1];
+        assert_eq!(dimm.size(), None);
+        assert_eq!(dimm.speed(), None);
+        assert_eq!(dimm.manufacturer(), None);
+
+        // Too short to be a valid memory device.
+        let data = make_structure(17, 0x1102, &body[..4], &[]);
+        let table = SmbiosTable::new(&data, SmbiosVersion::new(3, 2)).unwrap();
+        let structure = table.structures().next().unwrap();
+        assert!(MemoryDevice::from_structure(structure).is_none());
+    }
+}
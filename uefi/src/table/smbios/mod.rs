@@ -0,0 +1,648 @@
+//! SMBIOS structure table parsing.
+//!
+//! The firmware publishes the SMBIOS tables through the [`SMBIOS3_GUID`]
+//! configuration table entry, which points to a 64-bit
+//! [`Smbios3EntryPoint`], and/or through the [`SMBIOS_GUID`] entry, which
+//! points to a 32-bit [`SmbiosEntryPoint`]. Both entry points contain the
+//! address of the structure table.
+//!
+//! [`SmbiosTable`] validates the layout of the structure table and iterates
+//! over its [`Structure`]s, taking care of the string set that follows the
+//! formatted area of each structure. Typed views are provided for some
+//! common structures:
+//!
+//! * [`BiosInformation`] (type 0)
+//! * [`SystemInformation`] (type 1)
+//! * [`BaseboardInformation`] (type 2)
+//! * [`ProcessorInformation`] (type 4)
+//! * [`MemoryDevice`] (type 17)
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::table::smbios::{MemoryDevice, SmbiosError, SmbiosTable, SystemInformation};
+//! use uefi::table::{Boot, SystemTable};
+//!
+//! fn print_inventory(st: &SystemTable<Boot>) -> Result<(), SmbiosError> {
+//!     let smbios = SmbiosTable::from_system_table(st)?;
+//!     if let Some(system) = smbios.find::<SystemInformation>() {
+//!         log::info!("serial number: {:?}", system.serial_number());
+//!     }
+//!     for dimm in smbios.structures_of_type::<MemoryDevice>() {
+//!         log::info!("{:?}: {:?} bytes", dimm.device_locator(), dimm.size());
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`SMBIOS_GUID`]: super::cfg::SMBIOS_GUID
+//! [`SMBIOS3_GUID`]: super::cfg::SMBIOS3_GUID
+
+mod baseboard;
+mod bios;
+mod memory_device;
+mod processor;
+mod system;
+
+pub use baseboard::{BaseboardInformation, BaseboardType};
+pub use bios::BiosInformation;
+pub use memory_device::{MemoryDevice, MemoryFormFactor, MemoryType};
+pub use processor::ProcessorInformation;
+pub use system::SystemInformation;
+
+use super::cfg::{self, ConfigTable};
+use super::{Boot, SystemTable};
+use crate::util::{checksum_is_valid, read_at, Plain};
+use crate::Guid;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::{mem, slice, str};
+
+/// Errors returned when parsing SMBIOS tables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SmbiosError {
+    /// The configuration table does not contain an SMBIOS entry point.
+    EntryPointNotFound,
+
+    /// The structure table address is null or cannot be represented as a
+    /// pointer.
+    InvalidAddress,
+
+    /// The structure at the given offset extends past the end of the table,
+    /// or its length is too small.
+    InvalidStructure {
+        /// Offset of the structure in the table.
+        offset: usize,
+    },
+}
+
+impl Display for SmbiosError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EntryPointNotFound => write!(f, "SMBIOS entry point not found"),
+            Self::InvalidAddress => write!(f, "invalid SMBIOS structure table address"),
+            Self::InvalidStructure { offset } => {
+                write!(f, "invalid SMBIOS structure at offset {offset}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl core::error::Error for SmbiosError {}
+
+/// SMBIOS version, used to determine which fields are present.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SmbiosVersion {
+    /// Major version.
+    pub major: u8,
+    /// Minor version.
+    pub minor: u8,
+}
+
+impl SmbiosVersion {
+    /// Create a version from its major and minor parts.
+    #[must_use]
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Display for SmbiosVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// SMBIOS 2.1 (32-bit) entry point.
+///
+/// This is the structure pointed to by the [`SMBIOS_GUID`] configuration
+/// table entry.
+///
+/// [`SMBIOS_GUID`]: cfg::SMBIOS_GUID
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed)]
+pub struct SmbiosEntryPoint {
+    /// Must be [`SmbiosEntryPoint::ANCHOR`].
+    pub anchor: [u8; 4],
+    /// Makes the bytes of the entry point sum to zero.
+    pub checksum: u8,
+    /// Length of the entry point in bytes.
+    pub length: u8,
+    /// Major SMBIOS version.
+    pub major_version: u8,
+    /// Minor SMBIOS version.
+    pub minor_version: u8,
+    /// Size of the largest structure in the table.
+    pub max_structure_size: u16,
+    /// Revision of the entry point format.
+    pub entry_point_revision: u8,
+    /// Revision-specific data.
+    pub formatted_area: [u8; 5],
+    /// Must be [`SmbiosEntryPoint::INTERMEDIATE_ANCHOR`].
+    pub intermediate_anchor: [u8; 5],
+    /// Makes the bytes of the intermediate entry point sum to zero.
+    pub intermediate_checksum: u8,
+    /// Length of the structure table in bytes.
+    pub structure_table_length: u16,
+    /// Physical address of the structure table.
+    pub structure_table_address: u32,
+    /// Number of structures in the table.
+    pub number_of_structures: u16,
+    /// SMBIOS version in BCD format.
+    pub bcd_revision: u8,
+}
+
+impl SmbiosEntryPoint {
+    /// Anchor string at the start of the entry point.
+    pub const ANCHOR: [u8; 4] = *b"_SM_";
+
+    /// Anchor string of the intermediate entry point.
+    pub const INTERMEDIATE_ANCHOR: [u8; 5] = *b"_DMI_";
+
+    /// Offset of the intermediate entry point.
+    const INTERMEDIATE_OFFSET: usize = 16;
+}
+
+unsafe impl ConfigTable for SmbiosEntryPoint {
+    const GUID: Guid = cfg::SMBIOS_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let table = &*ptr.cast::<Self>();
+        // Version 2.1 of the specification listed the length as 0x1e,
+        // although the structure is 0x1f bytes long.
+        let length = usize::from(table.length).max(mem::size_of::<Self>());
+        let bytes = slice::from_raw_parts(ptr.cast::<u8>(), length);
+        let valid = table.anchor == Self::ANCHOR
+            && table.intermediate_anchor == Self::INTERMEDIATE_ANCHOR
+            && checksum_is_valid(&bytes[..usize::from(table.length)])
+            && checksum_is_valid(&bytes[Self::INTERMEDIATE_OFFSET..mem::size_of::<Self>()]);
+        valid.then_some(table)
+    }
+}
+
+/// SMBIOS 3.0 (64-bit) entry point.
+///
+/// This is the structure pointed to by the [`SMBIOS3_GUID`] configuration
+/// table entry.
+///
+/// [`SMBIOS3_GUID`]: cfg::SMBIOS3_GUID
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed)]
+pub struct Smbios3EntryPoint {
+    /// Must be [`Smbios3EntryPoint::ANCHOR`].
+    pub anchor: [u8; 5],
+    /// Makes the bytes of the entry point sum to zero.
+    pub checksum: u8,
+    /// Length of the entry point in bytes.
+    pub length: u8,
+    /// Major SMBIOS version.
+    pub major_version: u8,
+    /// Minor SMBIOS version.
+    pub minor_version: u8,
+    /// SMBIOS specification revision.
+    pub docrev: u8,
+    /// Revision of the entry point format.
+    pub entry_point_revision: u8,
+    /// Reserved.
+    pub reserved: u8,
+    /// Maximum size of the structure table in bytes. The table ends with an
+    /// end-of-table structure (type 127).
+    pub structure_table_max_size: u32,
+    /// Physical address of the structure table.
+    pub structure_table_address: u64,
+}
+
+impl Smbios3EntryPoint {
+    /// Anchor string at the start of the entry point.
+    pub const ANCHOR: [u8; 5] = *b"_SM3_";
+}
+
+unsafe impl ConfigTable for Smbios3EntryPoint {
+    const GUID: Guid = cfg::SMBIOS3_GUID;
+
+    unsafe fn from_ptr<'a>(ptr: *const c_void) -> Option<&'a Self> {
+        let table = &*ptr.cast::<Self>();
+        let length = usize::from(table.length);
+        let valid = table.anchor == Self::ANCHOR
+            && length >= mem::size_of::<Self>()
+            && checksum_is_valid(slice::from_raw_parts(ptr.cast::<u8>(), length));
+        valid.then_some(table)
+    }
+}
+
+/// Type of the end-of-table structure.
+const END_OF_TABLE: u8 = 127;
+
+/// Size of the header at the start of each structure.
+const HEADER_SIZE: usize = 4;
+
+/// A validated SMBIOS structure table.
+#[derive(Clone, Copy)]
+pub struct SmbiosTable<'a> {
+    data: &'a [u8],
+    version: SmbiosVersion,
+}
+
+impl<'a> SmbiosTable<'a> {
+    /// Find and validate the SMBIOS structure table in the configuration
+    /// table.
+    ///
+    /// The SMBIOS 3.0 entry point is preferred, falling back to the SMBIOS
+    /// 2.1 entry point.
+    pub fn from_system_table(st: &'a SystemTable<Boot>) -> Result<Self, SmbiosError> {
+        // SAFETY: while boot services are active, physical memory is
+        // identity mapped, and the firmware keeps the SMBIOS tables in
+        // memory that is not reused.
+        unsafe {
+            if let Some(entry_point) = st.find_config_table::<Smbios3EntryPoint>() {
+                Self::from_entry_point3(entry_point)
+            } else if let Some(entry_point) = st.find_config_table::<SmbiosEntryPoint>() {
+                Self::from_entry_point(entry_point)
+            } else {
+                Err(SmbiosError::EntryPointNotFound)
+            }
+        }
+    }
+
+    /// Validate the structure table referenced by a 32-bit entry point.
+    ///
+    /// # Safety
+    ///
+    /// Physical memory must be identity mapped, and the structure table
+    /// must remain valid for the lifetime `'a`.
+    pub unsafe fn from_entry_point(entry_point: &'a SmbiosEntryPoint) -> Result<Self, SmbiosError> {
+        let version = SmbiosVersion::new(entry_point.major_version, entry_point.minor_version);
+        let data = table_slice(
+            u64::from(entry_point.structure_table_address),
+            usize::from(entry_point.structure_table_length),
+        )?;
+        Self::new(data, version)
+    }
+
+    /// Validate the structure table referenced by a 64-bit entry point.
+    ///
+    /// # Safety
+    ///
+    /// Physical memory must be identity mapped, and the structure table
+    /// must remain valid for the lifetime `'a`.
+    pub unsafe fn from_entry_point3(
+        entry_point: &'a Smbios3EntryPoint,
+    ) -> Result<Self, SmbiosError> {
+        let version = SmbiosVersion::new(entry_point.major_version, entry_point.minor_version);
+        let data = table_slice(
+            entry_point.structure_table_address,
+            entry_point.structure_table_max_size as usize,
+        )?;
+        Self::new(data, version)
+    }
+
+    /// Validate the structures in `data`.
+    ///
+    /// The table ends at the end-of-table structure (type 127), or at the
+    /// end of `data` if there is none.
+    pub fn new(data: &'a [u8], version: SmbiosVersion) -> Result<Self, SmbiosError> {
+        let mut offset = 0;
+        while offset < data.len() {
+            let (structure, size) = Structure::parse(&data[offset..], version)
+                .ok_or(SmbiosError::InvalidStructure { offset })?;
+            offset += size;
+            if structure.structure_type() == END_OF_TABLE {
+                break;
+            }
+        }
+        Ok(Self {
+            data: &data[..offset],
+            version,
+        })
+    }
+
+    /// Version of the SMBIOS specification that the table conforms to.
+    #[must_use]
+    pub const fn version(&self) -> SmbiosVersion {
+        self.version
+    }
+
+    /// Get the bytes of the structure table.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Get an iterator over the structures in the table, excluding the
+    /// end-of-table structure.
+    #[must_use]
+    pub const fn structures(&self) -> StructureIter<'a> {
+        StructureIter {
+            data: self.data,
+            version: self.version,
+        }
+    }
+
+    /// Get an iterator over the structures of type `T`.
+    ///
+    /// Structures that are too short to be valid are skipped.
+    pub fn structures_of_type<T: TypedStructure<'a>>(&self) -> impl Iterator<Item = T> + 'a {
+        self.structures().filter_map(T::from_structure)
+    }
+
+    /// Find the first structure of type `T`.
+    #[must_use]
+    pub fn find<T: TypedStructure<'a>>(&self) -> Option<T> {
+        self.structures_of_type().next()
+    }
+
+    /// Find the structure with the given handle.
+    #[must_use]
+    pub fn find_by_handle(&self, handle: u16) -> Option<Structure<'a>> {
+        self.structures()
+            .find(|structure| structure.handle() == handle)
+    }
+}
+
+impl Debug for SmbiosTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmbiosTable")
+            .field("version", &self.version)
+            .field("length", &self.data.len())
+            .finish()
+    }
+}
+
+impl<'a> IntoIterator for &SmbiosTable<'a> {
+    type Item = Structure<'a>;
+    type IntoIter = StructureIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.structures()
+    }
+}
+
+/// Create a slice of the structure table at physical `address`.
+unsafe fn table_slice<'a>(address: u64, length: usize) -> Result<&'a [u8], SmbiosError> {
+    match usize::try_from(address) {
+        Ok(0) | Err(_) => Err(SmbiosError::InvalidAddress),
+        Ok(address) => Ok(slice::from_raw_parts(address as *const u8, length)),
+    }
+}
+
+/// Iterator over the structures of an [`SmbiosTable`].
+#[derive(Clone, Debug)]
+pub struct StructureIter<'a> {
+    data: &'a [u8],
+    version: SmbiosVersion,
+}
+
+impl<'a> Iterator for StructureIter<'a> {
+    type Item = Structure<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The data was validated when `SmbiosTable` was created.
+        let (structure, size) = Structure::parse(self.data, self.version)?;
+        if structure.structure_type() == END_OF_TABLE {
+            self.data = &[];
+            return None;
+        }
+        self.data = &self.data[size..];
+        Some(structure)
+    }
+}
+
+/// A single SMBIOS structure, consisting of a formatted area and a set of
+/// strings.
+#[derive(Clone, Copy)]
+pub struct Structure<'a> {
+    formatted: &'a [u8],
+    strings: &'a [u8],
+    version: SmbiosVersion,
+}
+
+impl<'a> Structure<'a> {
+    /// Parse the structure at the start of `data`, returning it along with
+    /// its total size. Returns `None` if the structure is truncated.
+    fn parse(data: &'a [u8], version: SmbiosVersion) -> Option<(Self, usize)> {
+        let length = usize::from(*data.get(1)?);
+        if length < HEADER_SIZE || length > data.len() {
+            return None;
+        }
+        // The string set is terminated by two nul bytes. If there are no
+        // strings, it consists of only the terminator.
+        let end = data[length..].windows(2).position(|pair| pair == [0, 0])? + length;
+        let structure = Self {
+            formatted: &data[..length],
+            strings: &data[length..end],
+            version,
+        };
+        Some((structure, end + 2))
+    }
+
+    /// Type of the structure.
+    #[must_use]
+    pub fn structure_type(&self) -> u8 {
+        self.formatted[0]
+    }
+
+    /// Handle that identifies the structure within the table.
+    #[must_use]
+    pub fn handle(&self) -> u16 {
+        // OK to unwrap: the header length was checked in `parse`.
+        self.read(2).unwrap()
+    }
+
+    /// Version of the table containing the structure.
+    #[must_use]
+    pub const fn version(&self) -> SmbiosVersion {
+        self.version
+    }
+
+    /// Get the formatted area of the structure, including the header.
+    #[must_use]
+    pub const fn formatted(&self) -> &'a [u8] {
+        self.formatted
+    }
+
+    /// Read a `T` at `offset` in the formatted area, or `None` if the
+    /// structure is too short.
+    #[must_use]
+    pub fn read<T: Plain>(&self, offset: usize) -> Option<T> {
+        read_at(self.formatted, offset)
+    }
+
+    /// Get an iterator over the raw strings of the structure. Strings are
+    /// referenced by their one-based index in this set.
+    pub fn strings(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.strings
+            .split(|byte| *byte == 0)
+            .take_while(|string| !string.is_empty())
+    }
+
+    /// Get the string with the one-based `index`.
+    ///
+    /// Returns `None` if `index` is zero (which means that there is no
+    /// string), out of range, or the string is not valid UTF-8.
+    #[must_use]
+    pub fn string(&self, index: u8) -> Option<&'a str> {
+        let index = usize::from(index).checked_sub(1)?;
+        str::from_utf8(self.strings().nth(index)?).ok()
+    }
+
+    /// Get the string referenced by the byte at `offset` in the formatted
+    /// area.
+    #[must_use]
+    pub fn string_at(&self, offset: usize) -> Option<&'a str> {
+        self.string(self.read(offset)?)
+    }
+}
+
+impl Debug for Structure<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Structure")
+            .field("type", &self.structure_type())
+            .field("handle", &self.handle())
+            .field("length", &self.formatted.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A typed view of an SMBIOS structure.
+pub trait TypedStructure<'a>: Sized + 'a {
+    /// Structure type.
+    const TYPE: u8;
+
+    /// Create the view, or return `None` if the structure has a different
+    /// type or is too short to contain the fields that are present in all
+    /// versions of the specification.
+    fn from_structure(structure: Structure<'a>) -> Option<Self>;
+}
+
+/// Implement [`TypedStructure`] and the `structure` getter for a wrapper
+/// around [`Structure`] whose formatted area is at least `$min_length`
+/// bytes long.
+macro_rules! impl_typed_structure {
+    ($ty:ident, $type_id:expr, $min_length:expr) => {
+        impl<'a> $crate::table::smbios::TypedStructure<'a> for $ty<'a> {
+            const TYPE: u8 = $type_id;
+
+            fn from_structure(structure: $crate::table::smbios::Structure<'a>) -> Option<Self> {
+                let valid = structure.structure_type() == Self::TYPE
+                    && structure.formatted().len() >= $min_length;
+                valid.then_some(Self(structure))
+            }
+        }
+
+        impl<'a> $ty<'a> {
+            /// Get the underlying structure.
+            #[must_use]
+            pub const fn structure(&self) -> $crate::table::smbios::Structure<'a> {
+                self.0
+            }
+        }
+    };
+}
+use impl_typed_structure;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    /// Build a structure from its formatted area (excluding the header) and
+    /// strings.
+    pub(crate) fn make_structure(ty: u8, handle: u16, body: &[u8], strings: &[&str]) -> Vec<u8> {
+        let mut v = alloc::vec![ty, u8::try_from(HEADER_SIZE + body.len()).unwrap()];
+        v.extend(handle.to_le_bytes());
+        v.extend(body);
+        for s in strings {
+            v.extend(s.as_bytes());
+            v.push(0);
+        }
+        if strings.is_empty() {
+            v.push(0);
+        }
+        v.push(0);
+        v
+    }
+
+    #[test]
+    fn test_smbios_table() {
+        let mut data = make_structure(0x80, 1, &[1, 2], &["one", "two"]);
+        data.extend(make_structure(0x81, 2, &[], &[]));
+        data.extend(make_structure(END_OF_TABLE, 3, &[], &[]));
+        let table_len = data.len();
+        // Padding after the end-of-table structure is ignored.
+        data.extend([0xff; 7]);
+
+        let table = SmbiosTable::new(&data, SmbiosVersion::new(3, 4)).unwrap();
+        assert_eq!(table.as_bytes().len(), table_len);
+        assert_eq!(table.version().to_string(), "3.4");
+
+        let structures: Vec<_> = table.structures().collect();
+        assert_eq!(structures.len(), 2);
+        let first = structures[0];
+        assert_eq!(first.structure_type(), 0x80);
+        assert_eq!(first.handle(), 1);
+        assert_eq!(first.formatted(), [0x80, 6, 1, 0, 1, 2]);
+        assert_eq!(first.read::<u8>(5), Some(2));
+        assert_eq!(first.read::<u16>(5), None);
+        assert_eq!(first.strings().collect::<Vec<_>>(), [b"one", b"two"]);
+        assert_eq!(first.string(0), None);
+        assert_eq!(first.string(2), Some("two"));
+        assert_eq!(first.string(3), None);
+        assert_eq!(first.string_at(4), Some("one"));
+
+        assert_eq!(structures[1].strings().count(), 0);
+        assert_eq!(table.find_by_handle(2).unwrap().structure_type(), 0x81);
+        assert!(table.find_by_handle(3).is_none());
+    }
+
+    #[test]
+    fn test_smbios_table_invalid() {
+        let mut data = make_structure(0x80, 1, &[], &["abc"]);
+        let offset = data.len();
+        data.extend(make_structure(0x80, 2, &[], &["abc"]));
+
+        // Missing string set terminator.
+        assert_eq!(
+            SmbiosTable::new(&data[..data.len() - 1], SmbiosVersion::new(3, 0)).unwrap_err(),
+            SmbiosError::InvalidStructure { offset }
+        );
+
+        // Formatted area longer than the table.
+        data[offset + 1] = 0x40;
+        assert_eq!(
+            SmbiosTable::new(&data, SmbiosVersion::new(3, 0)).unwrap_err(),
+            SmbiosError::InvalidStructure { offset }
+        );
+    }
+
+    #[test]
+    fn test_entry_points() {
+        let mut ep3 = Vec::new();
+        ep3.extend(Smbios3EntryPoint::ANCHOR);
+        ep3.extend([0, 0x18, 3, 2, 0, 1, 0]);
+        ep3.extend(0x1000u32.to_le_bytes());
+        ep3.extend(0x8000_0000u64.to_le_bytes());
+        ep3[5] = ep3.iter().fold(0u8, |sum, b| sum.wrapping_sub(*b));
+        let entry_point = unsafe { Smbios3EntryPoint::from_ptr(ep3.as_ptr().cast()) }.unwrap();
+        assert_eq!({ entry_point.structure_table_address }, 0x8000_0000);
+        ep3[20] ^= 1;
+        assert!(unsafe { Smbios3EntryPoint::from_ptr(ep3.as_ptr().cast()) }.is_none());
+
+        let mut ep = Vec::new();
+        ep.extend(SmbiosEntryPoint::ANCHOR);
+        ep.extend([0, 0x1f, 2, 8, 0xff, 0, 0, 0, 0, 0, 0, 0]);
+        ep.extend(SmbiosEntryPoint::INTERMEDIATE_ANCHOR);
+        ep.push(0);
+        ep.extend(0x100u16.to_le_bytes());
+        ep.extend(0x000f_0000u32.to_le_bytes());
+        ep.extend(4u16.to_le_bytes());
+        ep.push(0x28);
+        ep[21] = ep[16..].iter().fold(0u8, |sum, b| sum.wrapping_sub(*b));
+        ep[4] = ep.iter().fold(0u8, |sum, b| sum.wrapping_sub(*b));
+        let entry_point = unsafe { SmbiosEntryPoint::from_ptr(ep.as_ptr().cast()) }.unwrap();
+        assert_eq!({ entry_point.structure_table_address }, 0x000f_0000);
+        ep[16] = b'X';
+        assert!(unsafe { SmbiosEntryPoint::from_ptr(ep.as_ptr().cast()) }.is_none());
+    }
+}
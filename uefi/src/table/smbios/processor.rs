@@ -0,0 +1,99 @@
+use super::{impl_typed_structure, Structure};
+
+/// Processor information structure (type 4).
+#[derive(Clone, Copy, Debug)]
+pub struct ProcessorInformation<'a>(Structure<'a>);
+
+impl_typed_structure!(ProcessorInformation, 4, 0x1a);
+
+impl<'a> ProcessorInformation<'a> {
+    /// Designation of the socket, e.g. `"CPU 0"`.
+    #[must_use]
+    pub fn socket_designation(&self) -> Option<&'a str> {
+        self.0.string_at(0x04)
+    }
+
+    /// Name of the processor manufacturer.
+    #[must_use]
+    pub fn manufacturer(&self) -> Option<&'a str> {
+        self.0.string_at(0x07)
+    }
+
+    /// Raw processor identification data. On x86 this contains the
+    /// `EAX` and `EDX` values of CPUID leaf 1.
+    #[must_use]
+    pub fn processor_id(&self) -> u64 {
+        // OK to unwrap: the minimum length was checked on creation.
+        self.0.read(0x08).unwrap()
+    }
+
+    /// Version of the processor, e.g. the brand string.
+    #[must_use]
+    pub fn version(&self) -> Option<&'a str> {
+        self.0.string_at(0x10)
+    }
+
+    /// Maximum supported speed in MHz, or `None` if unknown.
+    #[must_use]
+    pub fn max_speed_mhz(&self) -> Option<u16> {
+        self.0.read(0x14).filter(|speed| *speed != 0)
+    }
+
+    /// Speed at boot in MHz, or `None` if unknown.
+    #[must_use]
+    pub fn current_speed_mhz(&self) -> Option<u16> {
+        self.0.read(0x16).filter(|speed| *speed != 0)
+    }
+
+    /// Returns `true` if the socket is populated.
+    #[must_use]
+    pub fn is_populated(&self) -> bool {
+        self.0.read::<u8>(0x18).unwrap() & (1 << 6) != 0
+    }
+
+    /// Serial number of the processor.
+    #[must_use]
+    pub fn serial_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x20)
+    }
+
+    /// Asset tag of the processor.
+    #[must_use]
+    pub fn asset_tag(&self) -> Option<&'a str> {
+        self.0.string_at(0x21)
+    }
+
+    /// Part number of the processor.
+    #[must_use]
+    pub fn part_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x22)
+    }
+
+    /// Read a count from the byte field at `offset`, or from the word
+    /// field at `extended_offset` if the count doesn't fit in a byte.
+    fn count(&self, offset: usize, extended_offset: usize) -> Option<u16> {
+        match self.0.read::<u8>(offset)? {
+            0 => None,
+            0xff => self.0.read(extended_offset).filter(|count| *count != 0),
+            count => Some(u16::from(count)),
+        }
+    }
+
+    /// Number of cores per socket, or `None` if unknown.
+    #[must_use]
+    pub fn core_count(&self) -> Option<u16> {
+        self.count(0x23, 0x2a)
+    }
+
+    /// Number of enabled cores per socket, or `None` if unknown.
+    #[must_use]
+    pub fn core_enabled(&self) -> Option<u16> {
+        self.count(0x24, 0x2c)
+    }
+
+    /// Number of threads per socket, or `None` if unknown.
+    #[must_use]
+    pub fn thread_count(&self) -> Option<u16> {
+        self.count(0x25, 0x2e)
+    }
+}
@@ -0,0 +1,106 @@
+use super::{impl_typed_structure, SmbiosVersion, Structure};
+use crate::Guid;
+
+/// System information structure (type 1).
+#[derive(Clone, Copy, Debug)]
+pub struct SystemInformation<'a>(Structure<'a>);
+
+impl_typed_structure!(SystemInformation, 1, 0x08);
+
+impl<'a> SystemInformation<'a> {
+    /// Name of the system manufacturer.
+    #[must_use]
+    pub fn manufacturer(&self) -> Option<&'a str> {
+        self.0.string_at(0x04)
+    }
+
+    /// Product name of the system.
+    #[must_use]
+    pub fn product_name(&self) -> Option<&'a str> {
+        self.0.string_at(0x05)
+    }
+
+    /// Version of the system.
+    #[must_use]
+    pub fn version(&self) -> Option<&'a str> {
+        self.0.string_at(0x06)
+    }
+
+    /// Serial number of the system.
+    #[must_use]
+    pub fn serial_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x07)
+    }
+
+    /// Universally unique ID of the system.
+    ///
+    /// Returns `None` if the structure doesn't contain a UUID, or if the
+    /// UUID is not present or not set (all zero or all one bits).
+    #[must_use]
+    pub fn uuid(&self) -> Option<Guid> {
+        let mut bytes = self.0.read::<[u8; 16]>(0x08)?;
+        if bytes == [0; 16] || bytes == [0xff; 16] {
+            return None;
+        }
+        // Before version 2.6, the specification didn't define the byte
+        // order of the first three fields, and they were usually stored in
+        // network byte order. Since then they are little-endian, like in a
+        // `Guid`.
+        if self.0.version() < SmbiosVersion::new(2, 6) {
+            bytes[..4].reverse();
+            bytes[4..6].reverse();
+            bytes[6..8].reverse();
+        }
+        Some(Guid::from_bytes(bytes))
+    }
+
+    /// Stock keeping unit (SKU) number of the system.
+    #[must_use]
+    pub fn sku_number(&self) -> Option<&'a str> {
+        self.0.string_at(0x19)
+    }
+
+    /// Family of the system.
+    #[must_use]
+    pub fn family(&self) -> Option<&'a str> {
+        self.0.string_at(0x1a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::make_structure;
+    use super::super::SmbiosTable;
+    use super::*;
+    use crate::guid;
+
+    #[test]
+    fn test_system_information() {
+        let mut body = [1, 2, 0, 3].to_vec();
+        body.extend([
+            0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ]);
+        let data = make_structure(1, 0x100, &body, &["Acme", "Widget", "SN123"]);
+
+        let table = SmbiosTable::new(&data, SmbiosVersion::new(2, 6)).unwrap();
+        let system = table.find::<SystemInformation>().unwrap();
+        assert_eq!(system.manufacturer(), Some("Acme"));
+        assert_eq!(system.product_name(), Some("Widget"));
+        assert_eq!(system.version(), None);
+        assert_eq!(system.serial_number(), Some("SN123"));
+        assert_eq!(
+            system.uuid(),
+            Some(guid!("00112233-4455-6677-8899-aabbccddeeff"))
+        );
+        assert_eq!(system.sku_number(), None);
+
+        // Older versions store the UUID in network byte order.
+        let table = SmbiosTable::new(&data, SmbiosVersion::new(2, 5)).unwrap();
+        let system = table.find::<SystemInformation>().unwrap();
+        assert_eq!(
+            system.uuid(),
+            Some(guid!("33221100-5544-7766-8899-aabbccddeeff"))
+        );
+    }
+}
@@ -88,6 +88,38 @@ impl<View: SystemTableView> SystemTable<View> {
         }
     }
 
+    /// Find the vendor table of type `T` in the config table.
+    ///
+    /// Returns `None` if there is no entry with [`T::GUID`], or if the
+    /// table fails the validation done by [`T::from_ptr`], e.g. because of
+    /// an invalid signature or checksum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use uefi::prelude::*;
+    /// use uefi::table::cfg::PropertiesTable;
+    ///
+    /// fn memory_protection(st: &SystemTable<Boot>) {
+    ///     if let Some(table) = st.find_config_table::<PropertiesTable>() {
+    ///         log::info!("memory protection: {:?}", table.memory_protection);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`T::GUID`]: cfg::ConfigTable::GUID
+    /// [`T::from_ptr`]: cfg::ConfigTable::from_ptr
+    #[must_use]
+    pub fn find_config_table<T: cfg::ConfigTable + ?Sized>(&self) -> Option<&T> {
+        let entry = self
+            .config_table()
+            .iter()
+            .find(|entry| entry.guid == T::GUID && !entry.address.is_null())?;
+        // SAFETY: the entry has the table's GUID, and the table is valid
+        // for at least as long as the system table.
+        unsafe { T::from_ptr(entry.address) }
+    }
+
     /// Creates a new `SystemTable<View>` from a raw address. The address might
     /// come from the Multiboot2 information structure or something similar.
     ///
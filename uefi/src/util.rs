@@ -25,6 +25,36 @@ pub const fn usize_from_u32(val: u32) -> usize {
     }
 }
 
+/// Marker for plain data types that are valid for any bit pattern, so they
+/// can be read from arbitrary bytes with [`read_at`].
+///
+/// # Safety
+///
+/// Implementers must not have padding, invalid bit patterns, or pointers.
+pub unsafe trait Plain: Copy {}
+
+unsafe impl Plain for u8 {}
+unsafe impl Plain for u16 {}
+unsafe impl Plain for u32 {}
+unsafe impl Plain for u64 {}
+unsafe impl<const N: usize> Plain for [u8; N] {}
+unsafe impl Plain for crate::Guid {}
+
+/// Read a `T` from `data` at byte `offset`. Alignment is not required, and
+/// integers are read in native byte order.
+/// Returns `None` if the value doesn't fit within `data`.
+pub fn read_at<T: Plain>(data: &[u8], offset: usize) -> Option<T> {
+    let bytes = data.get(offset..offset.checked_add(mem::size_of::<T>())?)?;
+    // SAFETY: the slice is large enough, and `T` is valid for any bytes.
+    Some(unsafe { bytes.as_ptr().cast::<T>().read_unaligned() })
+}
+
+/// Check that the bytes of `data` sum to zero, as required by the checksum
+/// fields of ACPI and SMBIOS structures.
+pub fn checksum_is_valid(data: &[u8]) -> bool {
+    data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) == 0
+}
+
 /// Get the raw pointer from `opt`, defaulting to `null_mut`.
 pub fn opt_nonnull_to_ptr<T>(opt: Option<NonNull<T>>) -> *mut T {
     opt.map(NonNull::as_ptr).unwrap_or(ptr::null_mut())
@@ -39,4 +69,21 @@ mod tests {
         assert_eq!(usize_from_u32(0), 0usize);
         assert_eq!(usize_from_u32(u32::MAX), 4294967295usize);
     }
+
+    #[test]
+    fn test_checksum_is_valid() {
+        assert!(checksum_is_valid(&[]));
+        assert!(checksum_is_valid(&[0x80, 0x7f, 0x01]));
+        assert!(!checksum_is_valid(&[0x80, 0x7f]));
+    }
+
+    #[test]
+    fn test_read_at() {
+        let data = [1, 2, 3, 4, 5];
+        assert_eq!(read_at::<u8>(&data, 4), Some(5));
+        assert_eq!(read_at::<u16>(&data, 1), Some(u16::from_ne_bytes([2, 3])));
+        assert_eq!(read_at::<[u8; 2]>(&data, 3), Some([4, 5]));
+        assert_eq!(read_at::<u32>(&data, 2), None);
+        assert_eq!(read_at::<u64>(&data, usize::MAX), None);
+    }
 }
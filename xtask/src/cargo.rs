@@ -48,11 +48,13 @@ impl Package {
 pub enum Feature {
     // `uefi` features.
     Alloc,
+    Async,
     GlobalAllocator,
     Logger,
     Unstable,
     PanicHandler,
     Qemu,
+    RandCore,
 
     // `uefi-test-runner` features.
     DebugSupport,
@@ -67,11 +69,13 @@ impl Feature {
     fn as_str(&self) -> &'static str {
         match self {
             Self::Alloc => "alloc",
+            Self::Async => "async",
             Self::GlobalAllocator => "global_allocator",
             Self::Logger => "logger",
             Self::Unstable => "unstable",
             Self::PanicHandler => "panic_handler",
             Self::Qemu => "qemu",
+            Self::RandCore => "rand_core",
 
             Self::DebugSupport => "uefi-test-runner/debug_support",
             Self::MultiProcessor => "uefi-test-runner/multi_processor",
@@ -87,11 +91,13 @@ impl Feature {
         match package {
             Package::Uefi => vec![
                 Self::Alloc,
+                Self::Async,
                 Self::GlobalAllocator,
                 Self::Logger,
                 Self::Unstable,
                 Self::PanicHandler,
                 Self::Qemu,
+                Self::RandCore,
             ],
             Package::UefiTestRunner => {
                 vec![